@@ -75,10 +75,94 @@ pub struct WorkerConfig {
 
     /// NUMA node to allocate memory from (if applicable)
     pub numa_node: Option<u32>,
+
+    /// Enable the HTTP admin/introspection endpoint exposing
+    /// `WorkerManager::snapshot` (task states, error counts)
+    #[serde(default = "default_worker_admin_enabled")]
+    pub admin_enabled: bool,
+
+    /// Port the admin endpoint listens on, when enabled
+    #[serde(default = "default_worker_admin_port")]
+    pub admin_port: u16,
+
+    /// Maximum number of times a batch's requests are requeued after a
+    /// retryable `forward_batch` failure before being dropped with an error
+    #[serde(default = "default_max_batch_retries")]
+    pub max_batch_retries: u32,
+
+    /// Base delay for a retried request's exponential backoff:
+    /// `delay = min(base * 2^attempt, batch_retry_max_delay_ms) + jitter`
+    #[serde(default = "default_batch_retry_base_delay_ms")]
+    pub batch_retry_base_delay_ms: u64,
+
+    /// Ceiling on the backoff delay before a batch retry, before jitter
+    #[serde(default = "default_batch_retry_max_delay_ms")]
+    pub batch_retry_max_delay_ms: u64,
+
+    /// Where the last tranquility value set through the admin
+    /// `/control/tranquility` endpoint is persisted, so a restart resumes
+    /// at the operator's last setting instead of reverting to
+    /// `batching.tranquility`. Persistence is disabled when unset.
+    #[serde(default)]
+    pub tranquility_state_path: Option<PathBuf>,
+
+    /// This worker's tensor-parallel shard assignment, if its layer group's
+    /// weight matrices are split column/row-wise across a group of peers
+    /// rather than each worker holding them whole. `None` (the default)
+    /// means no tensor-parallel sharding: this worker holds its full layer
+    /// group's weights.
+    #[serde(default)]
+    pub tensor_parallel: Option<TensorParallelConfig>,
+}
+
+/// A worker's rank within its tensor-parallel group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TensorParallelConfig {
+    /// This worker's index among the tensor-parallel group
+    pub rank: usize,
+
+    /// Number of workers sharing each layer's weight matrices
+    pub world_size: usize,
+}
+
+impl WorkerConfig {
+    /// Validate this worker's layer group against the model's full topology
+    ///
+    /// Checks that `layer_group` is exactly one of `model.layer_groups` (not
+    /// just shape-compatible), and that the pipeline-neighbor wiring is
+    /// consistent with this worker's position: the first group must have no
+    /// `prev_worker_endpoint`, and the group owning `total_layers` must have
+    /// no `next_worker_endpoint`.
+    pub fn validate_topology(&self, model: &ModelConfig) -> Result<()> {
+        let group = &self.layer_group;
+
+        if !model.layer_groups.iter().any(|g| g == group) {
+            return Err(ChatLoopError::config(format!(
+                "Worker layer group [{}, {}) is not one of the model's configured layer groups",
+                group.start_layer, group.end_layer
+            )));
+        }
+
+        if group.start_layer == 0 && self.prev_worker_endpoint.is_some() {
+            return Err(ChatLoopError::config(format!(
+                "Worker owning the first layer group [{}, {}) must not have a prev_worker_endpoint",
+                group.start_layer, group.end_layer
+            )));
+        }
+
+        if group.end_layer == group.total_layers && self.next_worker_endpoint.is_some() {
+            return Err(ChatLoopError::config(format!(
+                "Worker owning the last layer group [{}, {}) must not have a next_worker_endpoint",
+                group.start_layer, group.end_layer
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 /// Layer group configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LayerGroupConfig {
     /// Starting layer index (0-based)
     pub start_layer: usize,
@@ -100,6 +184,63 @@ pub struct LayerGroupConfig {
 
     /// Intermediate dimension (FFN)
     pub intermediate_dim: usize,
+
+    /// Which normalization `forward_layer` applies for attention_norm/ffn_norm
+    #[serde(default = "default_norm_type")]
+    pub norm_type: NormType,
+
+    /// Base for RoPE's per-pair frequencies, `theta_i = rope_base^(-2i/head_dim)`
+    #[serde(default = "default_rope_base")]
+    pub rope_base: f32,
+
+    /// Number of key/value heads, for grouped-query attention. `0` (the
+    /// default) means "not set, use `num_heads`" i.e. ordinary multi-head
+    /// attention. When set below `num_heads`, each group of
+    /// `num_heads / num_kv_heads` query heads shares one K/V head, as in
+    /// LLaMA-3/Mistral/Qwen2-style checkpoints.
+    #[serde(default = "default_num_kv_heads")]
+    pub num_kv_heads: usize,
+}
+
+/// Pre-normalization variant applied before attention and the FFN block
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NormType {
+    /// Mean-subtracting LayerNorm with a learned bias-free affine scale
+    LayerNorm,
+
+    /// RMSNorm: scale by the root-mean-square only, no mean subtraction.
+    /// What LLaMA, CodeGeeX4, and other decoder-only checkpoints actually use.
+    RmsNorm,
+}
+
+impl Default for NormType {
+    fn default() -> Self {
+        NormType::RmsNorm
+    }
+}
+
+impl std::str::FromStr for NormType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "layer_norm" | "layernorm" => Ok(NormType::LayerNorm),
+            "rms_norm" | "rmsnorm" => Ok(NormType::RmsNorm),
+            other => Err(format!("Unknown norm type: {}", other)),
+        }
+    }
+}
+
+fn default_norm_type() -> NormType {
+    NormType::RmsNorm
+}
+
+fn default_rope_base() -> f32 {
+    10000.0
+}
+
+fn default_num_kv_heads() -> usize {
+    0
 }
 
 /// Batching configuration
@@ -116,6 +257,91 @@ pub struct BatchingConfig {
 
     /// Timeout for queue operations
     pub queue_timeout_ms: u64,
+
+    /// Enable adaptive batching: shorten the window and apply backpressure
+    /// as queue depth rises, instead of always waiting the full window
+    #[serde(default = "default_backpressure")]
+    pub backpressure: bool,
+
+    /// Queue depth, as a fraction of `max_queue_size`, above which new
+    /// requests are rejected and the upstream connection is paused
+    #[serde(default = "default_high_watermark")]
+    pub high_watermark: f64,
+
+    /// Queue depth, as a fraction of `max_queue_size`, below which a paused
+    /// upstream connection resumes sending requests
+    #[serde(default = "default_low_watermark")]
+    pub low_watermark: f64,
+
+    /// Hard cap on the sum of prefill tokens (plus padding) admitted into a
+    /// single batch, independent of `max_batch_size`
+    #[serde(default = "default_max_batch_prefill_tokens")]
+    pub max_batch_prefill_tokens: usize,
+
+    /// Hard cap on the sum of all tokens (prefill and already-generated,
+    /// plus padding) a batch may hold at once - the KV-cache budget a
+    /// continuously-extended batch must stay under
+    #[serde(default = "default_max_batch_total_tokens")]
+    pub max_batch_total_tokens: usize,
+
+    /// Minimum ratio of waiting-request tokens to already-running tokens
+    /// required before a running batch is interrupted to admit them; below
+    /// this the waiting requests simply wait for the next natural gap
+    #[serde(default = "default_waiting_served_ratio")]
+    pub waiting_served_ratio: f32,
+
+    /// Floor of the adaptive batching window, used when queue depth is at or
+    /// below `low_watermark` so light load doesn't pay for a window nothing
+    /// will fill
+    #[serde(default = "default_min_batching_window_ms")]
+    pub min_batching_window_ms: u64,
+
+    /// Ceiling of the adaptive batching window, used when queue depth is at
+    /// or above `high_watermark` so heavy load trades a little latency for
+    /// bigger, more efficient batches
+    #[serde(default = "default_max_batching_window_ms")]
+    pub max_batching_window_ms: u64,
+
+    /// Maximum number of batches the worker loop dispatches to the
+    /// blocking pool at once, instead of running `forward_batch` serially
+    /// on the async reactor
+    #[serde(default = "default_batch_parallelism")]
+    pub batch_parallelism: usize,
+
+    /// Throttle on worker CPU/GPU usage (cf. Garage's scrub throttle): after
+    /// processing a batch that took `duration`, the loop sleeps for
+    /// `duration * tranquility` before pulling the next one, so a
+    /// tranquility of 2 keeps the worker busy at most one-third of the
+    /// time. Zero (the default) disables throttling entirely. Also
+    /// adjustable at runtime through the admin `/control/tranquility`
+    /// endpoint - see `chatloop_worker::tranquility`.
+    #[serde(default = "default_tranquility")]
+    pub tranquility: f64,
+}
+
+/// Weighted fair dispatch and anti-starvation aging for `PriorityScheduler`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityConfig {
+    /// Minimum number of slots in every batch reserved for the low-priority
+    /// tier, out of the scheduler's `max_batch_size`, so a steady stream of
+    /// high-priority work can never starve it completely
+    #[serde(default = "default_low_reserved_slots")]
+    pub low_reserved_slots: usize,
+
+    /// Minimum number of slots in every batch reserved for the
+    /// normal-priority tier
+    #[serde(default = "default_normal_reserved_slots")]
+    pub normal_reserved_slots: usize,
+
+    /// Age at which a queued low-priority request is promoted to normal
+    /// priority
+    #[serde(default = "default_low_max_wait_ms")]
+    pub low_max_wait_ms: u64,
+
+    /// Age at which a queued normal-priority request is promoted to high
+    /// priority
+    #[serde(default = "default_normal_max_wait_ms")]
+    pub normal_max_wait_ms: u64,
 }
 
 /// Coordinator-specific configuration
@@ -142,6 +368,105 @@ pub struct CoordinatorConfig {
 
     /// Maximum concurrent requests
     pub max_concurrent_requests: usize,
+
+    /// Enable the self-tuning concurrency governor: treat
+    /// `max_concurrent_requests` as a ceiling and continuously retune the
+    /// actual in-flight limit from measured end-to-end request latency,
+    /// instead of always admitting up to the static cap
+    #[serde(default = "default_adaptive_concurrency")]
+    pub adaptive_concurrency: bool,
+
+    /// Floor the governor will never shrink the in-flight limit below
+    #[serde(default = "default_min_concurrent_requests")]
+    pub min_concurrent_requests: usize,
+
+    /// Number of recent request completion latencies kept to compute the
+    /// observed latency the governor compares against its baseline
+    #[serde(default = "default_concurrency_latency_window")]
+    pub concurrency_latency_window: usize,
+
+    /// Observed latency above `baseline * this` factor is treated as
+    /// queueing and triggers a multiplicative back-off of the limit
+    #[serde(default = "default_concurrency_latency_threshold")]
+    pub concurrency_latency_threshold: f64,
+
+    /// Factor the limit is multiplied by on back-off (e.g. 0.7 shrinks it by 30%)
+    #[serde(default = "default_concurrency_backoff_factor")]
+    pub concurrency_backoff_factor: f64,
+
+    /// Base delay for a failed worker's exponential health-check backoff:
+    /// `next_try = now + min(base * 2^error_count, retry_max_backoff_secs)`
+    #[serde(default = "default_retry_base_backoff_ms")]
+    pub retry_base_backoff_ms: u64,
+
+    /// Ceiling on how long a consistently-failing worker waits between retries
+    #[serde(default = "default_retry_max_backoff_secs")]
+    pub retry_max_backoff_secs: u64,
+
+    /// Worker queue-depth load score above which `select_worker` treats the
+    /// worker as saturated and applies admission control instead of routing
+    /// to it unconditionally
+    #[serde(default = "default_max_queue_depth")]
+    pub max_queue_depth: usize,
+
+    /// Bounds how many requests can be admitted past a saturated worker at
+    /// once; once exhausted, further requests are rejected with
+    /// `ChatLoopError::Overloaded` rather than piling on indefinitely
+    #[serde(default = "default_max_pending_admissions")]
+    pub max_pending_admissions: usize,
+
+    /// Enable the admin/introspection HTTP endpoint exposing the router's
+    /// worker table at `GET /workers`
+    #[serde(default = "default_admin_enabled")]
+    pub admin_enabled: bool,
+
+    /// Port the admin endpoint binds to, on all interfaces
+    #[serde(default = "default_admin_port")]
+    pub admin_port: u16,
+}
+
+fn default_adaptive_concurrency() -> bool {
+    false
+}
+
+fn default_min_concurrent_requests() -> usize {
+    1
+}
+
+fn default_concurrency_latency_window() -> usize {
+    50
+}
+
+fn default_concurrency_latency_threshold() -> f64 {
+    1.5
+}
+
+fn default_concurrency_backoff_factor() -> f64 {
+    0.7
+}
+
+fn default_retry_base_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_retry_max_backoff_secs() -> u64 {
+    60
+}
+
+fn default_max_queue_depth() -> usize {
+    32
+}
+
+fn default_max_pending_admissions() -> usize {
+    16
+}
+
+fn default_admin_enabled() -> bool {
+    false
+}
+
+fn default_admin_port() -> u16 {
+    9101
 }
 
 /// Model configuration
@@ -170,6 +495,69 @@ pub struct ModelConfig {
     pub layer_groups: Vec<LayerGroupConfig>,
 }
 
+impl ModelConfig {
+    /// Validate that `layer_groups`, sorted by `start_layer`, exactly tile
+    /// `[0, num_layers)` with no gaps or overlaps, and that each group's
+    /// attention/FFN dimensions are internally consistent.
+    pub fn validate_topology(&self) -> Result<()> {
+        let mut groups = self.layer_groups.clone();
+        groups.sort_by_key(|g| g.start_layer);
+
+        let mut expected_start = 0usize;
+        for group in &groups {
+            if group.total_layers != self.num_layers {
+                return Err(ChatLoopError::config(format!(
+                    "Layer group [{}, {}) has total_layers={} but model num_layers={}",
+                    group.start_layer, group.end_layer, group.total_layers, self.num_layers
+                )));
+            }
+
+            if group.start_layer >= group.end_layer || group.end_layer > group.total_layers {
+                return Err(ChatLoopError::config(format!(
+                    "Layer group [{}, {}) must satisfy start_layer < end_layer <= total_layers ({})",
+                    group.start_layer, group.end_layer, group.total_layers
+                )));
+            }
+
+            if group.start_layer != expected_start {
+                return Err(ChatLoopError::config(format!(
+                    "Layer group [{}, {}) does not tile the model: expected it to start at layer {}",
+                    group.start_layer, group.end_layer, expected_start
+                )));
+            }
+            expected_start = group.end_layer;
+
+            if group.num_heads * group.head_dim != group.hidden_dim {
+                return Err(ChatLoopError::config(format!(
+                    "Layer group [{}, {}) has num_heads * head_dim ({} * {} = {}) != hidden_dim ({})",
+                    group.start_layer,
+                    group.end_layer,
+                    group.num_heads,
+                    group.head_dim,
+                    group.num_heads * group.head_dim,
+                    group.hidden_dim
+                )));
+            }
+
+            if group.intermediate_dim <= group.hidden_dim {
+                return Err(ChatLoopError::config(format!(
+                    "Layer group [{}, {}) has intermediate_dim ({}) <= hidden_dim ({})",
+                    group.start_layer, group.end_layer, group.intermediate_dim, group.hidden_dim
+                )));
+            }
+        }
+
+        if expected_start != self.num_layers {
+            return Err(ChatLoopError::config(format!(
+                "Layer groups only cover layers [0, {}) but model has num_layers={}",
+                expected_start, self.num_layers
+            )));
+        }
+
+        Ok(())
+    }
+}
+
 /// Quantization type
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum QuantizationType {
@@ -189,6 +577,19 @@ impl Default for QuantizationType {
     }
 }
 
+impl std::str::FromStr for QuantizationType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(QuantizationType::None),
+            "int8" => Ok(QuantizationType::Int8),
+            "int4" => Ok(QuantizationType::Int4),
+            other => Err(format!("Unknown quantization type: {}", other)),
+        }
+    }
+}
+
 /// Performance tuning configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceConfig {
@@ -211,6 +612,10 @@ pub struct PerformanceConfig {
     /// Memory allocation strategy
     #[serde(default = "default_allocator")]
     pub allocator: AllocatorType,
+
+    /// Comma-separated paths to shared libraries providing custom tensor kernels
+    #[serde(default = "default_kernel_plugins")]
+    pub kernel_plugins: String,
 }
 
 /// Memory allocator type
@@ -232,6 +637,19 @@ impl Default for AllocatorType {
     }
 }
 
+impl std::str::FromStr for AllocatorType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "system" => Ok(AllocatorType::System),
+            "arena" => Ok(AllocatorType::Arena),
+            "pool" => Ok(AllocatorType::Pool),
+            other => Err(format!("Unknown allocator type: {}", other)),
+        }
+    }
+}
+
 /// Observability configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObservabilityConfig {
@@ -247,6 +665,10 @@ pub struct ObservabilityConfig {
     #[serde(default = "default_metrics_port")]
     pub metrics_port: u16,
 
+    /// HTTP path the metrics exporter serves Prometheus text exposition on
+    #[serde(default = "default_metrics_path")]
+    pub metrics_path: String,
+
     /// Enable structured logging
     #[serde(default = "default_structured_logging")]
     pub structured_logging: bool,
@@ -268,6 +690,82 @@ fn default_discovery_method() -> String {
     "static".to_string()
 }
 
+fn default_backpressure() -> bool {
+    false
+}
+
+fn default_high_watermark() -> f64 {
+    0.8
+}
+
+fn default_low_watermark() -> f64 {
+    0.5
+}
+
+fn default_max_batch_prefill_tokens() -> usize {
+    4096
+}
+
+fn default_max_batch_total_tokens() -> usize {
+    16384
+}
+
+fn default_waiting_served_ratio() -> f32 {
+    0.3
+}
+
+fn default_min_batching_window_ms() -> u64 {
+    0
+}
+
+fn default_max_batching_window_ms() -> u64 {
+    50
+}
+
+fn default_batch_parallelism() -> usize {
+    1
+}
+
+fn default_worker_admin_enabled() -> bool {
+    false
+}
+
+fn default_worker_admin_port() -> u16 {
+    9102
+}
+
+fn default_max_batch_retries() -> u32 {
+    3
+}
+
+fn default_batch_retry_base_delay_ms() -> u64 {
+    100
+}
+
+fn default_batch_retry_max_delay_ms() -> u64 {
+    5_000
+}
+
+fn default_tranquility() -> f64 {
+    0.0
+}
+
+fn default_low_reserved_slots() -> usize {
+    1
+}
+
+fn default_normal_reserved_slots() -> usize {
+    1
+}
+
+fn default_low_max_wait_ms() -> u64 {
+    30_000
+}
+
+fn default_normal_max_wait_ms() -> u64 {
+    10_000
+}
+
 fn default_health_check_interval() -> u64 {
     5
 }
@@ -304,6 +802,10 @@ fn default_allocator() -> AllocatorType {
     AllocatorType::Arena
 }
 
+fn default_kernel_plugins() -> String {
+    String::new()
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -316,6 +818,10 @@ fn default_metrics_port() -> u16 {
     9091
 }
 
+fn default_metrics_path() -> String {
+    "/metrics".to_string()
+}
+
 fn default_structured_logging() -> bool {
     true
 }
@@ -334,6 +840,135 @@ impl ChatLoopConfig {
         Ok(config)
     }
 
+    /// Load configuration from a YAML file, then overlay env var overrides
+    ///
+    /// The mounted YAML stays the source of truth; env vars (see
+    /// [`ChatLoopConfig::merge_env`]) only tune fields that already have a
+    /// value, which is what lets a container/k8s deployment override a
+    /// couple of knobs without mutating the mounted config file.
+    pub fn from_file_with_env_overrides<P: Into<PathBuf>>(path: P) -> Result<Self> {
+        let mut config = Self::from_file(path)?;
+        config.merge_env();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Overlay structured `CHATLOOP_*` environment variable overrides
+    ///
+    /// Keys use a `__` separator to descend into nested structs, e.g.
+    /// `CHATLOOP_WORKER__BATCHING__MAX_BATCH_SIZE=64` overrides
+    /// `worker.batching.max_batch_size`, and
+    /// `CHATLOOP_COORDINATOR__WORKER_ENDPOINTS=a,b,c` overrides
+    /// `coordinator.worker_endpoints` as a comma-separated list. Only
+    /// fields of a substruct that's already `Some(...)` are touched — this
+    /// doesn't conjure up `worker`/`coordinator`/etc. from nothing, since
+    /// those have required fields a handful of env vars can't supply.
+    pub fn merge_env(&mut self) {
+        env_assign(&mut self.mode, "CHATLOOP_MODE");
+        env_assign(&mut self.bind_address, "CHATLOOP_BIND_ADDRESS");
+        env_assign(&mut self.port, "CHATLOOP_PORT");
+
+        if let Some(worker) = self.worker.as_mut() {
+            env_assign(&mut worker.worker_id, "CHATLOOP_WORKER__WORKER_ID");
+            env_assign_opt(&mut worker.next_worker_endpoint, "CHATLOOP_WORKER__NEXT_WORKER_ENDPOINT");
+            env_assign_opt(&mut worker.prev_worker_endpoint, "CHATLOOP_WORKER__PREV_WORKER_ENDPOINT");
+            env_assign(&mut worker.weights_path, "CHATLOOP_WORKER__WEIGHTS_PATH");
+            env_assign(&mut worker.worker_threads, "CHATLOOP_WORKER__WORKER_THREADS");
+            env_assign(&mut worker.enable_cpu_pinning, "CHATLOOP_WORKER__ENABLE_CPU_PINNING");
+            env_assign_opt(&mut worker.cpu_cores, "CHATLOOP_WORKER__CPU_CORES");
+            env_assign_opt(&mut worker.numa_node, "CHATLOOP_WORKER__NUMA_NODE");
+
+            env_assign(&mut worker.layer_group.start_layer, "CHATLOOP_WORKER__LAYER_GROUP__START_LAYER");
+            env_assign(&mut worker.layer_group.end_layer, "CHATLOOP_WORKER__LAYER_GROUP__END_LAYER");
+            env_assign(&mut worker.layer_group.total_layers, "CHATLOOP_WORKER__LAYER_GROUP__TOTAL_LAYERS");
+
+            env_assign(&mut worker.batching.max_batch_size, "CHATLOOP_WORKER__BATCHING__MAX_BATCH_SIZE");
+            env_assign(&mut worker.batching.batching_window_ms, "CHATLOOP_WORKER__BATCHING__BATCHING_WINDOW_MS");
+            env_assign(&mut worker.batching.max_queue_size, "CHATLOOP_WORKER__BATCHING__MAX_QUEUE_SIZE");
+            env_assign(&mut worker.batching.queue_timeout_ms, "CHATLOOP_WORKER__BATCHING__QUEUE_TIMEOUT_MS");
+        }
+
+        if let Some(coordinator) = self.coordinator.as_mut() {
+            env_assign_list(&mut coordinator.worker_endpoints, "CHATLOOP_COORDINATOR__WORKER_ENDPOINTS");
+            env_assign(&mut coordinator.discovery_method, "CHATLOOP_COORDINATOR__DISCOVERY_METHOD");
+            env_assign(
+                &mut coordinator.health_check_interval_secs,
+                "CHATLOOP_COORDINATOR__HEALTH_CHECK_INTERVAL_SECS",
+            );
+            env_assign(&mut coordinator.failure_threshold, "CHATLOOP_COORDINATOR__FAILURE_THRESHOLD");
+            env_assign(&mut coordinator.request_timeout_secs, "CHATLOOP_COORDINATOR__REQUEST_TIMEOUT_SECS");
+            env_assign(
+                &mut coordinator.max_concurrent_requests,
+                "CHATLOOP_COORDINATOR__MAX_CONCURRENT_REQUESTS",
+            );
+            env_assign(&mut coordinator.adaptive_concurrency, "CHATLOOP_COORDINATOR__ADAPTIVE_CONCURRENCY");
+            env_assign(
+                &mut coordinator.min_concurrent_requests,
+                "CHATLOOP_COORDINATOR__MIN_CONCURRENT_REQUESTS",
+            );
+            env_assign(
+                &mut coordinator.concurrency_latency_window,
+                "CHATLOOP_COORDINATOR__CONCURRENCY_LATENCY_WINDOW",
+            );
+            env_assign(
+                &mut coordinator.concurrency_latency_threshold,
+                "CHATLOOP_COORDINATOR__CONCURRENCY_LATENCY_THRESHOLD",
+            );
+            env_assign(
+                &mut coordinator.concurrency_backoff_factor,
+                "CHATLOOP_COORDINATOR__CONCURRENCY_BACKOFF_FACTOR",
+            );
+            env_assign(
+                &mut coordinator.retry_base_backoff_ms,
+                "CHATLOOP_COORDINATOR__RETRY_BASE_BACKOFF_MS",
+            );
+            env_assign(
+                &mut coordinator.retry_max_backoff_secs,
+                "CHATLOOP_COORDINATOR__RETRY_MAX_BACKOFF_SECS",
+            );
+            env_assign(&mut coordinator.max_queue_depth, "CHATLOOP_COORDINATOR__MAX_QUEUE_DEPTH");
+            env_assign(
+                &mut coordinator.max_pending_admissions,
+                "CHATLOOP_COORDINATOR__MAX_PENDING_ADMISSIONS",
+            );
+            env_assign(&mut coordinator.admin_enabled, "CHATLOOP_COORDINATOR__ADMIN_ENABLED");
+            env_assign(&mut coordinator.admin_port, "CHATLOOP_COORDINATOR__ADMIN_PORT");
+        }
+
+        if let Some(model) = self.model.as_mut() {
+            env_assign(&mut model.model_id, "CHATLOOP_MODEL__MODEL_ID");
+            env_assign(&mut model.architecture, "CHATLOOP_MODEL__ARCHITECTURE");
+            env_assign(&mut model.vocab_size, "CHATLOOP_MODEL__VOCAB_SIZE");
+            env_assign(&mut model.max_sequence_length, "CHATLOOP_MODEL__MAX_SEQUENCE_LENGTH");
+            env_assign(&mut model.quantization, "CHATLOOP_MODEL__QUANTIZATION");
+            env_assign(&mut model.num_layers, "CHATLOOP_MODEL__NUM_LAYERS");
+        }
+
+        if let Some(performance) = self.performance.as_mut() {
+            env_assign(&mut performance.enable_simd, "CHATLOOP_PERFORMANCE__ENABLE_SIMD");
+            env_assign(&mut performance.enable_numa, "CHATLOOP_PERFORMANCE__ENABLE_NUMA");
+            env_assign(&mut performance.kv_cache_mb, "CHATLOOP_PERFORMANCE__KV_CACHE_MB");
+            env_assign(
+                &mut performance.preallocate_activations,
+                "CHATLOOP_PERFORMANCE__PREALLOCATE_ACTIVATIONS",
+            );
+            env_assign(&mut performance.allocator, "CHATLOOP_PERFORMANCE__ALLOCATOR");
+            env_assign(&mut performance.kernel_plugins, "CHATLOOP_PERFORMANCE__KERNEL_PLUGINS");
+        }
+
+        if let Some(observability) = self.observability.as_mut() {
+            env_assign(&mut observability.log_level, "CHATLOOP_OBSERVABILITY__LOG_LEVEL");
+            env_assign(&mut observability.enable_metrics, "CHATLOOP_OBSERVABILITY__ENABLE_METRICS");
+            env_assign(&mut observability.metrics_port, "CHATLOOP_OBSERVABILITY__METRICS_PORT");
+            env_assign(&mut observability.metrics_path, "CHATLOOP_OBSERVABILITY__METRICS_PATH");
+            env_assign(
+                &mut observability.structured_logging,
+                "CHATLOOP_OBSERVABILITY__STRUCTURED_LOGGING",
+            );
+            env_assign_opt(&mut observability.otel_endpoint, "CHATLOOP_OBSERVABILITY__OTEL_ENDPOINT");
+        }
+    }
+
     /// Load configuration from environment variables
     pub fn from_env() -> Result<Self> {
         // This is a simplified version - in production, you'd use env-specific overrides
@@ -369,6 +1004,17 @@ impl ChatLoopConfig {
                 return Err(ChatLoopError::config(format!("Invalid mode: {}", self.mode)));
             }
         }
+
+        if let Some(model) = self.model.as_ref() {
+            model.validate_topology()?;
+        }
+
+        if let Some(worker) = self.worker.as_ref() {
+            if let Some(model) = self.model.as_ref() {
+                worker.validate_topology(model)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -389,6 +1035,31 @@ impl ChatLoopConfig {
     }
 }
 
+/// Overwrite `field` with the parsed value of `key` if it's set and parses cleanly
+fn env_assign<T: std::str::FromStr>(field: &mut T, key: &str) {
+    if let Ok(value) = std::env::var(key) {
+        if let Ok(parsed) = value.parse() {
+            *field = parsed;
+        }
+    }
+}
+
+/// Overwrite `field` with `Some(parsed value)` of `key` if it's set and parses cleanly
+fn env_assign_opt<T: std::str::FromStr>(field: &mut Option<T>, key: &str) {
+    if let Ok(value) = std::env::var(key) {
+        if let Ok(parsed) = value.parse() {
+            *field = Some(parsed);
+        }
+    }
+}
+
+/// Overwrite `field` with a comma-separated list parsed from `key` if it's set
+fn env_assign_list(field: &mut Vec<String>, key: &str) {
+    if let Ok(value) = std::env::var(key) {
+        *field = value.split(',').map(|s| s.trim().to_string()).collect();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +1080,9 @@ mod tests {
                     head_dim: 128,
                     hidden_dim: 4096,
                     intermediate_dim: 11008,
+                    norm_type: NormType::RmsNorm,
+                    rope_base: 10000.0,
+                    num_kv_heads: 0,
                 },
                 next_worker_endpoint: Some("http://localhost:50052".to_string()),
                 prev_worker_endpoint: None,
@@ -417,12 +1091,29 @@ mod tests {
                     batching_window_ms: 5,
                     max_queue_size: 512,
                     queue_timeout_ms: 100,
+                    backpressure: false,
+                    high_watermark: 0.8,
+                    low_watermark: 0.5,
+                    max_batch_prefill_tokens: 4096,
+                    max_batch_total_tokens: 16384,
+                    waiting_served_ratio: 0.3,
+                    min_batching_window_ms: 0,
+                    max_batching_window_ms: 50,
+                    batch_parallelism: 1,
+                    tranquility: 0.0,
                 },
                 weights_path: PathBuf::from("/models/weights"),
                 worker_threads: 0,
                 enable_cpu_pinning: true,
                 cpu_cores: None,
                 numa_node: None,
+                admin_enabled: false,
+                admin_port: 9102,
+                max_batch_retries: 3,
+                batch_retry_base_delay_ms: 100,
+                batch_retry_max_delay_ms: 5_000,
+                tranquility_state_path: None,
+                tensor_parallel: None,
             }),
             coordinator: None,
             model: None,
@@ -448,4 +1139,254 @@ mod tests {
 
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_merge_env_overrides_top_level_and_nested_fields() {
+        std::env::set_var("CHATLOOP_BIND_ADDRESS", "127.0.0.1");
+        std::env::set_var("CHATLOOP_WORKER__BATCHING__MAX_BATCH_SIZE", "64");
+        std::env::set_var("CHATLOOP_WORKER__NEXT_WORKER_ENDPOINT", "http://localhost:9999");
+        std::env::set_var("CHATLOOP_PERFORMANCE__ALLOCATOR", "arena");
+
+        let mut config = ChatLoopConfig {
+            mode: "worker".to_string(),
+            bind_address: "0.0.0.0".to_string(),
+            port: 50051,
+            worker: Some(WorkerConfig {
+                worker_id: "test-worker".to_string(),
+                layer_group: LayerGroupConfig {
+                    start_layer: 0,
+                    end_layer: 16,
+                    total_layers: 32,
+                    num_heads: 32,
+                    head_dim: 128,
+                    hidden_dim: 4096,
+                    intermediate_dim: 11008,
+                    norm_type: NormType::RmsNorm,
+                    rope_base: 10000.0,
+                    num_kv_heads: 0,
+                },
+                next_worker_endpoint: None,
+                prev_worker_endpoint: None,
+                batching: BatchingConfig {
+                    max_batch_size: 32,
+                    batching_window_ms: 5,
+                    max_queue_size: 512,
+                    queue_timeout_ms: 100,
+                    backpressure: false,
+                    high_watermark: 0.8,
+                    low_watermark: 0.5,
+                    max_batch_prefill_tokens: 4096,
+                    max_batch_total_tokens: 16384,
+                    waiting_served_ratio: 0.3,
+                    min_batching_window_ms: 0,
+                    max_batching_window_ms: 50,
+                    batch_parallelism: 1,
+                    tranquility: 0.0,
+                },
+                weights_path: PathBuf::from("/models/weights"),
+                worker_threads: 0,
+                enable_cpu_pinning: true,
+                cpu_cores: None,
+                numa_node: None,
+                admin_enabled: false,
+                admin_port: 9102,
+                max_batch_retries: 3,
+                batch_retry_base_delay_ms: 100,
+                batch_retry_max_delay_ms: 5_000,
+                tranquility_state_path: None,
+                tensor_parallel: None,
+            }),
+            coordinator: None,
+            model: None,
+            performance: Some(PerformanceConfig {
+                enable_simd: true,
+                enable_numa: false,
+                kv_cache_mb: 1024,
+                preallocate_activations: false,
+                allocator: AllocatorType::System,
+                kernel_plugins: String::new(),
+            }),
+            observability: None,
+        };
+
+        config.merge_env();
+
+        std::env::remove_var("CHATLOOP_BIND_ADDRESS");
+        std::env::remove_var("CHATLOOP_WORKER__BATCHING__MAX_BATCH_SIZE");
+        std::env::remove_var("CHATLOOP_WORKER__NEXT_WORKER_ENDPOINT");
+        std::env::remove_var("CHATLOOP_PERFORMANCE__ALLOCATOR");
+
+        assert_eq!(config.bind_address, "127.0.0.1");
+        assert_eq!(config.worker.as_ref().unwrap().batching.max_batch_size, 64);
+        assert_eq!(
+            config.worker.as_ref().unwrap().next_worker_endpoint,
+            Some("http://localhost:9999".to_string())
+        );
+        assert_eq!(config.performance.as_ref().unwrap().allocator, AllocatorType::Arena);
+    }
+
+    #[test]
+    fn test_merge_env_leaves_none_substructs_untouched() {
+        std::env::set_var("CHATLOOP_COORDINATOR__DISCOVERY_METHOD", "static");
+
+        let mut config = ChatLoopConfig {
+            mode: "worker".to_string(),
+            bind_address: "0.0.0.0".to_string(),
+            port: 50051,
+            worker: None,
+            coordinator: None,
+            model: None,
+            performance: None,
+            observability: None,
+        };
+
+        config.merge_env();
+
+        std::env::remove_var("CHATLOOP_COORDINATOR__DISCOVERY_METHOD");
+
+        assert!(config.coordinator.is_none());
+    }
+
+    fn test_layer_group(start: usize, end: usize) -> LayerGroupConfig {
+        LayerGroupConfig {
+            start_layer: start,
+            end_layer: end,
+            total_layers: 32,
+            num_heads: 32,
+            head_dim: 128,
+            hidden_dim: 4096,
+            intermediate_dim: 11008,
+            norm_type: NormType::RmsNorm,
+            rope_base: 10000.0,
+                    num_kv_heads: 0,
+        }
+    }
+
+    fn test_model_config(layer_groups: Vec<LayerGroupConfig>) -> ModelConfig {
+        ModelConfig {
+            model_id: "test-model".to_string(),
+            architecture: "llama".to_string(),
+            vocab_size: 32000,
+            max_sequence_length: 4096,
+            quantization: QuantizationType::None,
+            num_layers: 32,
+            layer_groups,
+        }
+    }
+
+    #[test]
+    fn test_model_topology_valid_tiling() {
+        let model = test_model_config(vec![test_layer_group(0, 16), test_layer_group(16, 32)]);
+        assert!(model.validate_topology().is_ok());
+    }
+
+    #[test]
+    fn test_model_topology_rejects_gap() {
+        let model = test_model_config(vec![test_layer_group(0, 15), test_layer_group(16, 32)]);
+        assert!(model.validate_topology().is_err());
+    }
+
+    #[test]
+    fn test_model_topology_rejects_overlap() {
+        let model = test_model_config(vec![test_layer_group(0, 17), test_layer_group(16, 32)]);
+        assert!(model.validate_topology().is_err());
+    }
+
+    #[test]
+    fn test_model_topology_rejects_bad_head_dims() {
+        let mut group = test_layer_group(0, 32);
+        group.num_heads = 31;
+        let model = test_model_config(vec![group]);
+        assert!(model.validate_topology().is_err());
+    }
+
+    #[test]
+    fn test_model_topology_rejects_small_intermediate_dim() {
+        let mut group = test_layer_group(0, 32);
+        group.intermediate_dim = group.hidden_dim;
+        let model = test_model_config(vec![group]);
+        assert!(model.validate_topology().is_err());
+    }
+
+    #[test]
+    fn test_worker_topology_rejects_unknown_group() {
+        let model = test_model_config(vec![test_layer_group(0, 16), test_layer_group(16, 32)]);
+        let worker = WorkerConfig {
+            worker_id: "w0".to_string(),
+            layer_group: test_layer_group(0, 20),
+            next_worker_endpoint: None,
+            prev_worker_endpoint: None,
+            batching: BatchingConfig {
+                max_batch_size: 32,
+                batching_window_ms: 5,
+                max_queue_size: 512,
+                queue_timeout_ms: 100,
+                backpressure: false,
+                high_watermark: 0.8,
+                low_watermark: 0.5,
+                max_batch_prefill_tokens: 4096,
+                max_batch_total_tokens: 16384,
+                waiting_served_ratio: 0.3,
+                min_batching_window_ms: 0,
+                max_batching_window_ms: 50,
+                batch_parallelism: 1,
+                tranquility: 0.0,
+            },
+            weights_path: PathBuf::from("/models/weights"),
+            worker_threads: 0,
+            enable_cpu_pinning: true,
+            cpu_cores: None,
+            numa_node: None,
+            admin_enabled: false,
+            admin_port: 9102,
+            max_batch_retries: 3,
+            batch_retry_base_delay_ms: 100,
+            batch_retry_max_delay_ms: 5_000,
+            tranquility_state_path: None,
+            tensor_parallel: None,
+        };
+
+        assert!(worker.validate_topology(&model).is_err());
+    }
+
+    #[test]
+    fn test_worker_topology_rejects_dangling_next_endpoint_on_last_group() {
+        let model = test_model_config(vec![test_layer_group(0, 16), test_layer_group(16, 32)]);
+        let worker = WorkerConfig {
+            worker_id: "w1".to_string(),
+            layer_group: test_layer_group(16, 32),
+            next_worker_endpoint: Some("http://localhost:50052".to_string()),
+            prev_worker_endpoint: None,
+            batching: BatchingConfig {
+                max_batch_size: 32,
+                batching_window_ms: 5,
+                max_queue_size: 512,
+                queue_timeout_ms: 100,
+                backpressure: false,
+                high_watermark: 0.8,
+                low_watermark: 0.5,
+                max_batch_prefill_tokens: 4096,
+                max_batch_total_tokens: 16384,
+                waiting_served_ratio: 0.3,
+                min_batching_window_ms: 0,
+                max_batching_window_ms: 50,
+                batch_parallelism: 1,
+                tranquility: 0.0,
+            },
+            weights_path: PathBuf::from("/models/weights"),
+            worker_threads: 0,
+            enable_cpu_pinning: true,
+            cpu_cores: None,
+            numa_node: None,
+            admin_enabled: false,
+            admin_port: 9102,
+            max_batch_retries: 3,
+            batch_retry_base_delay_ms: 100,
+            batch_retry_max_delay_ms: 5_000,
+            tranquility_state_path: None,
+            tensor_parallel: None,
+        };
+
+        assert!(worker.validate_topology(&model).is_err());
+    }
 }
@@ -73,6 +73,19 @@ pub enum ChatLoopError {
     #[error("Parse error: {0}")]
     Parse(String),
 
+    /// Peer worker is running an incompatible build or model
+    #[error("Incompatible worker: {0}")]
+    IncompatibleWorker(String),
+
+    /// Transient failure while streaming a shard from a content-addressable
+    /// store; safe to retry from the last acknowledged offset
+    #[error("Shard fetch error (retryable): {0}")]
+    FetchRetryable(String),
+
+    /// Worker discovery backend failure (DNS resolution, registry poll, etc.)
+    #[error("Discovery error: {0}")]
+    Discovery(String),
+
     /// Generic internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -131,6 +144,15 @@ impl ChatLoopError {
             ChatLoopError::Parse(msg) => {
                 tonic::Status::invalid_argument(format!("Parse error: {}", msg))
             }
+            ChatLoopError::IncompatibleWorker(msg) => {
+                tonic::Status::failed_precondition(format!("Incompatible worker: {}", msg))
+            }
+            ChatLoopError::FetchRetryable(msg) => {
+                tonic::Status::unavailable(format!("Shard fetch error: {}", msg))
+            }
+            ChatLoopError::Discovery(msg) => {
+                tonic::Status::unavailable(format!("Discovery error: {}", msg))
+            }
             ChatLoopError::Serialization(err) => {
                 tonic::Status::internal(format!("Serialization error: {}", err))
             }
@@ -179,6 +201,28 @@ impl ChatLoopError {
     pub fn overloaded(msg: impl Into<String>) -> Self {
         ChatLoopError::Overloaded(msg.into())
     }
+
+    /// Create an incompatible worker error
+    pub fn incompatible_worker(msg: impl Into<String>) -> Self {
+        ChatLoopError::IncompatibleWorker(msg.into())
+    }
+
+    /// Create a retryable shard fetch error
+    pub fn fetch_retryable(msg: impl Into<String>) -> Self {
+        ChatLoopError::FetchRetryable(msg.into())
+    }
+
+    /// Create a discovery backend error
+    pub fn discovery(msg: impl Into<String>) -> Self {
+        ChatLoopError::Discovery(msg.into())
+    }
+
+    /// Whether this failure is worth retrying with backoff, as opposed to a
+    /// deterministic failure (malformed input, an internal bug) that will
+    /// just fail the same way again
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, ChatLoopError::InvalidInput(_) | ChatLoopError::Internal(_))
+    }
 }
 
 /// Result type alias for ChatLoop operations
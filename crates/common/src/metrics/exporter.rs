@@ -0,0 +1,103 @@
+//! HTTP server exposing [`MetricsRegistry::gather`] for Prometheus to scrape
+//!
+//! Compiled in only under the `metrics` cargo feature so deployments that
+//! don't run Prometheus can drop the `hyper` dependency entirely.
+
+use super::METRICS;
+use crate::config::ChatLoopConfig;
+use crate::error::{ChatLoopError, Result};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Serves the global [`METRICS`] registry's Prometheus text exposition
+/// format over plain HTTP
+///
+/// Binds `listen_addr` and answers GET requests on `path` with the current
+/// `gather()` output; every other method or path gets a 404.
+pub struct MetricsExporter {
+    listen_addr: SocketAddr,
+    path: String,
+}
+
+impl MetricsExporter {
+    /// Create a new exporter bound to `listen_addr`, serving metrics at `path`
+    pub fn new(listen_addr: SocketAddr, path: impl Into<String>) -> Self {
+        Self {
+            listen_addr,
+            path: path.into(),
+        }
+    }
+
+    /// Run the exporter until the process exits
+    ///
+    /// Never returns on success; matches the long-running serve loops in
+    /// `grpc::server`.
+    pub async fn serve(&self) -> Result<()> {
+        let path = Arc::new(self.path.clone());
+
+        let make_svc = make_service_fn(move |_conn| {
+            let path = Arc::clone(&path);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let path = Arc::clone(&path);
+                    async move { Ok::<_, Infallible>(handle(&path, req)) }
+                }))
+            }
+        });
+
+        info!(
+            "Metrics exporter listening on {} (path: {})",
+            self.listen_addr, self.path
+        );
+
+        Server::bind(&self.listen_addr)
+            .serve(make_svc)
+            .await
+            .map_err(|e| ChatLoopError::config(format!("Metrics exporter failed: {}", e)))
+    }
+}
+
+/// Spawn the exporter as a background task if `config.observability.enable_metrics`
+/// is set, binding to `config.bind_address:metrics_port` and serving
+/// `metrics_path`
+///
+/// Returns `None` (and spawns nothing) when there's no observability config
+/// or metrics are disabled, so coordinator and worker `main` can call this
+/// unconditionally on startup.
+pub fn spawn_if_enabled(config: &ChatLoopConfig) -> Option<tokio::task::JoinHandle<()>> {
+    let observability = config.observability.as_ref()?;
+    if !observability.enable_metrics {
+        return None;
+    }
+
+    let listen_addr = match format!("{}:{}", config.bind_address, observability.metrics_port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            error!("Invalid metrics listen address: {}", e);
+            return None;
+        }
+    };
+
+    let exporter = MetricsExporter::new(listen_addr, observability.metrics_path.clone());
+    Some(tokio::spawn(async move {
+        if let Err(e) = exporter.serve().await {
+            error!("Metrics exporter exited: {}", e);
+        }
+    }))
+}
+
+/// Handle a single request: serve `gather()` on `GET {path}`, 404 otherwise
+fn handle(path: &str, req: Request<Body>) -> Response<Body> {
+    if req.method() == Method::GET && req.uri().path() == path {
+        Response::new(Body::from(METRICS.gather()))
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .expect("static 404 response is always well-formed")
+    }
+}
@@ -0,0 +1,639 @@
+//! Metrics collection for ChatLoop
+//!
+//! This module provides Prometheus metrics for observability.
+//! All metrics are carefully designed to minimize overhead in the hot path.
+
+#[cfg(feature = "metrics")]
+pub mod exporter;
+
+use lazy_static::lazy_static;
+use prometheus::{
+    core::AtomicU64 as U64, core::AtomicF64 as F64,
+    Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+};
+use std::sync::Arc;
+
+/// Metrics registry for ChatLoop
+#[derive(Debug, Clone)]
+pub struct MetricsRegistry {
+    pub registry: Arc<Registry>,
+    pub inference: InferenceMetrics,
+    pub worker: WorkerMetrics,
+    pub coordinator: CoordinatorMetrics,
+}
+
+/// Floating-point gauge (`IntGauge` only stores integers)
+pub type Gauge = prometheus::core::GenericGauge<F64>;
+
+/// Floating-point gauge vector, the labeled counterpart of [`Gauge`]
+pub type GaugeVec = prometheus::core::GenericGaugeVec<F64>;
+
+/// Accessor for worker metrics labeled by `worker_id`: equivalent to
+/// `.with_label_values(&[worker_id])` but names the label explicitly at
+/// call sites instead of an anonymous string slice
+pub trait WithWorker {
+    /// Concrete, unlabeled metric type this vector resolves to
+    type Metric;
+
+    /// Resolve this vector's series for a single worker
+    fn with_worker(&self, worker_id: &str) -> Self::Metric;
+}
+
+impl WithWorker for IntGaugeVec {
+    type Metric = IntGauge;
+
+    fn with_worker(&self, worker_id: &str) -> IntGauge {
+        self.with_label_values(&[worker_id])
+    }
+}
+
+impl WithWorker for IntCounterVec {
+    type Metric = IntCounter;
+
+    fn with_worker(&self, worker_id: &str) -> IntCounter {
+        self.with_label_values(&[worker_id])
+    }
+}
+
+impl WithWorker for HistogramVec {
+    type Metric = Histogram;
+
+    fn with_worker(&self, worker_id: &str) -> Histogram {
+        self.with_label_values(&[worker_id])
+    }
+}
+
+impl WithWorker for GaugeVec {
+    type Metric = Gauge;
+
+    fn with_worker(&self, worker_id: &str) -> Gauge {
+        self.with_label_values(&[worker_id])
+    }
+}
+
+/// Accessor for coordinator metrics labeled by target worker endpoint
+pub trait WithEndpoint {
+    /// Concrete, unlabeled metric type this vector resolves to
+    type Metric;
+
+    /// Resolve this vector's series for a single worker endpoint
+    fn with_endpoint(&self, endpoint: &str) -> Self::Metric;
+}
+
+impl WithEndpoint for IntCounterVec {
+    type Metric = IntCounter;
+
+    fn with_endpoint(&self, endpoint: &str) -> IntCounter {
+        self.with_label_values(&[endpoint])
+    }
+}
+
+impl WithEndpoint for HistogramVec {
+    type Metric = Histogram;
+
+    fn with_endpoint(&self, endpoint: &str) -> Histogram {
+        self.with_label_values(&[endpoint])
+    }
+}
+
+/// Inference-related metrics
+#[derive(Debug, Clone)]
+pub struct InferenceMetrics {
+    /// Total number of inference requests
+    pub requests_total: IntCounter,
+
+    /// Total number of successful requests
+    pub requests_success: IntCounter,
+
+    /// Total number of failed requests
+    pub requests_failed: IntCounter,
+
+    /// Request duration histogram
+    pub request_duration: Histogram,
+
+    /// Prompt processing duration
+    pub prompt_duration: Histogram,
+
+    /// Token generation duration
+    pub generation_duration: Histogram,
+
+    /// Tokens generated total
+    pub tokens_generated_total: IntCounter,
+
+    /// Tokens per second
+    pub tokens_per_second: Histogram,
+
+    /// Current active requests
+    pub active_requests: IntGauge,
+}
+
+/// Worker-specific metrics
+///
+/// Every series here is labeled by `worker_id` (via [`WithWorker::with_worker`])
+/// so that samples from different worker processes don't collapse into one
+/// series once they're all scraped through the same `Router`.
+#[derive(Debug, Clone)]
+pub struct WorkerMetrics {
+    /// Forward pass duration, labeled by `worker_id`
+    pub forward_duration: HistogramVec,
+
+    /// Queue wait time, labeled by `worker_id`
+    pub queue_time: HistogramVec,
+
+    /// Current queue depth, labeled by `worker_id`
+    pub queue_depth: IntGaugeVec,
+
+    /// Batch size histogram, labeled by `worker_id`
+    pub batch_size: HistogramVec,
+
+    /// CPU utilization percentage, labeled by `worker_id`
+    pub cpu_utilization: IntGaugeVec,
+
+    /// Memory usage in bytes, labeled by `worker_id`
+    pub memory_used: IntGaugeVec,
+
+    /// KV cache size in bytes, labeled by `worker_id`
+    pub kv_cache_size: IntGaugeVec,
+
+    /// Active sequences, labeled by `worker_id`
+    pub active_sequences: IntGaugeVec,
+
+    /// Semver-encoded version of each loaded tensor-kernel plugin, labeled
+    /// by `worker_id` and kernel name
+    pub kernel_plugin_version: IntGaugeVec,
+
+    /// Requests dropped due to queue backpressure, labeled by `worker_id`
+    pub requests_dropped: IntCounterVec,
+
+    /// Times the upstream connection was paused due to high-watermark
+    /// backpressure, labeled by `worker_id`
+    pub backpressure_pauses: IntCounterVec,
+
+    /// Requests dropped after exceeding `queue_timeout_ms` while still
+    /// queued, labeled by `worker_id`
+    pub queue_timeouts: IntCounterVec,
+
+    /// Time requests spend queued in `PriorityScheduler` before being
+    /// dispatched, labeled by `worker_id` and the tier (`high`/`normal`/`low`)
+    /// they were dispatched from
+    pub priority_wait_time: HistogramVec,
+
+    /// Currently-effective adaptive batching window, in milliseconds,
+    /// labeled by `worker_id`
+    pub effective_batching_window_ms: GaugeVec,
+
+    /// Peak resident set size observed during a tracked operation (e.g. a
+    /// forward pass), in bytes and labeled by `worker_id`
+    ///
+    /// Populated by `ResourceMonitor::track` in the worker crate rather
+    /// than a periodic sampler, since a fixed-interval sample can step
+    /// right over a short-lived spike.
+    pub peak_memory_used: HistogramVec,
+}
+
+/// Coordinator-specific metrics
+#[derive(Debug, Clone)]
+pub struct CoordinatorMetrics {
+    /// Requests routed, labeled by target worker endpoint (via
+    /// [`WithEndpoint::with_endpoint`])
+    pub requests_routed: IntCounterVec,
+
+    /// Active workers (fleet-wide total, not per-endpoint)
+    pub active_workers: IntGauge,
+
+    /// Unhealthy workers (fleet-wide total, not per-endpoint)
+    pub unhealthy_workers: IntGauge,
+
+    /// Worker response time, labeled by target worker endpoint
+    pub worker_response_time: HistogramVec,
+
+    /// Load balancing decisions, labeled by the chosen worker endpoint
+    pub load_balancing_decisions: IntCounterVec,
+
+    /// Failed requests due to no workers
+    pub no_workers_available: IntCounter,
+
+    /// Current in-flight request limit chosen by the adaptive concurrency governor
+    pub concurrency_limit: IntGauge,
+
+    /// Baseline (near-floor) end-to-end request latency the governor compares against, in milliseconds
+    pub concurrency_baseline_latency_ms: Gauge,
+
+    /// Mean end-to-end request latency over the governor's sliding window
+    /// of recent completions, in milliseconds
+    pub concurrency_observed_latency_ms: Gauge,
+
+    /// Requests rejected or throttled by `Router::select_worker`'s admission
+    /// control, either because the best worker's load score exceeded
+    /// `max_queue_depth` or because the concurrency governor's limit was
+    /// already reached
+    pub requests_throttled: IntCounter,
+
+    /// Workers currently draining via `Router::drain_worker` (fleet-wide
+    /// total, not per-endpoint)
+    pub draining_workers: IntGauge,
+}
+
+lazy_static! {
+    /// Global metrics registry instance
+    pub static ref METRICS: MetricsRegistry = MetricsRegistry::new();
+}
+
+impl MetricsRegistry {
+    /// Create a new metrics registry
+    pub fn new() -> Self {
+        let registry = Arc::new(Registry::new());
+
+        // Inference metrics
+        let requests_total = IntCounter::new(
+            "inference_requests_total",
+            "Total number of inference requests"
+        ).unwrap();
+
+        let requests_success = IntCounter::new(
+            "inference_requests_success_total",
+            "Total number of successful inference requests"
+        ).unwrap();
+
+        let requests_failed = IntCounter::new(
+            "inference_requests_failed_total",
+            "Total number of failed inference requests"
+        ).unwrap();
+
+        let request_duration = Histogram::with_opts(
+            prometheus::HistogramOpts::new(
+                "inference_request_duration_seconds",
+                "Inference request duration in seconds"
+            ).buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0])
+        ).unwrap();
+
+        let prompt_duration = Histogram::with_opts(
+            prometheus::HistogramOpts::new(
+                "inference_prompt_duration_seconds",
+                "Prompt processing duration in seconds"
+            ).buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5])
+        ).unwrap();
+
+        let generation_duration = Histogram::with_opts(
+            prometheus::HistogramOpts::new(
+                "inference_generation_duration_seconds",
+                "Token generation duration in seconds"
+            ).buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5])
+        ).unwrap();
+
+        let tokens_generated_total = IntCounter::new(
+            "inference_tokens_generated_total",
+            "Total number of tokens generated"
+        ).unwrap();
+
+        let tokens_per_second = Histogram::with_opts(
+            prometheus::HistogramOpts::new(
+                "inference_tokens_per_second",
+                "Tokens generated per second"
+            ).buckets(vec![1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0])
+        ).unwrap();
+
+        let active_requests = IntGauge::new(
+            "inference_active_requests",
+            "Current number of active inference requests"
+        ).unwrap();
+
+        // Worker metrics - all labeled by worker_id so samples from
+        // different worker processes stay distinguishable once scraped
+        // through the same Router
+        let forward_duration = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "worker_forward_duration_seconds",
+                "Worker forward pass duration in seconds"
+            ).buckets(vec![0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1]),
+            &["worker_id"]
+        ).unwrap();
+
+        let queue_time = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "worker_queue_time_seconds",
+                "Time requests spend in queue before processing"
+            ).buckets(vec![0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025]),
+            &["worker_id"]
+        ).unwrap();
+
+        let queue_depth = IntGaugeVec::new(
+            Opts::new(
+                "worker_queue_depth",
+                "Current depth of worker request queue"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let batch_size = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "worker_batch_size",
+                "Batch size distribution"
+            ).buckets(vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0]),
+            &["worker_id"]
+        ).unwrap();
+
+        let cpu_utilization = IntGaugeVec::new(
+            Opts::new(
+                "worker_cpu_utilization_percent",
+                "Worker CPU utilization percentage"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let memory_used = IntGaugeVec::new(
+            Opts::new(
+                "worker_memory_used_bytes",
+                "Worker memory usage in bytes"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let kv_cache_size = IntGaugeVec::new(
+            Opts::new(
+                "worker_kv_cache_size_bytes",
+                "Worker KV cache size in bytes"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let active_sequences = IntGaugeVec::new(
+            Opts::new(
+                "worker_active_sequences",
+                "Current number of active sequences"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let kernel_plugin_version = IntGaugeVec::new(
+            Opts::new(
+                "worker_kernel_plugin_version",
+                "Loaded version of each tensor-kernel plugin, labeled by worker_id and kernel name"
+            ),
+            &["worker_id", "kernel"]
+        ).unwrap();
+
+        let requests_dropped = IntCounterVec::new(
+            Opts::new(
+                "worker_requests_dropped_total",
+                "Total requests dropped due to queue backpressure"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let backpressure_pauses = IntCounterVec::new(
+            Opts::new(
+                "worker_backpressure_pauses_total",
+                "Total times the upstream connection was paused due to high-watermark backpressure"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let queue_timeouts = IntCounterVec::new(
+            Opts::new(
+                "worker_queue_timeouts_total",
+                "Total requests dropped after exceeding queue_timeout_ms while queued"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let priority_wait_time = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "worker_priority_wait_time_seconds",
+                "Time requests spend queued in PriorityScheduler before dispatch, by worker_id and tier"
+            ).buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]),
+            &["worker_id", "tier"]
+        ).unwrap();
+
+        let effective_batching_window_ms = GaugeVec::new(
+            Opts::new(
+                "worker_effective_batching_window_ms",
+                "Currently-effective adaptive batching window in milliseconds"
+            ),
+            &["worker_id"]
+        ).unwrap();
+
+        let peak_memory_used = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "worker_peak_memory_used_bytes",
+                "Peak resident set size observed during a tracked operation, in bytes"
+            ).buckets(vec![
+                1048576.0, 2097152.0, 4194304.0, 8388608.0, 16777216.0, 33554432.0,
+                67108864.0, 134217728.0, 268435456.0, 536870912.0, 1073741824.0, 2147483648.0,
+            ]),
+            &["worker_id"]
+        ).unwrap();
+
+        // Coordinator metrics
+        let requests_routed = IntCounterVec::new(
+            Opts::new(
+                "coordinator_requests_routed_total",
+                "Total number of requests routed, labeled by target worker endpoint"
+            ),
+            &["endpoint"]
+        ).unwrap();
+
+        let active_workers = IntGauge::new(
+            "coordinator_active_workers",
+            "Current number of active workers"
+        ).unwrap();
+
+        let unhealthy_workers = IntGauge::new(
+            "coordinator_unhealthy_workers",
+            "Current number of unhealthy workers"
+        ).unwrap();
+
+        let worker_response_time = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "coordinator_worker_response_time_seconds",
+                "Worker response time, labeled by target worker endpoint"
+            ).buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+            &["endpoint"]
+        ).unwrap();
+
+        let load_balancing_decisions = IntCounterVec::new(
+            Opts::new(
+                "coordinator_load_balancing_decisions_total",
+                "Total number of load balancing decisions, labeled by the chosen worker endpoint"
+            ),
+            &["endpoint"]
+        ).unwrap();
+
+        let no_workers_available = IntCounter::new(
+            "coordinator_no_workers_available_total",
+            "Total requests rejected due to no workers"
+        ).unwrap();
+
+        let concurrency_limit = IntGauge::new(
+            "coordinator_concurrency_limit",
+            "Current in-flight request limit chosen by the adaptive concurrency governor"
+        ).unwrap();
+
+        let concurrency_baseline_latency_ms = Gauge::new(
+            "coordinator_concurrency_baseline_latency_ms",
+            "Baseline end-to-end request latency the concurrency governor compares against"
+        ).unwrap();
+
+        let concurrency_observed_latency_ms = Gauge::new(
+            "coordinator_concurrency_observed_latency_ms",
+            "Mean end-to-end request latency over the concurrency governor's sliding window"
+        ).unwrap();
+
+        let requests_throttled = IntCounter::new(
+            "coordinator_requests_throttled_total",
+            "Total requests rejected or throttled by admission control due to worker saturation or the concurrency limit"
+        ).unwrap();
+
+        let draining_workers = IntGauge::new(
+            "coordinator_draining_workers",
+            "Current number of workers draining ahead of decommissioning"
+        ).unwrap();
+
+        // Register all metrics
+        registry.register(Box::new(requests_total.clone())).unwrap();
+        registry.register(Box::new(requests_success.clone())).unwrap();
+        registry.register(Box::new(requests_failed.clone())).unwrap();
+        registry.register(Box::new(request_duration.clone())).unwrap();
+        registry.register(Box::new(prompt_duration.clone())).unwrap();
+        registry.register(Box::new(generation_duration.clone())).unwrap();
+        registry.register(Box::new(tokens_generated_total.clone())).unwrap();
+        registry.register(Box::new(tokens_per_second.clone())).unwrap();
+        registry.register(Box::new(active_requests.clone())).unwrap();
+
+        registry.register(Box::new(forward_duration.clone())).unwrap();
+        registry.register(Box::new(queue_time.clone())).unwrap();
+        registry.register(Box::new(queue_depth.clone())).unwrap();
+        registry.register(Box::new(batch_size.clone())).unwrap();
+        registry.register(Box::new(cpu_utilization.clone())).unwrap();
+        registry.register(Box::new(memory_used.clone())).unwrap();
+        registry.register(Box::new(kv_cache_size.clone())).unwrap();
+        registry.register(Box::new(active_sequences.clone())).unwrap();
+        registry.register(Box::new(kernel_plugin_version.clone())).unwrap();
+        registry.register(Box::new(requests_dropped.clone())).unwrap();
+        registry.register(Box::new(backpressure_pauses.clone())).unwrap();
+        registry.register(Box::new(queue_timeouts.clone())).unwrap();
+        registry.register(Box::new(priority_wait_time.clone())).unwrap();
+        registry.register(Box::new(effective_batching_window_ms.clone())).unwrap();
+        registry.register(Box::new(peak_memory_used.clone())).unwrap();
+
+        registry.register(Box::new(requests_routed.clone())).unwrap();
+        registry.register(Box::new(active_workers.clone())).unwrap();
+        registry.register(Box::new(unhealthy_workers.clone())).unwrap();
+        registry.register(Box::new(worker_response_time.clone())).unwrap();
+        registry.register(Box::new(load_balancing_decisions.clone())).unwrap();
+        registry.register(Box::new(no_workers_available.clone())).unwrap();
+        registry.register(Box::new(concurrency_limit.clone())).unwrap();
+        registry.register(Box::new(concurrency_baseline_latency_ms.clone())).unwrap();
+        registry.register(Box::new(concurrency_observed_latency_ms.clone())).unwrap();
+        registry.register(Box::new(requests_throttled.clone())).unwrap();
+        registry.register(Box::new(draining_workers.clone())).unwrap();
+
+        let inference = InferenceMetrics {
+            requests_total,
+            requests_success,
+            requests_failed,
+            request_duration,
+            prompt_duration,
+            generation_duration,
+            tokens_generated_total,
+            tokens_per_second,
+            active_requests,
+        };
+
+        let worker = WorkerMetrics {
+            forward_duration,
+            queue_time,
+            queue_depth,
+            batch_size,
+            cpu_utilization,
+            memory_used,
+            kv_cache_size,
+            active_sequences,
+            kernel_plugin_version,
+            requests_dropped,
+            backpressure_pauses,
+            queue_timeouts,
+            priority_wait_time,
+            effective_batching_window_ms,
+            peak_memory_used,
+        };
+
+        let coordinator = CoordinatorMetrics {
+            requests_routed,
+            active_workers,
+            unhealthy_workers,
+            worker_response_time,
+            load_balancing_decisions,
+            no_workers_available,
+            concurrency_limit,
+            concurrency_baseline_latency_ms,
+            concurrency_observed_latency_ms,
+            requests_throttled,
+            draining_workers,
+        };
+
+        MetricsRegistry {
+            registry,
+            inference,
+            worker,
+            coordinator,
+        }
+    }
+
+    /// Gather all metrics as text
+    pub fn gather(&self) -> String {
+        use prometheus::Encoder;
+        let encoder = prometheus::TextEncoder::new();
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        encoder.encode(&metric_families, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper trait for measuring latency
+pub trait LatencyTimer {
+    /// Observe the duration of a closure
+    fn observe<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R;
+}
+
+impl LatencyTimer for Histogram {
+    fn observe<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let start = std::time::Instant::now();
+        let result = f();
+        let duration = start.elapsed().as_secs_f64();
+        self.observe(duration);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_registry() {
+        let metrics = MetricsRegistry::new();
+
+        // Record some metrics
+        metrics.inference.requests_total.inc();
+        metrics.inference.active_requests.inc();
+        metrics.worker.queue_depth.with_worker("worker-0").set(10);
+
+        // Gather metrics
+        let output = metrics.gather();
+        assert!(output.contains("inference_requests_total"));
+        assert!(output.contains("worker_queue_depth"));
+    }
+}
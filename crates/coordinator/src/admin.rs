@@ -0,0 +1,108 @@
+//! HTTP admin/introspection endpoint exposing the router's worker table
+//!
+//! Feature-gated behind the same `metrics` cargo feature as
+//! `chatloop_common::metrics::exporter`, since both are optional hyper-based
+//! side servers a minimal deployment may want to compile out.
+
+use crate::router::Router;
+use chatloop_common::config::CoordinatorConfig;
+use chatloop_common::error::{ChatLoopError, Result};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Serves a JSON snapshot of [`Router`]'s worker table on `GET /workers`
+pub struct AdminServer {
+    listen_addr: SocketAddr,
+    router: Arc<Router>,
+}
+
+impl AdminServer {
+    /// Create a new admin server bound to `listen_addr`, reading from `router`
+    pub fn new(listen_addr: SocketAddr, router: Arc<Router>) -> Self {
+        Self { listen_addr, router }
+    }
+
+    /// Run the admin server until the process exits
+    ///
+    /// Never returns on success; matches the long-running serve loops in
+    /// `grpc::server` and `MetricsExporter::serve`.
+    pub async fn serve(&self) -> Result<()> {
+        let router = Arc::clone(&self.router);
+
+        let make_svc = make_service_fn(move |_conn| {
+            let router = Arc::clone(&router);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let router = Arc::clone(&router);
+                    async move { Ok::<_, Infallible>(handle(&router, req).await) }
+                }))
+            }
+        });
+
+        info!("Admin server listening on {}", self.listen_addr);
+
+        Server::bind(&self.listen_addr)
+            .serve(make_svc)
+            .await
+            .map_err(|e| ChatLoopError::config(format!("Admin server failed: {}", e)))
+    }
+}
+
+/// Spawn the admin server as a background task if `config.admin_enabled` is
+/// set, binding to `config.admin_port` on all interfaces
+///
+/// Returns `None` (and spawns nothing) when admin is disabled, so
+/// `coordinator::main` can call this unconditionally on startup.
+pub fn spawn_if_enabled(
+    config: &CoordinatorConfig,
+    router: Arc<Router>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    if !config.admin_enabled {
+        return None;
+    }
+
+    let listen_addr = match format!("0.0.0.0:{}", config.admin_port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            error!("Invalid admin listen address: {}", e);
+            return None;
+        }
+    };
+
+    let admin = AdminServer::new(listen_addr, router);
+    Some(tokio::spawn(async move {
+        if let Err(e) = admin.serve().await {
+            error!("Admin server exited: {}", e);
+        }
+    }))
+}
+
+/// Handle a single request: serve the worker snapshot as JSON on
+/// `GET /workers`, 404 otherwise
+async fn handle(router: &Router, req: Request<Body>) -> Response<Body> {
+    if req.method() == Method::GET && req.uri().path() == "/workers" {
+        let snapshot = router.snapshot().await;
+        match serde_json::to_vec(&snapshot) {
+            Ok(body) => Response::builder()
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .expect("static JSON response is always well-formed"),
+            Err(e) => {
+                error!("Failed to serialize worker snapshot: {}", e);
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::empty())
+                    .expect("static 500 response is always well-formed")
+            }
+        }
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .expect("static 404 response is always well-formed")
+    }
+}
@@ -0,0 +1,294 @@
+//! Pluggable worker discovery backends
+//!
+//! `CoordinatorConfig::discovery_method` selects one of these at startup.
+//! Every backend reports membership the same way regardless of how it
+//! learns about it: `poll` returns only what changed since the previous
+//! call, so [`Router::apply_discovery_delta`](crate::router::Router::apply_discovery_delta)
+//! can update the routing table in place instead of replacing it wholesale
+//! every cycle.
+
+use crate::router::WorkerInfo;
+use chatloop_common::{ChatLoopError, Result};
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Incremental membership change reported by a discovery backend
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryDelta {
+    /// Workers that should be registered
+    pub added: Vec<WorkerInfo>,
+
+    /// Endpoints that are no longer part of the membership set
+    pub removed: Vec<String>,
+}
+
+impl DiscoveryDelta {
+    /// True if this delta changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A pluggable worker-discovery backend
+///
+/// `poll` is called on the coordinator's existing
+/// `health_check_interval_secs` cadence and should return only the
+/// membership delta since the previous call.
+pub trait DiscoveryBackend: Send + Sync {
+    /// Poll for membership changes since the last call
+    fn poll<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = Result<DiscoveryDelta>> + Send + 'a>>;
+}
+
+/// Static discovery backend backed by `CoordinatorConfig::worker_endpoints`
+///
+/// Reports the configured set once, on the first poll, and an empty delta
+/// thereafter since the set never changes.
+pub struct StaticDiscovery {
+    workers: Vec<WorkerInfo>,
+    polled: bool,
+}
+
+impl StaticDiscovery {
+    /// Create a static backend over a fixed set of workers
+    pub fn new(workers: Vec<WorkerInfo>) -> Self {
+        Self { workers, polled: false }
+    }
+}
+
+impl DiscoveryBackend for StaticDiscovery {
+    fn poll<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = Result<DiscoveryDelta>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.polled {
+                return Ok(DiscoveryDelta::default());
+            }
+            self.polled = true;
+            Ok(DiscoveryDelta {
+                added: self.workers.clone(),
+                removed: Vec::new(),
+            })
+        })
+    }
+}
+
+/// Resolves a DNS name to the set of currently live worker addresses
+///
+/// This is the seam a real SRV/A lookup plugs into; tests can supply a
+/// fixed or mutable in-memory resolver instead.
+pub trait DnsResolver: Send + Sync {
+    /// Resolve `record` to a set of `host:port` addresses
+    fn resolve<'a>(&'a self, record: &'a str) -> Pin<Box<dyn Future<Output = Result<Vec<String>>> + Send + 'a>>;
+}
+
+/// Discovery backend that periodically re-resolves a DNS record
+///
+/// Diffs each resolution against the previously-seen address set so only
+/// the addresses that actually appeared or vanished are reported.
+pub struct DnsDiscovery<R: DnsResolver> {
+    resolver: R,
+    record: String,
+    layer_group: (usize, usize),
+    known: HashSet<String>,
+}
+
+impl<R: DnsResolver> DnsDiscovery<R> {
+    /// Create a DNS discovery backend for `record`, assigning `layer_group`
+    /// to every worker address it resolves
+    pub fn new(resolver: R, record: impl Into<String>, layer_group: (usize, usize)) -> Self {
+        Self {
+            resolver,
+            record: record.into(),
+            layer_group,
+            known: HashSet::new(),
+        }
+    }
+}
+
+impl<R: DnsResolver> DiscoveryBackend for DnsDiscovery<R> {
+    fn poll<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = Result<DiscoveryDelta>> + Send + 'a>> {
+        Box::pin(async move {
+            let resolved: HashSet<String> = self.resolver.resolve(&self.record).await?.into_iter().collect();
+
+            let added = resolved
+                .difference(&self.known)
+                .map(|endpoint| {
+                    WorkerInfo::new(endpoint.clone(), format!("worker-{}", endpoint), self.layer_group)
+                })
+                .collect();
+            let removed = self.known.difference(&resolved).cloned().collect();
+
+            self.known = resolved;
+            Ok(DiscoveryDelta { added, removed })
+        })
+    }
+}
+
+/// One versioned page of membership changes from a registry service
+#[derive(Debug, Clone, Default)]
+pub struct RegistryChanges {
+    /// Version to pass as `since_version` on the next call
+    pub version: u64,
+
+    /// Workers that joined since `since_version`
+    pub added: Vec<WorkerInfo>,
+
+    /// Endpoints that left since `since_version`
+    pub removed: Vec<String>,
+
+    /// Populated if the registry failed to compute the change set
+    pub error: Option<String>,
+}
+
+/// Queries a membership registry for changes since a given version
+///
+/// Modeled as a versioned change feed: `changes_since` returns everything
+/// that happened after `since_version` (`None` means "from the
+/// beginning"). A populated `error` field is a hard failure, not an empty
+/// delta — [`RegistryDiscovery::poll`] surfaces it as `Err` rather than
+/// silently treating it as "nothing changed".
+pub trait RegistryClient: Send + Sync {
+    /// Fetch the change set since `since_version`
+    fn changes_since<'a>(
+        &'a self,
+        since_version: Option<u64>,
+    ) -> Pin<Box<dyn Future<Output = Result<RegistryChanges>> + Send + 'a>>;
+}
+
+/// Discovery backend that polls a membership registry's change feed
+pub struct RegistryDiscovery<C: RegistryClient> {
+    client: C,
+    last_version: Option<u64>,
+}
+
+impl<C: RegistryClient> RegistryDiscovery<C> {
+    /// Create a registry discovery backend starting from the beginning of
+    /// the change feed
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            last_version: None,
+        }
+    }
+}
+
+impl<C: RegistryClient> DiscoveryBackend for RegistryDiscovery<C> {
+    fn poll<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = Result<DiscoveryDelta>> + Send + 'a>> {
+        Box::pin(async move {
+            let changes = self.client.changes_since(self.last_version).await?;
+
+            if let Some(error) = changes.error {
+                return Err(ChatLoopError::discovery(format!(
+                    "registry failed to compute changes since version {:?}: {}",
+                    self.last_version, error
+                )));
+            }
+
+            self.last_version = Some(changes.version);
+            Ok(DiscoveryDelta {
+                added: changes.added,
+                removed: changes.removed,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_static_discovery_reports_once_then_empty() {
+        let mut discovery = StaticDiscovery::new(vec![WorkerInfo::new(
+            "http://localhost:50051".to_string(),
+            "worker-1".to_string(),
+            (0, 16),
+        )]);
+
+        let first = discovery.poll().await.unwrap();
+        assert_eq!(first.added.len(), 1);
+        assert!(first.removed.is_empty());
+
+        let second = discovery.poll().await.unwrap();
+        assert!(second.is_empty());
+    }
+
+    struct FixedDnsResolver {
+        addresses: Mutex<Vec<String>>,
+    }
+
+    impl DnsResolver for FixedDnsResolver {
+        fn resolve<'a>(&'a self, _record: &'a str) -> Pin<Box<dyn Future<Output = Result<Vec<String>>> + Send + 'a>> {
+            Box::pin(async move { Ok(self.addresses.lock().await.clone()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dns_discovery_diffs_against_previous_resolution() {
+        let resolver = FixedDnsResolver {
+            addresses: Mutex::new(vec!["http://10.0.0.1:50051".to_string()]),
+        };
+        let mut discovery = DnsDiscovery::new(resolver, "workers.internal", (0, 16));
+
+        let first = discovery.poll().await.unwrap();
+        assert_eq!(first.added.len(), 1);
+        assert!(first.removed.is_empty());
+
+        *discovery.resolver.addresses.lock().await = vec!["http://10.0.0.2:50051".to_string()];
+
+        let second = discovery.poll().await.unwrap();
+        assert_eq!(second.added.len(), 1);
+        assert_eq!(second.added[0].endpoint, "http://10.0.0.2:50051");
+        assert_eq!(second.removed, vec!["http://10.0.0.1:50051".to_string()]);
+    }
+
+    struct FixedRegistryClient {
+        response: RegistryChanges,
+    }
+
+    impl RegistryClient for FixedRegistryClient {
+        fn changes_since<'a>(
+            &'a self,
+            _since_version: Option<u64>,
+        ) -> Pin<Box<dyn Future<Output = Result<RegistryChanges>> + Send + 'a>> {
+            Box::pin(async move { Ok(self.response.clone()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registry_discovery_tracks_last_version() {
+        let client = FixedRegistryClient {
+            response: RegistryChanges {
+                version: 42,
+                added: vec![WorkerInfo::new(
+                    "http://localhost:50051".to_string(),
+                    "worker-1".to_string(),
+                    (0, 16),
+                )],
+                removed: vec![],
+                error: None,
+            },
+        };
+        let mut discovery = RegistryDiscovery::new(client);
+
+        let delta = discovery.poll().await.unwrap();
+        assert_eq!(delta.added.len(), 1);
+        assert_eq!(discovery.last_version, Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_registry_discovery_surfaces_error_as_err() {
+        let client = FixedRegistryClient {
+            response: RegistryChanges {
+                version: 1,
+                added: vec![],
+                removed: vec![],
+                error: Some("registry snapshot expired".to_string()),
+            },
+        };
+        let mut discovery = RegistryDiscovery::new(client);
+
+        assert!(discovery.poll().await.is_err());
+        assert_eq!(discovery.last_version, None);
+    }
+}
@@ -0,0 +1,200 @@
+//! Self-tuning concurrency governor
+//!
+//! `CoordinatorConfig::max_concurrent_requests` is a hard ceiling that is
+//! difficult to tune correctly across a heterogeneous worker fleet. When
+//! `adaptive_concurrency` is enabled, `ConcurrencyGovernor` instead treats
+//! that value as an upper bound and continuously retunes the actual
+//! in-flight limit from measured end-to-end request latency: it keeps a
+//! sliding window of recent completion times, tracks the lowest latency
+//! observed as a baseline, additively grows the limit by one while observed
+//! latency stays near baseline, and multiplicatively backs it off once
+//! observed latency inflates past a configured multiple of baseline (a sign
+//! of queueing).
+
+use chatloop_common::config::CoordinatorConfig;
+use chatloop_common::metrics::METRICS;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Sentinel value meaning "no baseline latency observed yet"
+const BASELINE_UNSET: u64 = u64::MAX;
+
+/// Tunes the coordinator's in-flight request limit from observed latency
+///
+/// A no-op wrapper around the static `max_concurrent_requests` ceiling when
+/// `adaptive_concurrency` is disabled in config.
+pub struct ConcurrencyGovernor {
+    enabled: bool,
+    min_limit: usize,
+    max_limit: usize,
+    latency_threshold: f64,
+    backoff_factor: f64,
+    window_size: usize,
+    current_limit: AtomicUsize,
+    baseline_nanos: AtomicU64,
+    window: Mutex<VecDeque<Duration>>,
+}
+
+impl ConcurrencyGovernor {
+    /// Build a governor from coordinator config, starting at the configured ceiling
+    pub fn new(config: &CoordinatorConfig) -> Self {
+        Self {
+            enabled: config.adaptive_concurrency,
+            min_limit: config.min_concurrent_requests.min(config.max_concurrent_requests),
+            max_limit: config.max_concurrent_requests,
+            latency_threshold: config.concurrency_latency_threshold,
+            backoff_factor: config.concurrency_backoff_factor,
+            window_size: config.concurrency_latency_window.max(1),
+            current_limit: AtomicUsize::new(config.max_concurrent_requests),
+            baseline_nanos: AtomicU64::new(BASELINE_UNSET),
+            window: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Current in-flight request limit; the static ceiling when adaptive
+    /// concurrency is disabled
+    pub fn limit(&self) -> usize {
+        self.current_limit.load(Ordering::Relaxed)
+    }
+
+    /// Record the end-to-end latency of a just-completed request and retune
+    /// the limit. A no-op when adaptive concurrency is disabled.
+    pub async fn record_completion(&self, latency: Duration) {
+        if !self.enabled {
+            return;
+        }
+
+        let observed = {
+            let mut window = self.window.lock().await;
+            window.push_back(latency);
+            if window.len() > self.window_size {
+                window.pop_front();
+            }
+            window.iter().sum::<Duration>() / window.len() as u32
+        };
+
+        let baseline = self.update_baseline(latency);
+
+        let current = self.current_limit.load(Ordering::Relaxed);
+        let next = if observed.as_secs_f64() > baseline.as_secs_f64() * self.latency_threshold {
+            ((current as f64 * self.backoff_factor) as usize).max(self.min_limit)
+        } else {
+            (current + 1).min(self.max_limit)
+        };
+        self.current_limit.store(next, Ordering::Relaxed);
+
+        METRICS.coordinator.concurrency_limit.set(next as i64);
+        METRICS
+            .coordinator
+            .concurrency_baseline_latency_ms
+            .set(baseline.as_secs_f64() * 1000.0);
+        METRICS
+            .coordinator
+            .concurrency_observed_latency_ms
+            .set(observed.as_secs_f64() * 1000.0);
+    }
+
+    /// Update the running minimum latency and return the current baseline
+    fn update_baseline(&self, latency: Duration) -> Duration {
+        let nanos = latency.as_nanos().min(u128::from(u64::MAX)) as u64;
+        let mut current = self.baseline_nanos.load(Ordering::Relaxed);
+        loop {
+            if current != BASELINE_UNSET && current <= nanos {
+                return Duration::from_nanos(current);
+            }
+            match self.baseline_nanos.compare_exchange_weak(
+                current,
+                nanos,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Duration::from_nanos(nanos),
+                Err(observed) => current = observed,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(adaptive: bool) -> CoordinatorConfig {
+        CoordinatorConfig {
+            worker_endpoints: vec!["http://127.0.0.1:9000".to_string()],
+            discovery_method: "static".to_string(),
+            health_check_interval_secs: 10,
+            failure_threshold: 3,
+            request_timeout_secs: 30,
+            max_concurrent_requests: 16,
+            adaptive_concurrency: adaptive,
+            min_concurrent_requests: 2,
+            concurrency_latency_window: 4,
+            concurrency_latency_threshold: 1.5,
+            concurrency_backoff_factor: 0.5,
+            retry_base_backoff_ms: 1_000,
+            retry_max_backoff_secs: 60,
+            max_queue_depth: 32,
+            max_pending_admissions: 16,
+            admin_enabled: false,
+            admin_port: 9101,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_governor_stays_at_ceiling() {
+        let governor = ConcurrencyGovernor::new(&test_config(false));
+        assert_eq!(governor.limit(), 16);
+
+        governor.record_completion(Duration::from_secs(5)).await;
+        assert_eq!(governor.limit(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_stable_latency_additively_grows_limit() {
+        let governor = ConcurrencyGovernor::new(&test_config(true));
+        assert_eq!(governor.limit(), 16);
+
+        // Start below the ceiling so the additive growth is observable.
+        governor
+            .current_limit
+            .store(10, std::sync::atomic::Ordering::Relaxed);
+
+        for _ in 0..3 {
+            governor.record_completion(Duration::from_millis(10)).await;
+        }
+        // Three requests at the same latency as the baseline: the limit
+        // grows by one each time.
+        assert_eq!(governor.limit(), 13);
+    }
+
+    #[tokio::test]
+    async fn test_latency_spike_backs_off_the_limit() {
+        let governor = ConcurrencyGovernor::new(&test_config(true));
+
+        // Establish a low baseline, then drop below the ceiling so the
+        // multiplicative back-off has somewhere to go.
+        governor.record_completion(Duration::from_millis(10)).await;
+        governor
+            .current_limit
+            .store(10, std::sync::atomic::Ordering::Relaxed);
+
+        // Latency well past threshold * baseline triggers back-off.
+        governor.record_completion(Duration::from_millis(100)).await;
+        assert_eq!(governor.limit(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_limit_never_drops_below_configured_minimum() {
+        let governor = ConcurrencyGovernor::new(&test_config(true));
+        governor.record_completion(Duration::from_millis(10)).await;
+        governor
+            .current_limit
+            .store(3, std::sync::atomic::Ordering::Relaxed);
+
+        governor.record_completion(Duration::from_millis(100)).await;
+        assert_eq!(governor.limit(), 2);
+    }
+}
@@ -2,8 +2,14 @@
 //!
 //! Stateless coordinator for routing inference requests to worker nodes.
 
+#[cfg(feature = "metrics")]
+pub mod admin;
+pub mod discovery;
+pub mod governor;
 pub mod router;
 pub mod worker_client;
 
-pub use router::{Router, WorkerInfo};
+pub use discovery::{DiscoveryBackend, DiscoveryDelta, DnsDiscovery, RegistryDiscovery, StaticDiscovery};
+pub use governor::ConcurrencyGovernor;
+pub use router::{Router, SelectedWorker, WorkerInfo};
 pub use worker_client::WorkerClient;
@@ -4,10 +4,11 @@
 //! It routes inference requests to worker nodes and manages load balancing.
 
 use chatloop_common::{ChatLoopConfig, ChatLoopError, Result};
-use chatloop_coordinator::{Router, WorkerInfo};
+use chatloop_coordinator::discovery::{DiscoveryBackend, StaticDiscovery};
+use chatloop_coordinator::{ConcurrencyGovernor, Router, WorkerInfo};
 use std::time::Duration;
 use tokio::signal;
-use tracing::{error, info};
+use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -27,7 +28,7 @@ async fn main() -> Result<()> {
     let config_path = std::env::var("CHATLOOP_CONFIG")
         .unwrap_or_else(|_| "configs/coordinator-config.yaml".to_string());
 
-    let config = ChatLoopConfig::from_file(&config_path)?;
+    let config = ChatLoopConfig::from_file_with_env_overrides(&config_path)?;
     config.validate()?;
 
     info!(
@@ -35,34 +36,60 @@ async fn main() -> Result<()> {
         config.mode, config.bind_address, config.port
     );
 
+    #[cfg(feature = "metrics")]
+    chatloop_common::metrics::exporter::spawn_if_enabled(&config);
+
     // Get coordinator-specific config
     let coordinator_config = config.coordinator.as_ref()
         .ok_or_else(|| ChatLoopError::config("Coordinator config not found"))?;
 
-    // Create router
-    let router = Router::new(
+    let concurrency_governor = Arc::new(ConcurrencyGovernor::new(coordinator_config));
+    info!(
+        "Concurrency governor initialized: adaptive={}, limit={}",
+        coordinator_config.adaptive_concurrency,
+        concurrency_governor.limit()
+    );
+
+    // Create router. `record_response_time` feeds the governor observed
+    // per-request latency so it keeps retuning, and `select_worker` sheds
+    // requests once `concurrency_governor.limit()` are already in flight.
+    let router = Router::with_admission_control(
         coordinator_config.health_check_interval_secs,
         coordinator_config.failure_threshold,
-    );
+        coordinator_config.retry_base_backoff_ms,
+        coordinator_config.retry_max_backoff_secs,
+        coordinator_config.max_queue_depth,
+        coordinator_config.max_pending_admissions,
+    )
+    .with_concurrency_governor(concurrency_governor.clone());
 
-    // Register initial workers
-    for endpoint in &coordinator_config.worker_endpoints {
-        let worker_info = WorkerInfo::new(
-            endpoint.clone(),
-            format!("worker-{}", endpoint),
-            (0, 32), // Would be loaded from config in production
-        );
+    let router_handle = Arc::new(router);
 
-        match router.register_worker(worker_info).await {
-            Ok(_) => info!("Registered worker: {}", endpoint),
-            Err(e) => error!("Failed to register worker {}: {}", endpoint, e),
-        }
+    // Start the discovery subsystem for the configured method. "static"
+    // re-reports the configured endpoints once, registering them through
+    // the same apply_discovery_delta path the dynamic backends use; "dns"
+    // and "registry" need a real resolver/registry client wired in before
+    // they can run, so fall back to static for now if selected.
+    let configured_workers: Vec<WorkerInfo> = coordinator_config
+        .worker_endpoints
+        .iter()
+        .map(|endpoint| WorkerInfo::new(endpoint.clone(), format!("worker-{}", endpoint), (0, 32)))
+        .collect();
+
+    if coordinator_config.discovery_method != "static" {
+        warn!(
+            "Discovery method '{}' has no resolver/registry client configured yet; falling back to static",
+            coordinator_config.discovery_method
+        );
     }
+    let discovery_backend: Box<dyn DiscoveryBackend> = Box::new(StaticDiscovery::new(configured_workers));
 
-    // Start health check task
-    let router_handle = Arc::new(router);
+    let discovery_handle = router_handle.clone().start_discovery(discovery_backend);
     let health_check_handle = router_handle.clone().start_health_checks();
 
+    #[cfg(feature = "metrics")]
+    chatloop_coordinator::admin::spawn_if_enabled(coordinator_config, router_handle.clone());
+
     info!("ChatLoop Coordinator running");
 
     // Wait for shutdown signal
@@ -73,6 +100,9 @@ async fn main() -> Result<()> {
         result = health_check_handle => {
             result?;
         }
+        result = discovery_handle => {
+            result?;
+        }
     }
 
     info!("ChatLoop Coordinator shutdown complete");
@@ -3,11 +3,18 @@
 //! This module implements intelligent request routing across worker nodes
 //! based on queue depth and health status.
 
+use crate::discovery::{DiscoveryBackend, DiscoveryDelta};
+use crate::governor::ConcurrencyGovernor;
 use crate::worker_client::WorkerClient;
 use chatloop_common::error::{ChatLoopError, Result};
+use chatloop_common::metrics::{WithEndpoint, METRICS};
+use rand::seq::SliceRandom;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
 use tracing::{debug, info, warn};
 
 /// Worker information for routing decisions
@@ -33,31 +40,71 @@ pub struct WorkerInfo {
 
     /// Number of consecutive failures
     pub failure_count: u32,
+
+    /// Number of consecutive failed probes since the last success, drives
+    /// `next_try`'s exponential backoff
+    pub error_count: u32,
+
+    /// When this worker was last probed, whether the probe succeeded or not
+    pub last_try: std::time::Instant,
+
+    /// Earliest instant at which this worker is eligible to be probed again
+    pub next_try: std::time::Instant,
+
+    /// Exponentially-weighted moving average of this worker's observed
+    /// response time in milliseconds, updated by
+    /// [`Router::record_response_time`]. Starts at a small nonzero default
+    /// so a never-yet-measured worker's score is still driven by queue
+    /// depth rather than collapsing to zero.
+    pub ewma_latency_ms: f64,
+
+    /// Set by [`Router::drain_worker`]. A draining worker stays registered
+    /// and keeps serving in-flight work, but `select_worker` stops routing
+    /// new requests to it — the worker can be decommissioned once its
+    /// queue empties, without dropping anything mid-flight.
+    pub draining: bool,
 }
 
+/// Smoothing factor for `WorkerInfo::ewma_latency_ms`: how much weight the
+/// latest observed latency gets versus the running average. Lower is
+/// smoother but slower to react to a worker getting (or recovering from)
+/// a slowdown.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Default EWMA latency assumed for a worker with no observed samples yet
+const DEFAULT_EWMA_LATENCY_MS: f64 = 1.0;
+
 impl WorkerInfo {
     /// Create new worker info
     pub fn new(endpoint: String, worker_id: String, layer_group: (usize, usize)) -> Self {
+        let now = std::time::Instant::now();
         Self {
             endpoint,
             worker_id,
             layer_group,
             queue_depth: 0,
             healthy: true,
-            last_health_check: std::time::Instant::now(),
+            last_health_check: now,
             failure_count: 0,
+            error_count: 0,
+            last_try: now,
+            next_try: now,
+            ewma_latency_ms: DEFAULT_EWMA_LATENCY_MS,
+            draining: false,
         }
     }
 
     /// Calculate load score for routing (lower is better)
+    ///
+    /// Combines queue depth with expected service time so a worker with a
+    /// short queue but pathologically slow forward passes doesn't keep
+    /// getting picked over a busier-but-faster one.
     pub fn load_score(&self) -> f64 {
-        if !self.healthy {
+        if !self.healthy || self.draining {
             return f64::INFINITY;
         }
 
-        // Simple load score based on queue depth
-        // Could be enhanced with latency, throughput, etc.
-        self.queue_depth as f64
+        (self.queue_depth as f64 + 1.0) * self.ewma_latency_ms
     }
 
     /// Check if worker needs health check
@@ -66,6 +113,67 @@ impl WorkerInfo {
     }
 }
 
+/// JSON-serializable snapshot of a [`WorkerInfo`] for the admin/introspection endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerSnapshot {
+    pub worker_id: String,
+    pub endpoint: String,
+    pub layer_group: (usize, usize),
+    pub queue_depth: usize,
+    pub healthy: bool,
+    pub draining: bool,
+    pub failure_count: u32,
+    pub last_health_check_secs_ago: f64,
+}
+
+impl From<&WorkerInfo> for WorkerSnapshot {
+    fn from(worker: &WorkerInfo) -> Self {
+        Self {
+            worker_id: worker.worker_id.clone(),
+            endpoint: worker.endpoint.clone(),
+            layer_group: worker.layer_group,
+            queue_depth: worker.queue_depth,
+            healthy: worker.healthy,
+            draining: worker.draining,
+            failure_count: worker.failure_count,
+            last_health_check_secs_ago: worker.last_health_check.elapsed().as_secs_f64(),
+        }
+    }
+}
+
+/// A worker chosen by [`Router::select_worker`]
+///
+/// Derefs to the worker's endpoint, so it can be used anywhere a `&str`
+/// endpoint is expected. When admission control reserved a slot to admit
+/// this request to a saturated worker, that slot is released when this
+/// value is dropped, so hold onto it for the lifetime of the request.
+pub struct SelectedWorker {
+    pub endpoint: String,
+    _permit: Option<tokio::sync::OwnedSemaphorePermit>,
+    _concurrency_guard: Option<ConcurrencyGuard>,
+}
+
+impl std::ops::Deref for SelectedWorker {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+/// RAII guard that releases one [`Router`]'s in-flight slot when dropped
+///
+/// Held by [`SelectedWorker`] alongside `_permit` so the governor's limit
+/// stays enforced for the whole lifetime of the request, not just at
+/// selection time.
+struct ConcurrencyGuard(Arc<AtomicUsize>);
+
+impl Drop for ConcurrencyGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// Router for distributing requests across workers
 pub struct Router {
     /// Registered workers
@@ -79,19 +187,106 @@ pub struct Router {
 
     /// Failure threshold before marking unhealthy
     failure_threshold: u32,
+
+    /// Base delay for a failed worker's exponential retry backoff
+    retry_base_backoff: std::time::Duration,
+
+    /// Ceiling a failed worker's retry backoff never grows past
+    retry_max_backoff: std::time::Duration,
+
+    /// Queue-depth load score above which `select_worker` applies admission
+    /// control instead of routing to the best worker unconditionally
+    max_queue_depth: usize,
+
+    /// Bounds how many requests can be admitted past a saturated worker at
+    /// once
+    admission_semaphore: Arc<Semaphore>,
+
+    /// Concurrency governor to feed observed per-request latency into, if
+    /// adaptive concurrency is wired up. When present, `select_worker` also
+    /// enforces `governor.limit()` as a hard cap on coordinator-wide
+    /// in-flight requests via `in_flight`.
+    governor: Option<Arc<ConcurrencyGovernor>>,
+
+    /// Count of requests currently holding a [`SelectedWorker`], gated
+    /// against `governor.limit()` in `select_worker` when a governor is
+    /// attached
+    in_flight: Arc<AtomicUsize>,
 }
 
 impl Router {
     /// Create a new router
     pub fn new(health_check_interval_secs: u64, failure_threshold: u32) -> Self {
+        Self::with_retry_backoff(health_check_interval_secs, failure_threshold, 1_000, 60)
+    }
+
+    /// Create a new router with explicit retry backoff bounds
+    ///
+    /// `retry_base_backoff_ms` and `retry_max_backoff_secs` govern how long
+    /// `start_health_checks` waits before re-probing a failed worker: each
+    /// consecutive failure doubles the delay, capped at the max.
+    pub fn with_retry_backoff(
+        health_check_interval_secs: u64,
+        failure_threshold: u32,
+        retry_base_backoff_ms: u64,
+        retry_max_backoff_secs: u64,
+    ) -> Self {
+        Self::with_admission_control(
+            health_check_interval_secs,
+            failure_threshold,
+            retry_base_backoff_ms,
+            retry_max_backoff_secs,
+            usize::MAX,
+            1,
+        )
+    }
+
+    /// Create a new router with explicit retry backoff bounds and admission
+    /// control thresholds
+    ///
+    /// `max_queue_depth` is the load-score ceiling past which a worker is
+    /// considered saturated; `max_pending_admissions` bounds how many
+    /// requests `select_worker` will admit to a saturated worker
+    /// concurrently before shedding load with `ChatLoopError::Overloaded`.
+    pub fn with_admission_control(
+        health_check_interval_secs: u64,
+        failure_threshold: u32,
+        retry_base_backoff_ms: u64,
+        retry_max_backoff_secs: u64,
+        max_queue_depth: usize,
+        max_pending_admissions: usize,
+    ) -> Self {
         Self {
             workers: Arc::new(RwLock::new(HashMap::new())),
             clients: Arc::new(RwLock::new(HashMap::new())),
             health_check_interval: std::time::Duration::from_secs(health_check_interval_secs),
             failure_threshold,
+            retry_base_backoff: std::time::Duration::from_millis(retry_base_backoff_ms),
+            retry_max_backoff: std::time::Duration::from_secs(retry_max_backoff_secs),
+            max_queue_depth,
+            admission_semaphore: Arc::new(Semaphore::new(max_pending_admissions)),
+            governor: None,
+            in_flight: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Attach a concurrency governor so `record_response_time` also feeds it
+    /// observed per-request latency
+    pub fn with_concurrency_governor(mut self, governor: Arc<ConcurrencyGovernor>) -> Self {
+        self.governor = Some(governor);
+        self
+    }
+
+    /// Compute the next retry delay for a worker with `error_count`
+    /// consecutive failures: `min(base * 2^error_count, max_backoff)`
+    fn backoff_duration(&self, error_count: u32) -> std::time::Duration {
+        let exponent = error_count.min(31);
+        self.retry_base_backoff
+            .checked_mul(1u32 << exponent)
+            .unwrap_or(self.retry_max_backoff)
+            .min(self.retry_max_backoff)
+    }
+
     /// Register a worker
     pub async fn register_worker(&self, worker_info: WorkerInfo) -> Result<()> {
         let endpoint = worker_info.endpoint.clone();
@@ -121,7 +316,11 @@ impl Router {
     pub async fn unregister_worker(&self, endpoint: &str) -> Result<()> {
         {
             let mut workers = self.workers.write().await;
-            workers.remove(endpoint);
+            if let Some(worker) = workers.remove(endpoint) {
+                if worker.draining {
+                    METRICS.coordinator.draining_workers.dec();
+                }
+            }
         }
 
         {
@@ -134,38 +333,127 @@ impl Router {
         Ok(())
     }
 
-    /// Select the best worker for a request
+    /// Select a worker for a request using power-of-two-choices
     ///
-    /// Uses least-loaded routing based on queue depth.
-    pub async fn select_worker(&self) -> Result<String> {
+    /// Sampling two random healthy workers and routing to the
+    /// lower-scoring one spreads load nearly as evenly as always picking
+    /// the single global best, without every concurrent caller converging
+    /// on that same "best" worker between health updates. Falls back to a
+    /// full scan when there are fewer than two healthy workers to sample
+    /// from. If even the chosen worker's load score exceeds
+    /// `max_queue_depth`, every worker is saturated, so instead of
+    /// buffering the request indefinitely this reserves a slot from a
+    /// bounded admission semaphore; once that's exhausted, further
+    /// requests are shed with `ChatLoopError::Overloaded` rather than
+    /// piling on. When a [`ConcurrencyGovernor`] is attached, its
+    /// `limit()` is enforced as a second, coordinator-wide cap on
+    /// concurrent in-flight requests before either of those per-worker
+    /// checks run. The returned [`SelectedWorker`] holds both reserved
+    /// slots until it's dropped, so callers should keep it alive for the
+    /// duration of the request rather than discarding it immediately.
+    pub async fn select_worker(&self) -> Result<SelectedWorker> {
+        let concurrency_guard = self.acquire_concurrency_slot()?;
+
         let workers = self.workers.read().await;
 
         if workers.is_empty() {
             return Err(ChatLoopError::worker_unavailable("No workers available"));
         }
 
-        // Find worker with lowest load score
-        let best_worker = workers
-            .values()
-            .filter(|w| w.healthy)
-            .min_by(|a, b| {
+        let candidates: Vec<&WorkerInfo> =
+            workers.values().filter(|w| w.healthy && !w.draining).collect();
+
+        let worker = if candidates.len() <= 2 {
+            candidates.into_iter().min_by(|a, b| {
                 a.load_score()
                     .partial_cmp(&b.load_score())
                     .unwrap_or(std::cmp::Ordering::Equal)
-            });
-
-        match best_worker {
-            Some(worker) => {
-                debug!(
-                    "Selected worker {} with queue depth {}",
-                    worker.worker_id, worker.queue_depth
-                );
-                Ok(worker.endpoint.clone())
+            })
+        } else {
+            let mut rng = rand::thread_rng();
+            candidates
+                .choose_multiple(&mut rng, 2)
+                .copied()
+                .min_by(|a, b| {
+                    a.load_score()
+                        .partial_cmp(&b.load_score())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+        };
+
+        let worker = match worker {
+            Some(worker) => worker,
+            None => {
+                return Err(ChatLoopError::worker_unavailable(
+                    "No healthy workers available",
+                ))
+            }
+        };
+
+        let load_score = worker.load_score();
+        let permit = if load_score > self.max_queue_depth as f64 {
+            match Arc::clone(&self.admission_semaphore).try_acquire_owned() {
+                Ok(permit) => {
+                    debug!(
+                        "Admitting request to saturated worker {} (load score {} > max_queue_depth {})",
+                        worker.worker_id, load_score, self.max_queue_depth
+                    );
+                    Some(permit)
+                }
+                Err(_) => {
+                    METRICS.coordinator.requests_throttled.inc();
+                    warn!(
+                        "Shedding request: worker {} load score {} exceeds max_queue_depth {}",
+                        worker.worker_id, load_score, self.max_queue_depth
+                    );
+                    return Err(ChatLoopError::overloaded(format!(
+                        "Worker {} load score {} exceeds max_queue_depth {}",
+                        worker.worker_id, load_score, self.max_queue_depth
+                    )));
+                }
             }
-            None => Err(ChatLoopError::worker_unavailable(
-                "No healthy workers available",
-            )),
+        } else {
+            None
+        };
+
+        debug!(
+            "Selected worker {} with queue depth {}",
+            worker.worker_id, worker.queue_depth
+        );
+        Ok(SelectedWorker {
+            endpoint: worker.endpoint.clone(),
+            _permit: permit,
+            _concurrency_guard: concurrency_guard,
+        })
+    }
+
+    /// Reserve one in-flight slot against the attached [`ConcurrencyGovernor`]'s
+    /// current `limit()`, shedding with `ChatLoopError::Overloaded` if the
+    /// limit is already reached. A no-op (always admits) when no governor is
+    /// attached.
+    fn acquire_concurrency_slot(&self) -> Result<Option<ConcurrencyGuard>> {
+        let Some(governor) = &self.governor else {
+            return Ok(None);
+        };
+
+        let limit = governor.limit();
+        let reserved = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if reserved > limit {
+            self.in_flight.fetch_sub(1, Ordering::Relaxed);
+            METRICS.coordinator.requests_throttled.inc();
+            warn!(
+                "Shedding request: {} in-flight requests at or above governor limit {}",
+                reserved - 1,
+                limit
+            );
+            return Err(ChatLoopError::overloaded(format!(
+                "Coordinator at governor concurrency limit ({})",
+                limit
+            )));
         }
+
+        Ok(Some(ConcurrencyGuard(Arc::clone(&self.in_flight))))
     }
 
     /// Get a worker client by endpoint
@@ -183,33 +471,107 @@ impl Router {
         }
     }
 
+    /// Record an observed response time for a worker
+    ///
+    /// Feeds `coordinator_worker_response_time` and rolls the sample into
+    /// the worker's `ewma_latency_ms`, which `load_score` uses alongside
+    /// queue depth to steer routing away from workers that are slow even
+    /// when their queue is short. Also hands the latency to the attached
+    /// [`ConcurrencyGovernor`], if any, so it can retune its limit, which
+    /// `select_worker` enforces as a cap on in-flight requests.
+    pub async fn record_response_time(&self, endpoint: &str, latency: Duration) {
+        METRICS
+            .coordinator
+            .worker_response_time
+            .with_endpoint(endpoint)
+            .observe(latency.as_secs_f64());
+
+        let mut workers = self.workers.write().await;
+        if let Some(worker) = workers.get_mut(endpoint) {
+            let sample_ms = latency.as_secs_f64() * 1000.0;
+            worker.ewma_latency_ms =
+                LATENCY_EWMA_ALPHA * sample_ms + (1.0 - LATENCY_EWMA_ALPHA) * worker.ewma_latency_ms;
+        }
+        drop(workers);
+
+        if let Some(governor) = &self.governor {
+            governor.record_completion(latency).await;
+        }
+    }
+
     /// Mark worker as failed
     pub async fn mark_failed(&self, endpoint: &str) {
+        let now = std::time::Instant::now();
         let mut workers = self.workers.write().await;
-        if let Some(worker) = workers.get_mut(endpoint) {
-            worker.failure_count += 1;
-
-            if worker.failure_count >= self.failure_threshold {
-                worker.healthy = false;
-                warn!(
-                    "Worker {} marked as unhealthy after {} failures",
-                    endpoint, worker.failure_count
-                );
-            }
+        let Some(worker) = workers.get_mut(endpoint) else {
+            return;
+        };
+
+        worker.failure_count += 1;
+        worker.error_count += 1;
+        worker.last_try = now;
+        worker.next_try = now + self.backoff_duration(worker.error_count);
+
+        if worker.failure_count >= self.failure_threshold {
+            worker.healthy = false;
+            warn!(
+                "Worker {} marked as unhealthy after {} failures",
+                endpoint, worker.failure_count
+            );
         }
     }
 
     /// Mark worker as healthy
     pub async fn mark_healthy(&self, endpoint: &str) {
+        let now = std::time::Instant::now();
         let mut workers = self.workers.write().await;
         if let Some(worker) = workers.get_mut(endpoint) {
             worker.healthy = true;
             worker.failure_count = 0;
-            worker.last_health_check = std::time::Instant::now();
+            worker.error_count = 0;
+            worker.last_health_check = now;
+            worker.last_try = now;
+            worker.next_try = now;
             debug!("Worker {} marked as healthy", endpoint);
         }
     }
 
+    /// Begin draining a worker
+    ///
+    /// The worker stays registered and keeps any in-flight requests, but
+    /// `select_worker` stops routing new ones to it. Use this ahead of a
+    /// rolling restart or a capacity change so a worker can be taken out
+    /// of rotation without dropping requests that are already in flight.
+    pub async fn drain_worker(&self, endpoint: &str) {
+        let mut workers = self.workers.write().await;
+        if let Some(worker) = workers.get_mut(endpoint) {
+            if !worker.draining {
+                worker.draining = true;
+                METRICS.coordinator.draining_workers.inc();
+                info!("Worker {} draining", endpoint);
+            }
+        }
+    }
+
+    /// Resume routing new requests to a previously drained worker
+    pub async fn resume_worker(&self, endpoint: &str) {
+        let mut workers = self.workers.write().await;
+        if let Some(worker) = workers.get_mut(endpoint) {
+            if worker.draining {
+                worker.draining = false;
+                METRICS.coordinator.draining_workers.dec();
+                info!("Worker {} resumed", endpoint);
+            }
+        }
+    }
+
+    /// Take a JSON-serializable snapshot of every registered worker, for
+    /// an admin/introspection endpoint
+    pub async fn snapshot(&self) -> Vec<WorkerSnapshot> {
+        let workers = self.workers.read().await;
+        workers.values().map(WorkerSnapshot::from).collect()
+    }
+
     /// Get all worker endpoints
     pub async fn get_worker_endpoints(&self) -> Vec<String> {
         let workers = self.workers.read().await;
@@ -222,7 +584,73 @@ impl Router {
         workers.values().filter(|w| w.healthy).count()
     }
 
+    /// Apply an incremental discovery delta to the routing table
+    ///
+    /// Newly discovered workers are registered immediately. Workers missing
+    /// from a poll aren't evicted on the first miss — they're marked
+    /// failed the same way a failed health check would, and only
+    /// unregistered once `failure_threshold` consecutive misses
+    /// accumulate, so routing doesn't flap on a single stale discovery
+    /// response.
+    pub async fn apply_discovery_delta(&self, delta: DiscoveryDelta) -> Result<()> {
+        for worker in delta.added {
+            let already_known = self.workers.read().await.contains_key(&worker.endpoint);
+            if already_known {
+                continue;
+            }
+            self.register_worker(worker).await?;
+        }
+
+        for endpoint in delta.removed {
+            self.mark_failed(&endpoint).await;
+
+            let should_evict = self
+                .workers
+                .read()
+                .await
+                .get(&endpoint)
+                .map(|w| w.failure_count >= self.failure_threshold)
+                .unwrap_or(false);
+
+            if should_evict {
+                self.unregister_worker(&endpoint).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start a background task that polls `backend` on the health-check
+    /// cadence and applies the resulting delta to the routing table
+    pub fn start_discovery(
+        self: Arc<Self>,
+        mut backend: Box<dyn DiscoveryBackend>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.health_check_interval);
+
+            loop {
+                interval.tick().await;
+
+                match backend.poll().await {
+                    Ok(delta) => {
+                        if let Err(e) = self.apply_discovery_delta(delta).await {
+                            warn!("Failed to apply discovery delta: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Worker discovery poll failed: {}", e);
+                    }
+                }
+            }
+        })
+    }
+
     /// Start background health check task
+    ///
+    /// Skips any worker whose `next_try` hasn't arrived yet, so a worker
+    /// that's been failing consistently gets probed on its own
+    /// exponentially-growing backoff schedule instead of every tick.
     pub fn start_health_checks(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(self.health_check_interval);
@@ -230,9 +658,17 @@ impl Router {
             loop {
                 interval.tick().await;
 
-                let endpoints = self.get_worker_endpoints().await;
+                let now = std::time::Instant::now();
+                let due_endpoints: Vec<String> = {
+                    let workers = self.workers.read().await;
+                    workers
+                        .values()
+                        .filter(|w| w.next_try <= now)
+                        .map(|w| w.endpoint.clone())
+                        .collect()
+                };
 
-                for endpoint in endpoints {
+                for endpoint in due_endpoints {
                     // Perform health check
                     match self.perform_health_check(&endpoint).await {
                         Ok(healthy) => {
@@ -315,4 +751,380 @@ mod tests {
 
         assert_eq!(router.healthy_worker_count().await, 1);
     }
+
+    #[tokio::test]
+    async fn test_apply_discovery_delta_registers_added_workers() {
+        let router = Router::new(5, 3);
+
+        router
+            .apply_discovery_delta(DiscoveryDelta {
+                added: vec![WorkerInfo::new(
+                    "http://localhost:50051".to_string(),
+                    "worker-1".to_string(),
+                    (0, 16),
+                )],
+                removed: vec![],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(router.healthy_worker_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_discovery_delta_evicts_only_after_failure_threshold() {
+        let router = Router::new(5, 2);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        // First miss: marked failed, but below the threshold, so still registered.
+        router
+            .apply_discovery_delta(DiscoveryDelta {
+                added: vec![],
+                removed: vec!["http://localhost:50051".to_string()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(router.get_worker_endpoints().await.len(), 1);
+
+        // Second consecutive miss reaches failure_threshold and evicts the worker.
+        router
+            .apply_discovery_delta(DiscoveryDelta {
+                added: vec![],
+                removed: vec!["http://localhost:50051".to_string()],
+            })
+            .await
+            .unwrap();
+        assert!(router.get_worker_endpoints().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mark_failed_schedules_exponentially_growing_next_try() {
+        let router = Router::with_retry_backoff(5, 100, 1_000, 60);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        router.mark_failed("http://localhost:50051").await;
+        let first_delay = {
+            let workers = router.workers.read().await;
+            let worker = &workers["http://localhost:50051"];
+            assert_eq!(worker.error_count, 1);
+            worker.next_try.duration_since(worker.last_try)
+        };
+
+        router.mark_failed("http://localhost:50051").await;
+        let second_delay = {
+            let workers = router.workers.read().await;
+            let worker = &workers["http://localhost:50051"];
+            assert_eq!(worker.error_count, 2);
+            worker.next_try.duration_since(worker.last_try)
+        };
+
+        assert!(second_delay > first_delay);
+    }
+
+    #[tokio::test]
+    async fn test_mark_failed_backoff_is_capped_at_max() {
+        let router = Router::with_retry_backoff(5, 100, 1_000, 5);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        for _ in 0..10 {
+            router.mark_failed("http://localhost:50051").await;
+        }
+
+        let workers = router.workers.read().await;
+        let worker = &workers["http://localhost:50051"];
+        assert!(worker.next_try.duration_since(worker.last_try) <= std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_mark_healthy_resets_error_count_and_next_try() {
+        let router = Router::with_retry_backoff(5, 2, 1_000, 60);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        router.mark_failed("http://localhost:50051").await;
+        router.mark_healthy("http://localhost:50051").await;
+
+        let workers = router.workers.read().await;
+        let worker = &workers["http://localhost:50051"];
+        assert_eq!(worker.error_count, 0);
+        assert!(worker.next_try <= std::time::Instant::now());
+    }
+
+    #[tokio::test]
+    async fn test_select_worker_admits_below_max_queue_depth() {
+        let router = Router::with_admission_control(5, 3, 1_000, 60, 10, 1);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+        router.update_queue_depth("http://localhost:50051", 5).await;
+
+        assert!(router.select_worker().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_select_worker_sheds_load_once_admission_budget_exhausted() {
+        let router = Router::with_admission_control(5, 3, 1_000, 60, 10, 1);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+        router.update_queue_depth("http://localhost:50051", 20).await;
+
+        // Hold the single admission permit for the duration of this call.
+        let _first = router.select_worker().await.unwrap();
+
+        let second = router.select_worker().await;
+        assert!(matches!(second, Err(ChatLoopError::Overloaded(_))));
+    }
+
+    fn test_coordinator_config(max_concurrent_requests: usize) -> chatloop_common::config::CoordinatorConfig {
+        chatloop_common::config::CoordinatorConfig {
+            worker_endpoints: vec!["http://127.0.0.1:9000".to_string()],
+            discovery_method: "static".to_string(),
+            health_check_interval_secs: 5,
+            failure_threshold: 3,
+            request_timeout_secs: 30,
+            max_concurrent_requests,
+            adaptive_concurrency: false,
+            min_concurrent_requests: 1,
+            concurrency_latency_window: 4,
+            concurrency_latency_threshold: 1.5,
+            concurrency_backoff_factor: 0.5,
+            retry_base_backoff_ms: 1_000,
+            retry_max_backoff_secs: 60,
+            max_queue_depth: usize::MAX,
+            max_pending_admissions: 1,
+            admin_enabled: false,
+            admin_port: 9101,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_select_worker_admits_up_to_governor_limit() {
+        let governor = Arc::new(ConcurrencyGovernor::new(&test_coordinator_config(2)));
+        let router = Router::with_retry_backoff(5, 3, 1_000, 60).with_concurrency_governor(governor);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        // Hold both governor-limited slots for the duration of this call.
+        let _first = router.select_worker().await.unwrap();
+        let _second = router.select_worker().await.unwrap();
+
+        let third = router.select_worker().await;
+        assert!(matches!(third, Err(ChatLoopError::Overloaded(_))));
+    }
+
+    #[tokio::test]
+    async fn test_select_worker_releases_governor_slot_on_drop() {
+        let governor = Arc::new(ConcurrencyGovernor::new(&test_coordinator_config(1)));
+        let router = Router::with_retry_backoff(5, 3, 1_000, 60).with_concurrency_governor(governor);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        {
+            let _first = router.select_worker().await.unwrap();
+            assert!(router.select_worker().await.is_err());
+        }
+
+        assert!(router.select_worker().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_load_score_combines_queue_depth_and_latency() {
+        let mut fast = WorkerInfo::new(
+            "http://localhost:50051".to_string(),
+            "worker-1".to_string(),
+            (0, 16),
+        );
+        fast.queue_depth = 0;
+        fast.ewma_latency_ms = 1.0;
+
+        let mut slow = WorkerInfo::new(
+            "http://localhost:50052".to_string(),
+            "worker-2".to_string(),
+            (0, 16),
+        );
+        slow.queue_depth = 0;
+        slow.ewma_latency_ms = 500.0;
+
+        assert!(fast.load_score() < slow.load_score());
+    }
+
+    #[tokio::test]
+    async fn test_record_response_time_updates_ewma_latency() {
+        let router = Router::new(5, 3);
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        for _ in 0..50 {
+            router
+                .record_response_time("http://localhost:50051", std::time::Duration::from_millis(100))
+                .await;
+        }
+
+        let workers = router.workers.read().await;
+        let worker = &workers["http://localhost:50051"];
+        assert!((worker.ewma_latency_ms - 100.0).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_select_worker_prefers_lower_score_among_sampled_pair() {
+        let router = Router::new(5, 3);
+
+        // A single pathologically slow worker among several fast ones.
+        // With >2 healthy candidates, select_worker samples a random pair,
+        // so whichever pair includes the slow worker also includes a fast
+        // one (there's only one slow worker) — the slow one should never
+        // win that comparison.
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:60050".to_string(),
+                "slow".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+        router
+            .record_response_time("http://localhost:60050", Duration::from_millis(1_000))
+            .await;
+
+        for i in 0..4 {
+            router
+                .register_worker(WorkerInfo::new(
+                    format!("http://localhost:6006{}", i),
+                    format!("fast-{}", i),
+                    (0, 16),
+                ))
+                .await
+                .unwrap();
+        }
+
+        for _ in 0..20 {
+            let selected = router.select_worker().await.unwrap();
+            assert!(!selected.starts_with("http://localhost:60050"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drain_worker_excludes_it_from_selection() {
+        let router = Router::new(5, 3);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50052".to_string(),
+                "worker-2".to_string(),
+                (0, 16),
+            ))
+            .await
+            .unwrap();
+
+        router.drain_worker("http://localhost:50051").await;
+
+        for _ in 0..10 {
+            let selected = router.select_worker().await.unwrap();
+            assert_eq!(&*selected, "http://localhost:50052");
+        }
+
+        router.resume_worker("http://localhost:50051").await;
+        {
+            let workers = router.workers.read().await;
+            assert!(!workers["http://localhost:50051"].draining);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_reports_registered_workers() {
+        let router = Router::new(5, 3);
+
+        router
+            .register_worker(WorkerInfo::new(
+                "http://localhost:50051".to_string(),
+                "worker-1".to_string(),
+                (3, 6),
+            ))
+            .await
+            .unwrap();
+        router.update_queue_depth("http://localhost:50051", 7).await;
+
+        let snapshot = router.snapshot().await;
+        assert_eq!(snapshot.len(), 1);
+        let entry = &snapshot[0];
+        assert_eq!(entry.worker_id, "worker-1");
+        assert_eq!(entry.endpoint, "http://localhost:50051");
+        assert_eq!(entry.layer_group, (3, 6));
+        assert_eq!(entry.queue_depth, 7);
+        assert!(entry.healthy);
+        assert!(!entry.draining);
+        assert_eq!(entry.failure_count, 0);
+    }
 }
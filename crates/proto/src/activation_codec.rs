@@ -0,0 +1,360 @@
+//! Binary, quantization-aware wire codec for inter-worker activations
+//!
+//! `ForwardRequest`/`ForwardResponse::hidden_states` are moved between
+//! pipeline stages as plain `Vec<f32>`, so routing them through `JsonCodec`
+//! (see `codec.rs`) would pay a per-element parse cost on every hop, which
+//! dominates cross-stage latency once hidden dimensions get large. This
+//! module instead lays out a fixed little-endian header followed by a raw
+//! little-endian payload that is read back into `f32`s (or dequantized) with
+//! one fixed-width read per element and no text parsing, and
+//! optionally narrows the payload itself to FP16 or INT8 to cut wire bytes
+//! for quantized workers. The payload is not padded to any alignment, and
+//! decoding copies it into a new `Vec<f32>` rather than casting the received
+//! bytes in place. [`ActivationCodec`] plugs this frame format into
+//! `tonic` as a `Codec`, the same way `JsonCodec` plugs in `serde_json`.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chatloop_common::{ChatLoopError, Result};
+use half::f16;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::Status;
+
+use crate::{ForwardRequest, ForwardResponse};
+
+/// On-the-wire element encoding for an activation tensor
+///
+/// Roughly mirrors `chatloop_common::config::QuantizationType`: a worker
+/// running `QuantizationType::None` sends `Fp32`, and one running
+/// `QuantizationType::Int8` or `QuantizationType::Int4` sends `Int8`
+/// activations (weights may be packed tighter than activations, but the
+/// wire format only needs to distinguish the three payload shapes below).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ActivationDType {
+    /// Raw 4-byte-per-element floats
+    Fp32 = 0,
+    /// 2-byte-per-element half-precision floats
+    Fp16 = 1,
+    /// 1-byte-per-element quantized ints with a per-tensor scale/zero-point
+    Int8 = 2,
+}
+
+impl ActivationDType {
+    fn from_tag(tag: u8) -> Result<Self> {
+        match tag {
+            0 => Ok(ActivationDType::Fp32),
+            1 => Ok(ActivationDType::Fp16),
+            2 => Ok(ActivationDType::Int8),
+            other => Err(ChatLoopError::invalid_input(format!(
+                "unknown activation dtype tag: {}",
+                other
+            ))),
+        }
+    }
+}
+
+// request_id_len(u32) + sequence_id(u64) + element_count(u32) + dtype_tag(u8)
+const HEADER_LEN: usize = 4 + 8 + 4 + 1;
+// scale(f32) + zero_point(i32), only present when dtype == Int8
+const INT8_PARAMS_LEN: usize = 4 + 4;
+
+/// Per-tensor asymmetric scale/zero-point, matching
+/// `chatloop_worker::tensor::ops::quantize_int8`'s convention of
+/// `q = round(x / scale + zero_point)` / `x = (q - zero_point) * scale`
+fn int8_quantization_params(data: &[f32]) -> (f32, i32) {
+    let min = data.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = data.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let scale = if max > min { (max - min) / 255.0 } else { 1.0 };
+    let zero_point = (-min / scale).round() as i32 - 128;
+    (scale, zero_point)
+}
+
+/// Encode a request id, sequence id, and activation tensor into a single
+/// length-prefixed-free frame. `ForwardResponse` has no sequence id of its
+/// own, so it passes `0` to keep both structs on the same header shape.
+fn encode_frame(request_id: &str, sequence_id: u64, hidden_states: &[f32], dtype: ActivationDType) -> Bytes {
+    let id_bytes = request_id.as_bytes();
+
+    let payload_len = match dtype {
+        ActivationDType::Fp32 => hidden_states.len() * 4,
+        ActivationDType::Fp16 => hidden_states.len() * 2,
+        ActivationDType::Int8 => INT8_PARAMS_LEN + hidden_states.len(),
+    };
+
+    let mut buf = BytesMut::with_capacity(HEADER_LEN + id_bytes.len() + payload_len);
+    buf.put_u32_le(id_bytes.len() as u32);
+    buf.put_u64_le(sequence_id);
+    buf.put_u32_le(hidden_states.len() as u32);
+    buf.put_u8(dtype as u8);
+    buf.put_slice(id_bytes);
+
+    match dtype {
+        ActivationDType::Fp32 => {
+            for &v in hidden_states {
+                buf.put_f32_le(v);
+            }
+        }
+        ActivationDType::Fp16 => {
+            for &v in hidden_states {
+                buf.put_u16_le(f16::from_f32(v).to_bits());
+            }
+        }
+        ActivationDType::Int8 => {
+            let (scale, zero_point) = int8_quantization_params(hidden_states);
+            buf.put_f32_le(scale);
+            buf.put_i32_le(zero_point);
+            for &v in hidden_states {
+                let q = (v / scale + zero_point as f32).round().clamp(i8::MIN as f32, i8::MAX as f32);
+                buf.put_i8(q as i8);
+            }
+        }
+    }
+
+    buf.freeze()
+}
+
+/// Decode a frame produced by `encode_frame`, returning the request id,
+/// sequence id (`0` for frames encoded by a `ForwardResponse`), and
+/// dequantized activation tensor.
+fn decode_frame(mut src: &[u8]) -> Result<(String, u64, Vec<f32>)> {
+    if src.remaining() < HEADER_LEN {
+        return Err(ChatLoopError::invalid_input(
+            "activation frame shorter than fixed header",
+        ));
+    }
+
+    let id_len = src.get_u32_le() as usize;
+    let sequence_id = src.get_u64_le();
+    let element_count = src.get_u32_le() as usize;
+    let dtype = ActivationDType::from_tag(src.get_u8())?;
+
+    if src.remaining() < id_len {
+        return Err(ChatLoopError::invalid_input(
+            "activation frame truncated before request_id",
+        ));
+    }
+    let id_bytes = src.copy_to_bytes(id_len);
+    let request_id = String::from_utf8(id_bytes.to_vec())
+        .map_err(|e| ChatLoopError::invalid_input(format!("request_id is not valid utf-8: {}", e)))?;
+
+    let hidden_states = match dtype {
+        ActivationDType::Fp32 => {
+            if src.remaining() < element_count * 4 {
+                return Err(ChatLoopError::invalid_input(
+                    "activation frame truncated before fp32 payload",
+                ));
+            }
+            (0..element_count).map(|_| src.get_f32_le()).collect()
+        }
+        ActivationDType::Fp16 => {
+            if src.remaining() < element_count * 2 {
+                return Err(ChatLoopError::invalid_input(
+                    "activation frame truncated before fp16 payload",
+                ));
+            }
+            (0..element_count)
+                .map(|_| f16::from_bits(src.get_u16_le()).to_f32())
+                .collect()
+        }
+        ActivationDType::Int8 => {
+            if src.remaining() < INT8_PARAMS_LEN + element_count {
+                return Err(ChatLoopError::invalid_input(
+                    "activation frame truncated before int8 payload",
+                ));
+            }
+            let scale = src.get_f32_le();
+            let zero_point = src.get_i32_le();
+            (0..element_count)
+                .map(|_| (src.get_i8() as i32 - zero_point) as f32 * scale)
+                .collect()
+        }
+    };
+
+    Ok((request_id, sequence_id, hidden_states))
+}
+
+impl ForwardRequest {
+    /// Encode this request as a length-prefixed-free binary frame
+    ///
+    /// See the module docs for the header/payload layout.
+    pub fn encode(&self, dtype: ActivationDType) -> Bytes {
+        encode_frame(&self.request_id, self.sequence_id, &self.hidden_states, dtype)
+    }
+
+    /// Decode a frame produced by `encode`
+    pub fn decode(src: &[u8]) -> Result<Self> {
+        let (request_id, sequence_id, hidden_states) = decode_frame(src)?;
+        Ok(ForwardRequest {
+            request_id,
+            sequence_id,
+            hidden_states,
+        })
+    }
+}
+
+impl ForwardResponse {
+    /// Encode this response as a length-prefixed-free binary frame
+    ///
+    /// See the module docs for the header/payload layout.
+    pub fn encode(&self, dtype: ActivationDType) -> Bytes {
+        encode_frame(&self.request_id, 0, &self.hidden_states, dtype)
+    }
+
+    /// Decode a frame produced by `encode`
+    pub fn decode(src: &[u8]) -> Result<Self> {
+        let (request_id, _sequence_id, hidden_states) = decode_frame(src)?;
+        Ok(ForwardResponse {
+            request_id,
+            hidden_states,
+        })
+    }
+}
+
+/// `tonic::codec::Codec` implementation that moves `ForwardRequest`/
+/// `ForwardResponse` as the fixed-layout binary frames above instead of
+/// `JsonCodec`'s per-element JSON parse
+///
+/// Unlike `JsonCodec<T, U>`, which is generic over any `Serialize`/
+/// `DeserializeOwned` pair, this codec is hardwired to the one direction a
+/// pipeline-forwarding client actually needs: encode a `ForwardRequest`,
+/// decode a `ForwardResponse`. The `dtype` passed to `new` controls how the
+/// request's hidden states are narrowed on the wire; the response is always
+/// decoded back into `f32`s regardless of how it was quantized.
+#[derive(Clone)]
+pub struct ActivationCodec {
+    dtype: ActivationDType,
+}
+
+impl ActivationCodec {
+    /// Build a codec that encodes outgoing `ForwardRequest`s using `dtype`
+    pub fn new(dtype: ActivationDType) -> Self {
+        Self { dtype }
+    }
+}
+
+impl Codec for ActivationCodec {
+    type Encode = ForwardRequest;
+    type Decode = ForwardResponse;
+    type Encoder = ActivationEncoder;
+    type Decoder = ActivationDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        ActivationEncoder { dtype: self.dtype }
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        ActivationDecoder
+    }
+}
+
+/// Encodes a `ForwardRequest` as a single binary activation frame
+pub struct ActivationEncoder {
+    dtype: ActivationDType,
+}
+
+impl Encoder for ActivationEncoder {
+    type Item = ForwardRequest;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item.encode(self.dtype));
+        Ok(())
+    }
+}
+
+/// Decodes a single binary activation frame into a `ForwardResponse`
+pub struct ActivationDecoder;
+
+impl Decoder for ActivationDecoder {
+    type Item = ForwardResponse;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        if !src.has_remaining() {
+            return Ok(None);
+        }
+
+        let bytes = src.copy_to_bytes(src.remaining());
+        ForwardResponse::decode(&bytes)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("activation frame decode error: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_hidden_states() -> Vec<f32> {
+        vec![-3.5, -1.0, 0.0, 0.25, 1.5, 4.0, 8.25]
+    }
+
+    #[test]
+    fn test_forward_request_roundtrips_fp32() {
+        let req = ForwardRequest {
+            request_id: "req-1".to_string(),
+            sequence_id: 42,
+            hidden_states: sample_hidden_states(),
+        };
+
+        let frame = req.encode(ActivationDType::Fp32);
+        let decoded = ForwardRequest::decode(&frame).unwrap();
+
+        assert_eq!(decoded.request_id, req.request_id);
+        assert_eq!(decoded.sequence_id, req.sequence_id);
+        assert_eq!(decoded.hidden_states, req.hidden_states);
+    }
+
+    #[test]
+    fn test_forward_request_fp16_is_lossy_but_close() {
+        let req = ForwardRequest {
+            request_id: "req-2".to_string(),
+            sequence_id: 7,
+            hidden_states: sample_hidden_states(),
+        };
+
+        let frame = req.encode(ActivationDType::Fp16);
+        assert!(frame.len() < req.encode(ActivationDType::Fp32).len());
+
+        let decoded = ForwardRequest::decode(&frame).unwrap();
+        for (a, b) in decoded.hidden_states.iter().zip(req.hidden_states.iter()) {
+            assert!((a - b).abs() < 1e-2, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_forward_response_int8_roundtrips_within_quantization_error() {
+        let resp = ForwardResponse {
+            request_id: "req-3".to_string(),
+            hidden_states: sample_hidden_states(),
+        };
+
+        let frame = resp.encode(ActivationDType::Int8);
+        assert!(frame.len() < resp.encode(ActivationDType::Fp32).len());
+
+        let decoded = ForwardResponse::decode(&frame).unwrap();
+        assert_eq!(decoded.request_id, resp.request_id);
+        for (a, b) in decoded.hidden_states.iter().zip(resp.hidden_states.iter()) {
+            assert!((a - b).abs() < 0.1, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_header() {
+        let err = ForwardRequest::decode(&[0u8; 3]).unwrap_err();
+        assert!(matches!(err, ChatLoopError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_dtype_tag() {
+        let req = ForwardRequest {
+            request_id: "req-4".to_string(),
+            sequence_id: 1,
+            hidden_states: vec![1.0],
+        };
+        let mut frame = req.encode(ActivationDType::Fp32).to_vec();
+        frame[16] = 9; // dtype tag byte
+        let err = ForwardRequest::decode(&frame).unwrap_err();
+        assert!(matches!(err, ChatLoopError::InvalidInput(_)));
+    }
+}
@@ -0,0 +1,81 @@
+//! Shared JSON-over-gRPC codec
+//!
+//! This crate's message types are plain `serde` structs rather than
+//! `prost`-generated ones (see the crate-level placeholder note), so
+//! hand-written clients/servers move them over a `tonic` HTTP/2 channel
+//! using this codec instead of `tonic`'s default protobuf codec.
+
+use bytes::{Buf, BufMut};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::Status;
+
+/// `tonic::codec::Codec` implementation that (de)serializes messages as JSON
+pub struct JsonCodec<T, U>(PhantomData<(T, U)>);
+
+impl<T, U> Default for JsonCodec<T, U> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T, U> Clone for JsonCodec<T, U> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T, U> Codec for JsonCodec<T, U>
+where
+    T: Serialize + Send + Sync + 'static,
+    U: DeserializeOwned + Send + Sync + 'static,
+{
+    type Encode = T;
+    type Decode = U;
+    type Encoder = JsonEncoder<T>;
+    type Decoder = JsonDecoder<U>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        JsonEncoder(PhantomData)
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        JsonDecoder(PhantomData)
+    }
+}
+
+/// Encodes a message as a single JSON-serialized frame
+pub struct JsonEncoder<T>(PhantomData<T>);
+
+impl<T: Serialize> Encoder for JsonEncoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        let bytes = serde_json::to_vec(&item)
+            .map_err(|e| Status::internal(format!("JSON encode error: {}", e)))?;
+        dst.put_slice(&bytes);
+        Ok(())
+    }
+}
+
+/// Decodes a single JSON-serialized frame into a message
+pub struct JsonDecoder<U>(PhantomData<U>);
+
+impl<U: DeserializeOwned> Decoder for JsonDecoder<U> {
+    type Item = U;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        if !src.has_remaining() {
+            return Ok(None);
+        }
+
+        let bytes = src.copy_to_bytes(src.remaining());
+        let item = serde_json::from_slice(&bytes)
+            .map_err(|e| Status::internal(format!("JSON decode error: {}", e)))?;
+        Ok(Some(item))
+    }
+}
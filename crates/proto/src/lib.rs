@@ -5,6 +5,11 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod activation_codec;
+pub mod codec;
+pub use activation_codec::{ActivationCodec, ActivationDType};
+pub use codec::JsonCodec;
+
 // Inference Service types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceRequest {
@@ -47,6 +52,69 @@ pub struct HealthCheckResponse {
     pub serving: bool,
 }
 
+/// Lowest protocol version this build of the worker can speak
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+
+/// Highest protocol version this build of the worker can speak
+pub const MAX_PROTOCOL_VERSION: u16 = 1;
+
+/// Identity exchanged between pipeline-adjacent workers before forwarding
+///
+/// Two workers must agree on `model_id` and `weights_digest` exactly, and on
+/// a mutually-supported `protocol_version`, before either will forward
+/// activations to the other.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkerVersion {
+    /// Model identifier this worker loaded a partition of
+    pub model_id: String,
+
+    /// Wire protocol version this build speaks
+    pub protocol_version: u16,
+
+    /// sha256 digest of the memory-mapped weights file
+    pub weights_digest: String,
+}
+
+impl WorkerVersion {
+    /// Check whether `self` and `other` can safely forward activations
+    ///
+    /// Requires an identical `model_id` and `weights_digest`, and a peer
+    /// `protocol_version` within `[MIN_PROTOCOL_VERSION, MAX_PROTOCOL_VERSION]`.
+    pub fn is_compatible(&self, other: &WorkerVersion) -> bool {
+        self.model_id == other.model_id
+            && self.weights_digest == other.weights_digest
+            && (MIN_PROTOCOL_VERSION..=MAX_PROTOCOL_VERSION).contains(&other.protocol_version)
+    }
+
+    /// Describe the first field that makes `self` and `other` incompatible
+    ///
+    /// Returns `None` if the two versions are compatible.
+    pub fn incompatibility_reason(&self, other: &WorkerVersion) -> Option<String> {
+        if self.model_id != other.model_id {
+            return Some(format!(
+                "model_id mismatch: local={}, peer={}",
+                self.model_id, other.model_id
+            ));
+        }
+
+        if self.weights_digest != other.weights_digest {
+            return Some(format!(
+                "weights_digest mismatch: local={}, peer={}",
+                self.weights_digest, other.weights_digest
+            ));
+        }
+
+        if !(MIN_PROTOCOL_VERSION..=MAX_PROTOCOL_VERSION).contains(&other.protocol_version) {
+            return Some(format!(
+                "protocol_version {} outside supported range {}..={}",
+                other.protocol_version, MIN_PROTOCOL_VERSION, MAX_PROTOCOL_VERSION
+            ));
+        }
+
+        None
+    }
+}
+
 pub mod inference {
     pub use super::*;
 }
@@ -0,0 +1,244 @@
+//! HTTP admin/introspection endpoint exposing the worker's task states
+//!
+//! Feature-gated behind the same `metrics` cargo feature as
+//! `chatloop_common::metrics::exporter`, since both are optional hyper-based
+//! side servers a minimal deployment may want to compile out.
+
+use crate::manager::{WorkerCommand, WorkerManager};
+use crate::progress::ProgressPublisher;
+use chatloop_common::config::WorkerConfig;
+use chatloop_common::error::{ChatLoopError, Result};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+use tracing::{error, info, warn};
+
+/// Serves a JSON snapshot of [`WorkerManager`]'s task table on `GET /tasks`,
+/// forwards `POST /control/{pause,resume,drain,tranquility}` onto the
+/// inference loop's [`WorkerCommand`] channel for live operations, and
+/// streams newline-delimited JSON [`crate::progress::BatchProgressEvent`]s
+/// on `GET /progress/stream` for dashboards/routers to subscribe to.
+///
+/// `/progress/stream` rides this HTTP server rather than a dedicated gRPC
+/// service: `chatloop_proto` has no tonic-build codegen and
+/// `grpc::server::WorkerServer` has no working server-side dispatch to hang
+/// a streaming RPC off of, unlike the client side (`grpc::client`), which
+/// this admin server's request/response model already mirrors closely
+/// enough to reuse.
+pub struct AdminServer {
+    listen_addr: SocketAddr,
+    manager: Arc<WorkerManager>,
+    commands: mpsc::Sender<WorkerCommand>,
+    progress: Arc<ProgressPublisher>,
+}
+
+impl AdminServer {
+    /// Create a new admin server bound to `listen_addr`, reading from
+    /// `manager` and `progress`, and forwarding control commands onto
+    /// `commands`
+    pub fn new(
+        listen_addr: SocketAddr,
+        manager: Arc<WorkerManager>,
+        commands: mpsc::Sender<WorkerCommand>,
+        progress: Arc<ProgressPublisher>,
+    ) -> Self {
+        Self { listen_addr, manager, commands, progress }
+    }
+
+    /// Run the admin server until the process exits
+    ///
+    /// Never returns on success; matches the long-running serve loops in
+    /// `grpc::server` and `MetricsExporter::serve`.
+    pub async fn serve(&self) -> Result<()> {
+        let manager = Arc::clone(&self.manager);
+        let commands = self.commands.clone();
+        let progress = Arc::clone(&self.progress);
+
+        let make_svc = make_service_fn(move |_conn| {
+            let manager = Arc::clone(&manager);
+            let commands = commands.clone();
+            let progress = Arc::clone(&progress);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let manager = Arc::clone(&manager);
+                    let commands = commands.clone();
+                    let progress = Arc::clone(&progress);
+                    async move {
+                        Ok::<_, Infallible>(handle(&manager, &commands, &progress, req).await)
+                    }
+                }))
+            }
+        });
+
+        info!("Admin server listening on {}", self.listen_addr);
+
+        Server::bind(&self.listen_addr)
+            .serve(make_svc)
+            .await
+            .map_err(|e| ChatLoopError::config(format!("Admin server failed: {}", e)))
+    }
+}
+
+/// Spawn the admin server as a background task if `config.admin_enabled` is
+/// set, binding to `config.admin_port` on all interfaces
+///
+/// Returns `None` (and spawns nothing) when admin is disabled, so
+/// `worker::main` can call this unconditionally on startup.
+pub fn spawn_if_enabled(
+    config: &WorkerConfig,
+    manager: Arc<WorkerManager>,
+    commands: mpsc::Sender<WorkerCommand>,
+    progress: Arc<ProgressPublisher>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    if !config.admin_enabled {
+        return None;
+    }
+
+    let listen_addr = match format!("0.0.0.0:{}", config.admin_port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            error!("Invalid admin listen address: {}", e);
+            return None;
+        }
+    };
+
+    let admin = AdminServer::new(listen_addr, manager, commands, progress);
+    Some(tokio::spawn(async move {
+        if let Err(e) = admin.serve().await {
+            error!("Admin server exited: {}", e);
+        }
+    }))
+}
+
+/// Body expected on `POST /control/tranquility`
+#[derive(serde::Deserialize)]
+struct SetTranquilityRequest {
+    tranquility: f64,
+}
+
+/// Handle a single request: serve the task snapshot as JSON on
+/// `GET /tasks`, forward `POST /control/{pause,resume,drain,tranquility}`
+/// onto `commands`, stream progress events on `GET /progress/stream`, 404
+/// otherwise
+async fn handle(
+    manager: &WorkerManager,
+    commands: &mpsc::Sender<WorkerCommand>,
+    progress: &Arc<ProgressPublisher>,
+    req: Request<Body>,
+) -> Response<Body> {
+    match (req.method(), req.uri().path()) {
+        (&Method::GET, "/tasks") => {
+            let snapshot = manager.snapshot();
+            match serde_json::to_vec(&snapshot) {
+                Ok(body) => Response::builder()
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .expect("static JSON response is always well-formed"),
+                Err(e) => {
+                    error!("Failed to serialize worker task snapshot: {}", e);
+                    Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::empty())
+                        .expect("static 500 response is always well-formed")
+                }
+            }
+        }
+        (&Method::POST, "/control/pause") => send_command(commands, WorkerCommand::Pause).await,
+        (&Method::POST, "/control/resume") => send_command(commands, WorkerCommand::Resume).await,
+        (&Method::POST, "/control/drain") => send_command(commands, WorkerCommand::Drain).await,
+        (&Method::POST, "/control/tranquility") => set_tranquility(commands, req).await,
+        (&Method::GET, "/progress/stream") => stream_progress(progress),
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .expect("static 404 response is always well-formed"),
+    }
+}
+
+/// Stream newline-delimited JSON [`crate::progress::BatchProgressEvent`]s to
+/// the caller for as long as the connection stays open; a subscriber that
+/// falls behind the broadcast channel's buffer just misses the events it
+/// lagged on rather than closing the stream
+fn stream_progress(progress: &Arc<ProgressPublisher>) -> Response<Body> {
+    let events = BroadcastStream::new(progress.subscribe()).filter_map(|event| match event {
+        Ok(event) => match serde_json::to_vec(&event) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                Some(Ok::<_, std::io::Error>(line))
+            }
+            Err(e) => {
+                warn!("Failed to serialize progress event: {}", e);
+                None
+            }
+        },
+        Err(_lagged) => None,
+    });
+
+    Response::builder()
+        .header("content-type", "application/x-ndjson")
+        .body(Body::wrap_stream(events))
+        .expect("streaming progress response is always well-formed")
+}
+
+/// Parse a `{"tranquility": <f64>}` body and forward it to the inference
+/// loop as a [`WorkerCommand::SetTranquility`]; 400 on a malformed body or
+/// a negative value, which would make `throttle_delay` meaningless
+async fn set_tranquility(
+    commands: &mpsc::Sender<WorkerCommand>,
+    req: Request<Body>,
+) -> Response<Body> {
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!("Failed to read /control/tranquility body: {}", e);
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::empty())
+                .expect("static 400 response is always well-formed");
+        }
+    };
+
+    let parsed: std::result::Result<SetTranquilityRequest, _> = serde_json::from_slice(&body);
+    match parsed {
+        Ok(SetTranquilityRequest { tranquility }) if tranquility >= 0.0 && tranquility.is_finite() => {
+            send_command(commands, WorkerCommand::SetTranquility(tranquility)).await
+        }
+        Ok(SetTranquilityRequest { tranquility }) => {
+            warn!("Rejecting invalid tranquility value: {}", tranquility);
+            Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::empty())
+                .expect("static 400 response is always well-formed")
+        }
+        Err(e) => {
+            warn!("Failed to parse /control/tranquility body: {}", e);
+            Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::empty())
+                .expect("static 400 response is always well-formed")
+        }
+    }
+}
+
+/// Forward `command` to the inference loop, reporting whether it was accepted
+async fn send_command(
+    commands: &mpsc::Sender<WorkerCommand>,
+    command: WorkerCommand,
+) -> Response<Body> {
+    if commands.send(command).await.is_err() {
+        error!("Failed to forward {:?}: inference loop already exited", command);
+        return Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(Body::empty())
+            .expect("static 503 response is always well-formed");
+    }
+
+    Response::builder()
+        .body(Body::empty())
+        .expect("static 200 response is always well-formed")
+}
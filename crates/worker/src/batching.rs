@@ -4,21 +4,35 @@
 //! while maintaining low latency. Uses lock-free queues for minimal overhead.
 
 use crate::error::{ChatLoopError, Result};
-use chatloop_common::config::BatchingConfig;
+use chatloop_common::config::{BatchingConfig, PriorityConfig};
+use chatloop_common::metrics::{WithWorker, METRICS};
 use crossbeam::queue::SegQueue;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Notify;
+use tokio::sync::{mpsc, Notify, OwnedSemaphorePermit, Semaphore};
 use tokio::time::timeout;
 use tracing::{debug, trace, warn};
 
+/// Smoothing factor for the per-token forward latency moving average
+const LATENCY_EMA_ALPHA: f64 = 0.1;
+
 /// Request identifier
 pub type RequestId = String;
 
 /// Sequence identifier for tracking multi-step generation
 pub type SequenceId = u64;
 
+/// A single generated token id
+pub type GeneratedToken = i32;
+
+/// Receiving half of a request's generated-token channel, returned by
+/// [`BatchScheduler::submit_and_await`]
+pub type ResponseStream = mpsc::Receiver<Result<GeneratedToken>>;
+
+/// Channel capacity for a single request's generated-token stream
+const RESPONSE_CHANNEL_CAPACITY: usize = 16;
+
 /// A single inference request waiting to be batched
 #[derive(Debug, Clone)]
 pub struct InferenceRequest {
@@ -31,6 +45,11 @@ pub struct InferenceRequest {
     /// Input tokens
     pub tokens: Vec<i32>,
 
+    /// Number of tokens at the end of `tokens` not yet processed by the
+    /// inference engine: the whole prompt on a prefill step, or just the
+    /// one token generated since the previous call on a decode step.
+    pub new_tokens: usize,
+
     /// Generation parameters
     pub temperature: f32,
     pub top_p: f32,
@@ -42,6 +61,33 @@ pub struct InferenceRequest {
 
     /// Metadata
     pub metadata: serde_json::Value,
+
+    /// Sending half of this request's generated-token channel, if a caller
+    /// is waiting on it via [`BatchScheduler::submit_and_await`]. The batch
+    /// processing loop sends each generated token down this channel as it's
+    /// produced, and drops it (closing the stream) once the sequence
+    /// finishes, errors, or the request is abandoned. `None` for requests
+    /// submitted directly via `try_submit`/`submit_with_backpressure` with
+    /// no one awaiting a result.
+    pub response_tx: Option<mpsc::Sender<Result<GeneratedToken>>>,
+
+    /// Number of times this request has already been retried after a
+    /// retryable `forward_batch` failure; incremented by the worker loop
+    /// each time it requeues the request, so a request that keeps failing
+    /// is eventually dropped instead of retried forever.
+    pub retry_count: u32,
+}
+
+/// A queued request paired with the semaphore permit (if any) that bounds
+/// its place in the queue
+///
+/// [`BatchScheduler::submit_with_backpressure`] attaches a permit acquired
+/// from `BatchScheduler::semaphore`, held until the request is popped back
+/// out in [`BatchScheduler::pop_one`]; [`BatchScheduler::try_submit`] never
+/// acquires one; it just checks capacity non-blockingly up front.
+struct QueuedRequest {
+    request: InferenceRequest,
+    permit: Option<OwnedSemaphorePermit>,
 }
 
 /// Batched requests ready for processing
@@ -79,7 +125,7 @@ impl RequestBatch {
     }
 
     /// Check if the batch is empty
-    pub is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.requests.is_empty()
     }
 
@@ -93,64 +139,406 @@ impl RequestBatch {
 ///
 /// This scheduler collects requests into batches, waiting up to the configured
 /// batching window before dispatching. Implements backpressure when full.
+///
+/// When `config.backpressure` is enabled, the scheduler additionally tracks
+/// queue depth against `high_watermark`/`low_watermark` fractions of
+/// `max_queue_size`: crossing the high watermark rejects new requests and
+/// signals the upstream connection to pause (see [`BatchScheduler::is_paused`]),
+/// and the effective batching window grows linearly from
+/// `min_batching_window_ms` at the low watermark to `max_batching_window_ms`
+/// at the high watermark (see [`BatchScheduler::effective_window`]).
+///
+/// Queued requests older than `config.queue_timeout_ms` are dropped rather
+/// than batched: [`BatchScheduler::pop_one`] checks the deadline on every
+/// pop, and [`BatchScheduler::start_queue_reaper`] periodically walks the
+/// whole queue to catch requests buried behind fresher arrivals that a pop
+/// wouldn't reach in time.
 pub struct BatchScheduler {
+    /// Identifies this scheduler's worker in labeled metrics
+    worker_id: String,
+
     /// Configuration
     config: BatchingConfig,
 
     /// Request queue (lock-free)
-    queue: Arc<SegQueue<InferenceRequest>>,
+    queue: Arc<SegQueue<QueuedRequest>>,
+
+    /// Bounds [`BatchScheduler::submit_with_backpressure`] to `max_queue_size`
+    /// in-flight requests: a permit is acquired before the request is pushed
+    /// and released only once it's popped back out, so acquire and push
+    /// together behave as one atomic admission check instead of the
+    /// load-then-push race in `try_submit`
+    semaphore: Arc<Semaphore>,
 
     /// Current queue depth (atomic for metrics)
     queue_depth: Arc<AtomicUsize>,
 
+    /// Sum of `tokens.len()` across all currently-queued requests, kept in
+    /// sync with `queue_depth` so [`BatchScheduler::next_batch_within_budget`]
+    /// can evaluate `waiting_served_ratio` without draining the queue
+    queued_tokens: Arc<AtomicUsize>,
+
     /// Shutdown flag
     shutdown: Arc<AtomicBool>,
 
+    /// Set by [`BatchScheduler::start_draining`]: new submissions are
+    /// rejected, but unlike `shutdown`, already-queued requests are still
+    /// popped and batched normally by [`BatchScheduler::next_batch`] so a
+    /// graceful rolling restart finishes in-flight work instead of
+    /// abandoning it
+    draining: Arc<AtomicBool>,
+
     /// Notification for new requests
     notify: Arc<Notify>,
+
+    /// Set when the upstream connection has been asked to pause
+    paused: Arc<AtomicBool>,
+
+    /// Moving average of per-token forward latency, in nanoseconds (bit
+    /// pattern of the f64 average, for lock-free updates)
+    latency_ema_ns_bits: Arc<AtomicU64>,
 }
 
 impl BatchScheduler {
-    /// Create a new batch scheduler
-    pub fn new(config: BatchingConfig) -> Self {
+    /// Create a new batch scheduler for the given worker
+    ///
+    /// `worker_id` labels every metric this scheduler records, so queue
+    /// depth, batch size, and latency can be broken down per worker node
+    /// once the `Router` is fanning out across several of them.
+    pub fn new(worker_id: impl Into<String>, config: BatchingConfig) -> Self {
         Self {
+            worker_id: worker_id.into(),
+            semaphore: Arc::new(Semaphore::new(config.max_queue_size)),
             config,
-            queue: Arc::new(SegQueue::clone()),
+            queue: Arc::new(SegQueue::new()),
             queue_depth: Arc::new(AtomicUsize::new(0)),
+            queued_tokens: Arc::new(AtomicUsize::new(0)),
             shutdown: Arc::new(AtomicBool::new(false)),
+            draining: Arc::new(AtomicBool::new(false)),
             notify: Arc::new(Notify::new()),
+            paused: Arc::new(AtomicBool::new(false)),
+            latency_ema_ns_bits: Arc::new(AtomicU64::new(0)),
         }
     }
 
-    /// Submit a request to the scheduler
+    /// Submit a request to the scheduler without waiting for capacity
     ///
-    /// Returns error if the queue is full (backpressure).
-    pub fn submit(&self, request: InferenceRequest) -> Result<()> {
+    /// Non-blocking fast path: returns `queue_full` immediately if the queue
+    /// looks full or paused. The depth check and the push are not atomic, so
+    /// under concurrent callers this can occasionally admit slightly past
+    /// `max_queue_size` or reject when a slot just freed up — callers that
+    /// need a precise bound and are willing to wait should use
+    /// [`BatchScheduler::submit_with_backpressure`] instead.
+    pub fn try_submit(&self, request: InferenceRequest) -> Result<()> {
+        if self.draining.load(Ordering::Relaxed) {
+            return Err(ChatLoopError::queue_full(
+                "Worker is draining, rejecting new request",
+            ));
+        }
+
         // Check queue depth
         if self.queue_depth.load(Ordering::Relaxed) >= self.config.max_queue_size {
+            METRICS.worker.requests_dropped.with_worker(&self.worker_id).inc();
             return Err(ChatLoopError::queue_full(
                 "Request queue is full, rejecting new request",
             ));
         }
 
-        self.queue.push(request);
-        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+        if self.config.backpressure && self.is_paused() {
+            METRICS.worker.requests_dropped.with_worker(&self.worker_id).inc();
+            return Err(ChatLoopError::queue_full(
+                "Upstream connection is paused due to backpressure",
+            ));
+        }
+
+        self.push(QueuedRequest { request, permit: None });
+
+        Ok(())
+    }
+
+    /// Submit a request, waiting up to `wait_timeout` for queue capacity
+    /// instead of failing immediately
+    ///
+    /// Acquires an `OwnedSemaphorePermit` from a semaphore sized to
+    /// `max_queue_size` before pushing the request, so the acquire and the
+    /// push together form a precise, race-free admission check — unlike
+    /// [`BatchScheduler::try_submit`]'s load-then-push. The permit is held
+    /// for as long as the request sits in the queue and is released (via
+    /// `Drop`) once [`BatchScheduler::pop_one`] takes it out. Returns
+    /// `queue_full` only once `wait_timeout` elapses without a permit
+    /// becoming available.
+    pub async fn submit_with_backpressure(
+        &self,
+        request: InferenceRequest,
+        wait_timeout: Duration,
+    ) -> Result<()> {
+        if self.draining.load(Ordering::Relaxed) {
+            return Err(ChatLoopError::queue_full(
+                "Worker is draining, rejecting new request",
+            ));
+        }
+
+        let permit = timeout(wait_timeout, self.semaphore.clone().acquire_owned())
+            .await
+            .map_err(|_| {
+                METRICS.worker.requests_dropped.with_worker(&self.worker_id).inc();
+                ChatLoopError::queue_full("Timed out waiting for queue capacity")
+            })?
+            .expect("BatchScheduler never closes its own semaphore");
+
+        if self.config.backpressure && self.is_paused() {
+            drop(permit);
+            METRICS.worker.requests_dropped.with_worker(&self.worker_id).inc();
+            return Err(ChatLoopError::queue_full(
+                "Upstream connection is paused due to backpressure",
+            ));
+        }
+
+        self.push(QueuedRequest {
+            request,
+            permit: Some(permit),
+        });
+
+        Ok(())
+    }
+
+    /// Push a queued request, updating the depth gauge, token sum,
+    /// notification, and backpressure watermark state shared by both
+    /// submission paths
+    fn push(&self, queued: QueuedRequest) {
+        self.queued_tokens
+            .fetch_add(queued.request.tokens.len(), Ordering::Relaxed);
+        self.queue.push(queued);
+        let depth = self.queue_depth.fetch_add(1, Ordering::Relaxed) + 1;
         self.notify.notify_one();
+        METRICS.worker.queue_depth.with_worker(&self.worker_id).set(depth as i64);
+
+        if self.config.backpressure {
+            self.update_backpressure(depth);
+        }
+
+        trace!("Request submitted, queue depth: {}", depth);
+    }
+
+    /// Submit a request and return a stream of its generated tokens
+    ///
+    /// Creates the request's generated-token channel, attaches the sending
+    /// half as `request.response_tx` (overwriting whatever was set there),
+    /// and submits it via `try_submit`. The receiving half is returned so
+    /// the caller (e.g. the gRPC `WorkerServer`) can await or stream
+    /// results without polling the scheduler directly. Turns
+    /// `BatchScheduler` into a usable service boundary rather than just a
+    /// queue: the batch processing loop resolves each request's sender as
+    /// tokens are produced and drops it once the sequence finishes, errors,
+    /// or the request is abandoned, which closes the stream for the
+    /// receiver.
+    pub async fn submit_and_await(&self, mut request: InferenceRequest) -> Result<ResponseStream> {
+        let (tx, rx) = mpsc::channel(RESPONSE_CHANNEL_CAPACITY);
+        request.response_tx = Some(tx);
+        self.try_submit(request)?;
+        Ok(rx)
+    }
+
+    /// Record an observed per-token forward latency, updating the moving
+    /// average used to inform adaptive batching decisions
+    pub fn record_forward_latency(&self, per_token_latency: Duration) {
+        let sample_ns = per_token_latency.as_nanos() as f64;
+        let previous = f64::from_bits(self.latency_ema_ns_bits.load(Ordering::Relaxed));
+        let updated = if previous == 0.0 {
+            sample_ns
+        } else {
+            LATENCY_EMA_ALPHA * sample_ns + (1.0 - LATENCY_EMA_ALPHA) * previous
+        };
+        self.latency_ema_ns_bits
+            .store(updated.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Current moving average of per-token forward latency
+    pub fn avg_forward_latency(&self) -> Duration {
+        Duration::from_nanos(f64::from_bits(self.latency_ema_ns_bits.load(Ordering::Relaxed)) as u64)
+    }
+
+    /// Whether the upstream connection has been asked to pause sending
+    /// new `ForwardRequest`s
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Re-evaluate the pause state for the current queue depth, crossing
+    /// the high/low watermarks configured in `BatchingConfig`
+    fn update_backpressure(&self, depth: usize) {
+        let high = (self.config.max_queue_size as f64 * self.config.high_watermark) as usize;
+        let low = (self.config.max_queue_size as f64 * self.config.low_watermark) as usize;
+
+        if !self.paused.load(Ordering::Relaxed) && depth >= high {
+            self.paused.store(true, Ordering::Relaxed);
+            METRICS.worker.backpressure_pauses.with_worker(&self.worker_id).inc();
+            warn!(
+                "Queue depth {} reached high watermark {}, pausing upstream",
+                depth, high
+            );
+        } else if self.paused.load(Ordering::Relaxed) && depth <= low {
+            self.paused.store(false, Ordering::Relaxed);
+            debug!(
+                "Queue depth {} fell to low watermark {}, resuming upstream",
+                depth, low
+            );
+        }
+    }
+
+    /// Pop one live request off the queue, keeping the depth gauge and
+    /// backpressure watermark state in sync with the dequeue
+    ///
+    /// Dropping the popped `QueuedRequest` here releases its semaphore
+    /// permit (if `submit_with_backpressure` attached one), freeing that
+    /// slot for the next waiting caller. Any request already past
+    /// `queue_timeout_ms` is evicted instead of returned - see
+    /// [`BatchScheduler::evict_expired`] - and the search continues with
+    /// the next entry.
+    fn pop_one(&self) -> Option<InferenceRequest> {
+        loop {
+            let queued = self.queue.pop()?;
+            let depth = self.queue_depth.fetch_sub(1, Ordering::Relaxed) - 1;
+            self.queued_tokens
+                .fetch_sub(queued.request.tokens.len(), Ordering::Relaxed);
+            METRICS.worker.queue_depth.with_worker(&self.worker_id).set(depth as i64);
+
+            if self.config.backpressure {
+                self.update_backpressure(depth);
+            }
+
+            if self.is_expired(&queued.request) {
+                self.evict_expired(queued);
+                continue;
+            }
+
+            return Some(queued.request);
+        }
+    }
+
+    /// Whether `request` has been queued longer than `queue_timeout_ms`
+    fn is_expired(&self, request: &InferenceRequest) -> bool {
+        request.arrival_time.elapsed() >= Duration::from_millis(self.config.queue_timeout_ms)
+    }
 
-        trace!(
-            "Request submitted, queue depth: {}",
-            self.queue_depth.load(Ordering::Relaxed)
+    /// Drop an expired request, counting it and resolving its response
+    /// channel (if any) with a timeout error instead of silently dropping
+    /// the sender, so a streaming caller sees why its stream ended rather
+    /// than just observing it close
+    fn evict_expired(&self, queued: QueuedRequest) {
+        METRICS.worker.queue_timeouts.with_worker(&self.worker_id).inc();
+        warn!(
+            "Dropping request {} after exceeding queue_timeout_ms ({} ms) while queued",
+            queued.request.request_id, self.config.queue_timeout_ms
         );
 
-        Ok(())
+        if let Some(tx) = &queued.request.response_tx {
+            let _ = tx.try_send(Err(ChatLoopError::timeout(
+                "Request exceeded queue_timeout_ms while queued",
+            )));
+        }
+    }
+
+    /// Walk the entire queue once, evicting any request past
+    /// `queue_timeout_ms` and requeuing the rest in their original order
+    ///
+    /// `SegQueue` is append-only FIFO with no way to scan or remove from
+    /// the middle, so a request buried behind fresher arrivals could sit
+    /// well past its deadline until something pops far enough to reach it.
+    /// This is the only way to catch those without waiting on
+    /// [`BatchScheduler::pop_one`].
+    fn reap_expired(&self) {
+        for _ in 0..self.queue_depth.load(Ordering::Relaxed) {
+            let queued = match self.queue.pop() {
+                Some(queued) => queued,
+                None => break,
+            };
+
+            if self.is_expired(&queued.request) {
+                let depth = self.queue_depth.fetch_sub(1, Ordering::Relaxed) - 1;
+                self.queued_tokens
+                    .fetch_sub(queued.request.tokens.len(), Ordering::Relaxed);
+                METRICS.worker.queue_depth.with_worker(&self.worker_id).set(depth as i64);
+
+                if self.config.backpressure {
+                    self.update_backpressure(depth);
+                }
+
+                self.evict_expired(queued);
+            } else {
+                self.queue.push(queued);
+            }
+        }
+    }
+
+    /// Start a background task that periodically reaps requests that have
+    /// been sitting in the queue longer than `queue_timeout_ms`
+    pub fn start_queue_reaper(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                if self.shutdown.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                self.reap_expired();
+            }
+        })
+    }
+
+    /// Effective batching window for the current queue depth
+    ///
+    /// With adaptive batching enabled, the window is driven by queue
+    /// pressure rather than held fixed: at or below `low_watermark` it's
+    /// pinned to `min_batching_window_ms` so a lightly loaded worker
+    /// flushes each request with minimal added latency instead of waiting
+    /// out a window nothing will fill; at or above `high_watermark` it's
+    /// pinned to `max_batching_window_ms` so a saturated worker collects
+    /// longer and ships bigger, more efficient batches; in between it's
+    /// interpolated linearly. The result is also published as a gauge for
+    /// observability.
+    fn effective_window(&self) -> Duration {
+        let window = if !self.config.backpressure {
+            Duration::from_millis(self.config.batching_window_ms)
+        } else {
+            let min_window = Duration::from_millis(self.config.min_batching_window_ms);
+            let max_window = Duration::from_millis(self.config.max_batching_window_ms);
+
+            let low = self.config.max_queue_size as f64 * self.config.low_watermark;
+            let high =
+                (self.config.max_queue_size as f64 * self.config.high_watermark).max(low + 1.0);
+            let depth = self.queue_depth.load(Ordering::Relaxed) as f64;
+
+            if depth <= low {
+                min_window
+            } else if depth >= high {
+                max_window
+            } else {
+                let ratio = (depth - low) / (high - low);
+                min_window + max_window.saturating_sub(min_window).mul_f64(ratio)
+            }
+        };
+
+        METRICS
+            .worker
+            .effective_batching_window_ms
+            .with_worker(&self.worker_id)
+            .set(window.as_secs_f64() * 1000.0);
+
+        window
     }
 
     /// Get the next batch of requests
     ///
     /// This waits for up to the batching window to collect requests.
-    /// Returns immediately if max_batch_size is reached.
+    /// Returns immediately if max_batch_size is reached. With adaptive
+    /// batching enabled the window is recomputed on every pass so a queue
+    /// depth crossing a watermark mid-collection is picked up immediately
+    /// rather than waiting for the next call to `next_batch`.
     pub async fn next_batch(&self) -> Result<Option<RequestBatch>> {
-        let batching_window = Duration::from_millis(self.config.batching_window_ms);
         let mut batch = RequestBatch::new();
 
         // Wait for first request
@@ -161,20 +549,18 @@ impl BatchScheduler {
             }
 
             // Try to get a request
-            if let Some(req) = self.queue.pop() {
-                self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+            if let Some(req) = self.pop_one() {
                 batch.add(req);
                 break;
             }
 
             // Wait for notification
-            timeout(batching_window, self.notify.notified())
+            timeout(self.effective_window(), self.notify.notified())
                 .await
                 .map_err(|_| ChatLoopError::timeout("Batching window timeout"))?;
 
             // If still no request after timeout, return empty batch
-            if let Some(req) = self.queue.pop() {
-                self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+            if let Some(req) = self.pop_one() {
                 batch.add(req);
                 break;
             } else {
@@ -182,11 +568,16 @@ impl BatchScheduler {
             }
         }
 
-        // Collect more requests within batching window
+        // Collect more requests within the batching window, recomputing the
+        // deadline off the current queue depth each pass
         let start = Instant::now();
-        while batch.len() < self.config.max_batch_size && start.elapsed() < batching_window {
-            if let Some(req) = self.queue.pop() {
-                self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+        loop {
+            let batching_window = self.effective_window();
+            if batch.len() >= self.config.max_batch_size || start.elapsed() >= batching_window {
+                break;
+            }
+
+            if let Some(req) = self.pop_one() {
                 batch.add(req);
             } else {
                 // Wait a bit for more requests
@@ -209,6 +600,75 @@ impl BatchScheduler {
         Ok(Some(batch))
     }
 
+    /// Admit waiting requests into `batch` without exceeding either token
+    /// budget, given `running_tokens` already committed to the batch
+    /// in-flight (0 for a fresh batch)
+    ///
+    /// Continuous batching (as in text-generation-inference's `Queue`) lets
+    /// new requests join a batch that's already running instead of waiting
+    /// for every request in it to finish first. To keep the batch from
+    /// outgrowing the KV-cache budget, admission stops once either the sum
+    /// of prefill tokens (plus a padding estimate, since all requests in a
+    /// batch are padded up to `max_seq_len`) would exceed
+    /// `max_batch_prefill_tokens`, or the total token count (running plus
+    /// newly admitted) would exceed `max_batch_total_tokens`.
+    ///
+    /// When `running_tokens > 0` (there's already a batch in flight), new
+    /// requests are only admitted once the waiting queue has built up
+    /// enough demand relative to it: `waiting_tokens / running_tokens >=
+    /// waiting_served_ratio`. This avoids interrupting a running batch for
+    /// every trickle of new requests.
+    pub fn next_batch_within_budget(&self, running_tokens: usize) -> Result<Option<RequestBatch>> {
+        if self.shutdown.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        if running_tokens > 0 {
+            let waiting_tokens = self.queued_tokens.load(Ordering::Relaxed);
+            if (waiting_tokens as f32) / (running_tokens as f32) < self.config.waiting_served_ratio {
+                return Ok(None);
+            }
+        }
+
+        let mut batch = RequestBatch::new();
+        let mut prefill_tokens = 0usize;
+
+        while let Some(req) = self.pop_one() {
+            let candidate_len = batch.requests.len() + 1;
+            let candidate_max_seq_len = batch.max_seq_len.max(req.tokens.len());
+            let padded_prefill_tokens = candidate_max_seq_len * candidate_len;
+            let candidate_total_tokens =
+                running_tokens + prefill_tokens + req.tokens.len();
+
+            if batch.len() >= self.config.max_batch_size
+                || padded_prefill_tokens > self.config.max_batch_prefill_tokens
+                || candidate_total_tokens > self.config.max_batch_total_tokens
+            {
+                self.queued_tokens
+                    .fetch_add(req.tokens.len(), Ordering::Relaxed);
+                self.queue.push(QueuedRequest { request: req, permit: None });
+                self.queue_depth.fetch_add(1, Ordering::Relaxed);
+                break;
+            }
+
+            prefill_tokens += req.tokens.len();
+            batch.add(req);
+        }
+
+        if batch.is_empty() {
+            return Ok(None);
+        }
+
+        debug!(
+            "Admitted batch within budget: {} requests, prefill_tokens: {}, running_tokens: {}",
+            batch.len(),
+            prefill_tokens,
+            running_tokens
+        );
+
+        Ok(Some(batch))
+    }
+
     /// Get the current queue depth
     pub fn queue_depth(&self) -> usize {
         self.queue_depth.load(Ordering::Relaxed)
@@ -225,13 +685,48 @@ impl BatchScheduler {
         self.shutdown.store(true, Ordering::Relaxed);
         self.notify.notify_waiters();
     }
+
+    /// Stop accepting new requests via `try_submit`/`submit_with_backpressure`
+    ///
+    /// Unlike `shutdown`, `next_batch` keeps popping and batching whatever is
+    /// already queued, so a drain finishes in-flight/queued work instead of
+    /// abandoning it.
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `start_draining` has been called
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// True once draining has been requested and the queue has fully
+    /// emptied, i.e. it's safe to stop calling `next_batch`
+    pub fn drained(&self) -> bool {
+        self.is_draining() && self.queue_depth() == 0
+    }
 }
 
 /// Priority-based request scheduler
 ///
-/// This implements priority queues for different request classes.
-/// Not used by default but can be enabled for multi-tenant scenarios.
+/// This implements priority queues for different request classes. Not used
+/// by default but can be enabled for multi-tenant scenarios.
+///
+/// [`PriorityScheduler::next_batch`] no longer drains strictly
+/// high→normal→low - a steady stream of high-priority work would starve the
+/// lower tiers indefinitely. Instead each batch reserves
+/// `config.low_reserved_slots`/`config.normal_reserved_slots` slots for
+/// those tiers before filling the remainder in priority order, and any
+/// request that's been waiting longer than its tier's `max_wait` is
+/// promoted one level so it can't wait forever behind fresh high-priority
+/// arrivals.
 pub struct PriorityScheduler {
+    /// Identifies this scheduler's worker in labeled metrics
+    worker_id: String,
+
+    /// Weighted fair dispatch and aging configuration
+    config: PriorityConfig,
+
     /// High-priority queue (e.g., admin requests)
     high_priority: Arc<SegQueue<InferenceRequest>>,
 
@@ -249,12 +744,14 @@ pub struct PriorityScheduler {
 }
 
 impl PriorityScheduler {
-    /// Create a new priority scheduler
-    pub fn new() -> Self {
+    /// Create a new priority scheduler for the given worker
+    pub fn new(worker_id: impl Into<String>, config: PriorityConfig) -> Self {
         Self {
-            high_priority: Arc::new(SegQueue::clone()),
-            normal_priority: Arc::new(SegQueue::clone()),
-            low_priority: Arc::new(SegQueue::clone()),
+            worker_id: worker_id.into(),
+            config,
+            high_priority: Arc::new(SegQueue::new()),
+            normal_priority: Arc::new(SegQueue::new()),
+            low_priority: Arc::new(SegQueue::new()),
             shutdown: Arc::new(AtomicBool::new(false)),
             notify: Arc::new(Notify::new()),
         }
@@ -274,32 +771,117 @@ impl PriorityScheduler {
         Ok(())
     }
 
+    /// Promote requests that have aged past their tier's `max_wait` into
+    /// the next tier up
+    ///
+    /// Each tier is scanned at most once (bounded by its length at the
+    /// start of the scan), so a request promoted into a tier this round
+    /// isn't immediately re-examined for a second promotion in the same
+    /// pass.
+    fn promote_aged(&self) {
+        // Scan normal before low so requests promoted out of low this pass
+        // land in a tier that has already been scanned.
+        Self::promote_tier(
+            &self.normal_priority,
+            &self.high_priority,
+            Duration::from_millis(self.config.normal_max_wait_ms),
+        );
+        Self::promote_tier(
+            &self.low_priority,
+            &self.normal_priority,
+            Duration::from_millis(self.config.low_max_wait_ms),
+        );
+    }
+
+    /// Move requests older than `max_wait` from `from` to `to`, leaving
+    /// everything else in place
+    fn promote_tier(
+        from: &Arc<SegQueue<InferenceRequest>>,
+        to: &Arc<SegQueue<InferenceRequest>>,
+        max_wait: Duration,
+    ) {
+        for _ in 0..from.len() {
+            if let Some(req) = from.pop() {
+                if req.arrival_time.elapsed() >= max_wait {
+                    to.push(req);
+                } else {
+                    from.push(req);
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Pop up to `n` requests from `queue` into `batch`, recording each
+    /// request's queue wait time against `worker_id` and `tier`
+    fn fill_from(
+        worker_id: &str,
+        queue: &Arc<SegQueue<InferenceRequest>>,
+        batch: &mut RequestBatch,
+        n: usize,
+        tier: &str,
+    ) {
+        for _ in 0..n {
+            if let Some(req) = queue.pop() {
+                METRICS
+                    .worker
+                    .priority_wait_time
+                    .with_label_values(&[worker_id, tier])
+                    .observe(req.arrival_time.elapsed().as_secs_f64());
+                batch.add(req);
+            } else {
+                break;
+            }
+        }
+    }
+
     /// Get next batch considering priorities
     ///
-    /// Always processes high-priority requests first.
+    /// Ages requests first, then reserves `low_reserved_slots` and
+    /// `normal_reserved_slots` of `max_batch_size` for those tiers (so they
+    /// always get a share of every batch), and fills whatever's left in
+    /// high→normal→low priority order.
     pub async fn next_batch(&self, max_batch_size: usize) -> Result<Option<RequestBatch>> {
-        let mut batch = RequestBatch::new();
-
         // Check shutdown
         if self.shutdown.load(Ordering::Relaxed) {
             return Ok(None);
         }
 
-        // Priority order: high -> normal -> low
-        let queues = [&self.high_priority, &self.normal_priority, &self.low_priority];
+        self.promote_aged();
 
-        for queue in &queues {
-            while batch.len() < max_batch_size {
-                if let Some(req) = queue.pop() {
-                    batch.add(req);
-                } else {
-                    break;
-                }
-            }
+        let mut batch = RequestBatch::new();
 
-            if !batch.is_empty() {
-                break;
-            }
+        Self::fill_from(
+            &self.worker_id,
+            &self.low_priority,
+            &mut batch,
+            self.config.low_reserved_slots.min(max_batch_size),
+            "low",
+        );
+        Self::fill_from(
+            &self.worker_id,
+            &self.normal_priority,
+            &mut batch,
+            self.config
+                .normal_reserved_slots
+                .min(max_batch_size.saturating_sub(batch.len())),
+            "normal",
+        );
+
+        let remaining_queues = [
+            (&self.high_priority, "high"),
+            (&self.normal_priority, "normal"),
+            (&self.low_priority, "low"),
+        ];
+        for (queue, tier) in &remaining_queues {
+            Self::fill_from(
+                &self.worker_id,
+                queue,
+                &mut batch,
+                max_batch_size.saturating_sub(batch.len()),
+                tier,
+            );
         }
 
         if batch.is_empty() {
@@ -342,9 +924,18 @@ mod tests {
             batching_window_ms: 10,
             max_queue_size: 100,
             queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
         };
 
-        let scheduler = BatchScheduler::new(config);
+        let scheduler = BatchScheduler::new("worker-0", config);
 
         // Submit some requests
         for i in 0..3 {
@@ -352,15 +943,18 @@ mod tests {
                 request_id: format!("req-{}", i),
                 sequence_id: i as u64,
                 tokens: vec![1, 2, 3],
+                new_tokens: 3,
                 temperature: 1.0,
                 top_p: 0.9,
                 top_k: 50,
                 max_tokens: 100,
                 arrival_time: Instant::now(),
                 metadata: serde_json::json!({}),
+                response_tx: None,
+                retry_count: 0,
             };
 
-            scheduler.submit(request).unwrap();
+            scheduler.try_submit(request).unwrap();
         }
 
         // Get next batch
@@ -376,9 +970,18 @@ mod tests {
             batching_window_ms: 10,
             max_queue_size: 5,
             queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
         };
 
-        let scheduler = BatchScheduler::new(config);
+        let scheduler = BatchScheduler::new("worker-0", config);
 
         // Fill the queue
         for i in 0..10 {
@@ -386,15 +989,18 @@ mod tests {
                 request_id: format!("req-{}", i),
                 sequence_id: i as u64,
                 tokens: vec![1, 2, 3],
+                new_tokens: 3,
                 temperature: 1.0,
                 top_p: 0.9,
                 top_k: 50,
                 max_tokens: 100,
                 arrival_time: Instant::now(),
                 metadata: serde_json::json!({}),
+                response_tx: None,
+                retry_count: 0,
             };
 
-            let result = scheduler.submit(request);
+            let result = scheduler.try_submit(request);
             if i < 5 {
                 assert!(result.is_ok());
             } else {
@@ -404,4 +1010,485 @@ mod tests {
 
         assert_eq!(scheduler.queue_depth(), 5);
     }
+
+    #[tokio::test]
+    async fn test_submit_with_backpressure_waits_then_times_out() {
+        let config = BatchingConfig {
+            max_batch_size: 4,
+            batching_window_ms: 10,
+            max_queue_size: 1,
+            queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = Arc::new(BatchScheduler::new("worker-0", config));
+
+        scheduler
+            .submit_with_backpressure(adaptive_request(0), Duration::from_millis(50))
+            .await
+            .unwrap();
+
+        // Queue is now full (max_queue_size: 1): a second caller should wait
+        // rather than being rejected immediately.
+        let waiter = {
+            let scheduler = Arc::clone(&scheduler);
+            tokio::spawn(async move {
+                scheduler
+                    .submit_with_backpressure(adaptive_request(1), Duration::from_millis(500))
+                    .await
+            })
+        };
+
+        // Give the waiter a moment to start blocking on the permit, then
+        // free a slot by popping the first request.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(scheduler.pop_one().is_some());
+
+        assert!(waiter.await.unwrap().is_ok());
+
+        // With no slots freed, a third caller times out instead of blocking
+        // forever.
+        let result = scheduler
+            .submit_with_backpressure(adaptive_request(2), Duration::from_millis(20))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_await_resolves_once_response_tx_is_sent_to() {
+        let config = BatchingConfig {
+            max_batch_size: 4,
+            batching_window_ms: 10,
+            max_queue_size: 10,
+            queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+
+        let mut rx = scheduler
+            .submit_and_await(adaptive_request(0))
+            .await
+            .unwrap();
+
+        // The request popped off the queue carries the sender the caller's
+        // stream is waiting on.
+        let request = scheduler.pop_one().unwrap();
+        let tx = request.response_tx.expect("submit_and_await attaches a sender");
+
+        tx.send(Ok(42)).await.unwrap();
+        assert!(matches!(rx.recv().await, Some(Ok(42))));
+
+        drop(tx);
+        assert!(rx.recv().await.is_none());
+    }
+
+    fn adaptive_request(i: usize) -> InferenceRequest {
+        InferenceRequest {
+            request_id: format!("req-{}", i),
+            sequence_id: i as u64,
+            tokens: vec![1, 2, 3],
+            new_tokens: 3,
+            temperature: 1.0,
+            top_p: 0.9,
+            top_k: 50,
+            max_tokens: 100,
+            arrival_time: Instant::now(),
+            metadata: serde_json::json!({}),
+            response_tx: None,
+            retry_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_adaptive_backpressure_pauses_and_resumes() {
+        let config = BatchingConfig {
+            max_batch_size: 4,
+            batching_window_ms: 10,
+            max_queue_size: 10,
+            queue_timeout_ms: 1000,
+            backpressure: true,
+            high_watermark: 0.8,
+            low_watermark: 0.2,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+
+        for i in 0..8 {
+            scheduler.try_submit(adaptive_request(i)).unwrap();
+        }
+        assert!(scheduler.is_paused());
+
+        // A 9th request is rejected while paused, even though max_queue_size isn't hit.
+        assert!(scheduler.try_submit(adaptive_request(8)).is_err());
+
+        for i in 0..7 {
+            let _ = scheduler.pop_one();
+            let _ = i;
+        }
+        assert!(!scheduler.is_paused());
+    }
+
+    #[test]
+    fn test_effective_window_tracks_queue_pressure_between_watermarks() {
+        let config = BatchingConfig {
+            max_batch_size: 100,
+            batching_window_ms: 100,
+            max_queue_size: 10,
+            queue_timeout_ms: 1000,
+            backpressure: true,
+            high_watermark: 0.8,
+            low_watermark: 0.2,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 5,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+
+        // Empty queue, at or below the low watermark (2): pinned to the min
+        // window so a lightly loaded worker doesn't add needless latency.
+        assert_eq!(scheduler.effective_window(), Duration::from_millis(5));
+
+        for i in 0..8 {
+            scheduler.try_submit(adaptive_request(i)).unwrap();
+        }
+        // At the high watermark (8): pinned to the max window so a
+        // saturated worker collects longer for bigger batches.
+        assert_eq!(scheduler.effective_window(), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_record_forward_latency_tracks_moving_average() {
+        let config = BatchingConfig {
+            max_batch_size: 4,
+            batching_window_ms: 10,
+            max_queue_size: 10,
+            queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+        assert_eq!(scheduler.avg_forward_latency(), Duration::ZERO);
+
+        scheduler.record_forward_latency(Duration::from_millis(10));
+        assert_eq!(scheduler.avg_forward_latency(), Duration::from_millis(10));
+
+        scheduler.record_forward_latency(Duration::from_millis(20));
+        assert!(scheduler.avg_forward_latency() > Duration::from_millis(10));
+        assert!(scheduler.avg_forward_latency() < Duration::from_millis(20));
+    }
+
+    fn request_with_tokens(i: usize, token_count: usize) -> InferenceRequest {
+        InferenceRequest {
+            request_id: format!("req-{}", i),
+            sequence_id: i as u64,
+            tokens: vec![1; token_count],
+            new_tokens: token_count,
+            temperature: 1.0,
+            top_p: 0.9,
+            top_k: 50,
+            max_tokens: 100,
+            arrival_time: Instant::now(),
+            metadata: serde_json::json!({}),
+            response_tx: None,
+            retry_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_next_batch_within_budget_stops_at_prefill_token_budget() {
+        let config = BatchingConfig {
+            max_batch_size: 100,
+            batching_window_ms: 10,
+            max_queue_size: 100,
+            queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 25,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+
+        // Three requests of 10 tokens each: padded cost is 10 * count, so the
+        // third request (padded cost 30) would exceed the budget of 25.
+        for i in 0..3 {
+            scheduler.try_submit(request_with_tokens(i, 10)).unwrap();
+        }
+
+        let batch = scheduler.next_batch_within_budget(0).unwrap().unwrap();
+
+        assert_eq!(batch.len(), 2);
+        assert_eq!(scheduler.queue_depth(), 1);
+    }
+
+    #[test]
+    fn test_next_batch_within_budget_stops_at_total_token_budget() {
+        let config = BatchingConfig {
+            max_batch_size: 100,
+            batching_window_ms: 10,
+            max_queue_size: 100,
+            queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 16384,
+            max_batch_total_tokens: 50,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+
+        for i in 0..3 {
+            scheduler.try_submit(request_with_tokens(i, 10)).unwrap();
+        }
+
+        // 40 tokens already running, leaving room for exactly one more
+        // 10-token request before the 50-token total budget is exceeded.
+        let batch = scheduler.next_batch_within_budget(40).unwrap().unwrap();
+
+        assert_eq!(batch.len(), 1);
+        assert_eq!(scheduler.queue_depth(), 2);
+    }
+
+    #[test]
+    fn test_next_batch_within_budget_waits_for_served_ratio() {
+        let config = BatchingConfig {
+            max_batch_size: 100,
+            batching_window_ms: 10,
+            max_queue_size: 100,
+            queue_timeout_ms: 1000,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 16384,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.5,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+        scheduler.try_submit(request_with_tokens(0, 10)).unwrap();
+
+        // 10 waiting tokens / 100 running tokens = 0.1, below the 0.5 ratio.
+        assert!(scheduler.next_batch_within_budget(100).unwrap().is_none());
+        assert_eq!(scheduler.queue_depth(), 1);
+
+        // 10 waiting tokens / 10 running tokens = 1.0, above the ratio.
+        let batch = scheduler.next_batch_within_budget(10).unwrap().unwrap();
+        assert_eq!(batch.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_drops_requests_past_queue_timeout() {
+        let config = BatchingConfig {
+            max_batch_size: 10,
+            batching_window_ms: 10,
+            max_queue_size: 10,
+            queue_timeout_ms: 20,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+        scheduler.try_submit(adaptive_request(0)).unwrap();
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        scheduler.try_submit(adaptive_request(1)).unwrap();
+
+        // req-0 is already past its 20ms deadline; next_batch should skip
+        // it and return only the fresh req-1.
+        let batch = scheduler.next_batch().await.unwrap().unwrap();
+
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.requests[0].request_id, "req-1");
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_await_resolves_with_timeout_error_on_eviction() {
+        let config = BatchingConfig {
+            max_batch_size: 10,
+            batching_window_ms: 10,
+            max_queue_size: 10,
+            queue_timeout_ms: 10,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = BatchScheduler::new("worker-0", config);
+        let mut rx = scheduler.submit_and_await(adaptive_request(0)).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // Only the stale request is queued, so next_batch's own window
+        // naturally elapses too; what matters here is that pop_one already
+        // evicted it and resolved its response channel on the way.
+        let _ = scheduler.next_batch().await;
+
+        assert!(matches!(rx.recv().await, Some(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_start_queue_reaper_evicts_stale_request_without_a_pop() {
+        let config = BatchingConfig {
+            max_batch_size: 10,
+            batching_window_ms: 10,
+            max_queue_size: 10,
+            queue_timeout_ms: 10,
+            backpressure: false,
+            high_watermark: 0.8,
+            low_watermark: 0.5,
+            max_batch_prefill_tokens: 4096,
+            max_batch_total_tokens: 16384,
+            waiting_served_ratio: 0.3,
+            min_batching_window_ms: 0,
+            max_batching_window_ms: 50,
+            batch_parallelism: 1,
+        };
+
+        let scheduler = Arc::new(BatchScheduler::new("worker-0", config));
+        scheduler.try_submit(adaptive_request(0)).unwrap();
+
+        let handle = Arc::clone(&scheduler).start_queue_reaper(Duration::from_millis(15));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        assert_eq!(scheduler.queue_depth(), 0);
+        scheduler.shutdown();
+        let _ = handle.await;
+    }
+
+    fn priority_request(id: &str) -> InferenceRequest {
+        InferenceRequest {
+            request_id: id.to_string(),
+            sequence_id: 0,
+            tokens: vec![1, 2, 3],
+            new_tokens: 3,
+            temperature: 1.0,
+            top_p: 0.9,
+            top_k: 50,
+            max_tokens: 100,
+            arrival_time: Instant::now(),
+            metadata: serde_json::json!({}),
+            response_tx: None,
+            retry_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_priority_scheduler_reserves_slots_for_lower_tiers() {
+        let config = PriorityConfig {
+            low_reserved_slots: 1,
+            normal_reserved_slots: 1,
+            low_max_wait_ms: 1_000_000,
+            normal_max_wait_ms: 1_000_000,
+        };
+        let scheduler = PriorityScheduler::new("worker-0", config);
+
+        for i in 0..10 {
+            scheduler
+                .submit(priority_request(&format!("high-{}", i)), Priority::High)
+                .unwrap();
+        }
+        scheduler
+            .submit(priority_request("normal-0"), Priority::Normal)
+            .unwrap();
+        scheduler
+            .submit(priority_request("low-0"), Priority::Low)
+            .unwrap();
+
+        // Without reservation, 10 high-priority requests would completely
+        // starve the single normal/low request out of a 3-slot batch.
+        let batch = scheduler.next_batch(3).await.unwrap().unwrap();
+
+        assert_eq!(batch.len(), 3);
+        let ids: Vec<&str> = batch.requests.iter().map(|r| r.request_id.as_str()).collect();
+        assert!(ids.contains(&"normal-0"));
+        assert!(ids.contains(&"low-0"));
+    }
+
+    #[tokio::test]
+    async fn test_priority_scheduler_promotes_aged_requests() {
+        let config = PriorityConfig {
+            low_reserved_slots: 0,
+            normal_reserved_slots: 1,
+            low_max_wait_ms: 0,
+            normal_max_wait_ms: 1_000_000,
+        };
+        let scheduler = PriorityScheduler::new("worker-0", config);
+
+        for i in 0..5 {
+            scheduler
+                .submit(priority_request(&format!("high-{}", i)), Priority::High)
+                .unwrap();
+        }
+        scheduler
+            .submit(priority_request("low-0"), Priority::Low)
+            .unwrap();
+
+        // `low_max_wait_ms: 0` promotes the low request to normal
+        // immediately; the normal tier's reserved slot then guarantees it's
+        // the one request that makes it into a batch too small for all the
+        // fresh high-priority work to fit.
+        let batch = scheduler.next_batch(1).await.unwrap().unwrap();
+
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.requests[0].request_id, "low-0");
+    }
 }
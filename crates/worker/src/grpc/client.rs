@@ -1,27 +1,267 @@
 //! Worker gRPC client for communicating with next worker in pipeline
 
-use chatloop_common::{Result, ChatLoopError};
-use chatloop_proto::InferenceRequest;
-use tracing::{debug, warn};
+use chatloop_common::{ChatLoopError, Result};
+use chatloop_proto::{
+    ActivationCodec, ActivationDType, ForwardRequest, ForwardResponse, InferenceRequest,
+    InferenceResponse, JsonCodec, WorkerVersion,
+};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tonic::Code;
+use tracing::{debug, info, warn};
+
+/// Connection and retry tuning for the pipeline forwarding client
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Timeout for establishing the initial connection
+    pub connect_timeout: Duration,
+
+    /// Per-call deadline applied to each forward attempt
+    pub call_timeout: Duration,
+
+    /// Maximum number of retry attempts for retryable failures
+    pub max_retries: u32,
+
+    /// Base delay for exponential backoff between retries
+    pub retry_base_delay: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            call_timeout: Duration::from_secs(10),
+            max_retries: 3,
+            retry_base_delay: Duration::from_millis(100),
+        }
+    }
+}
 
 /// gRPC client for next worker in pipeline
+///
+/// Lazily establishes a connection to `endpoint` on first use and caches it
+/// for subsequent calls; the underlying `tonic` channel handles HTTP/2
+/// multiplexing so a single connection serves concurrent forwards.
 pub struct WorkerClient {
     endpoint: String,
+    config: ClientConfig,
+    local_version: WorkerVersion,
+    channel: Mutex<Option<Channel>>,
 }
 
 impl WorkerClient {
-    /// Create a new worker client
-    pub fn new(endpoint: String) -> Self {
-        Self { endpoint }
+    /// Create a new worker client with default connection/retry tuning
+    pub fn new(endpoint: String, local_version: WorkerVersion) -> Self {
+        Self::with_config(endpoint, local_version, ClientConfig::default())
+    }
+
+    /// Create a new worker client with explicit connection/retry tuning
+    pub fn with_config(endpoint: String, local_version: WorkerVersion, config: ClientConfig) -> Self {
+        Self {
+            endpoint,
+            config,
+            local_version,
+            channel: Mutex::new(None),
+        }
+    }
+
+    /// Get the cached channel, establishing one (and handshaking) if this is
+    /// the first call
+    async fn channel(&self) -> Result<Channel> {
+        let mut guard = self.channel.lock().await;
+        if let Some(channel) = guard.as_ref() {
+            return Ok(channel.clone());
+        }
+
+        let endpoint = Endpoint::from_shared(self.endpoint.clone())
+            .map_err(|e| ChatLoopError::Connection(format!("Invalid endpoint {}: {}", self.endpoint, e)))?
+            .connect_timeout(self.config.connect_timeout)
+            .timeout(self.config.call_timeout);
+
+        let channel = endpoint.connect().await.map_err(|e| {
+            ChatLoopError::Connection(format!("Failed to connect to {}: {}", self.endpoint, e))
+        })?;
+
+        self.handshake(channel.clone()).await?;
+
+        *guard = Some(channel.clone());
+        Ok(channel)
+    }
+
+    /// Exchange `WorkerVersion`s with the peer and refuse to proceed on
+    /// mismatch
+    ///
+    /// This runs once per freshly-established connection so a worker never
+    /// forwards activations to a peer running an incompatible build or
+    /// model shard.
+    async fn handshake(&self, channel: Channel) -> Result<()> {
+        let mut client = tonic::client::Grpc::new(channel);
+        client
+            .ready()
+            .await
+            .map_err(|e| ChatLoopError::Connection(format!("Channel not ready: {}", e)))?;
+
+        let path = PathAndQuery::from_static("/chatloop.worker.Worker/Handshake");
+        let response = client
+            .unary(
+                tonic::Request::new(self.local_version.clone()),
+                path,
+                JsonCodec::<WorkerVersion, WorkerVersion>::default(),
+            )
+            .await
+            .map_err(status_to_error)?;
+
+        let peer_version = response.into_inner();
+
+        match self.local_version.incompatibility_reason(&peer_version) {
+            None => {
+                info!(
+                    "Handshake with {} succeeded: model_id={}, protocol_version={}",
+                    self.endpoint, peer_version.model_id, peer_version.protocol_version
+                );
+                Ok(())
+            }
+            Some(reason) => Err(ChatLoopError::incompatible_worker(format!(
+                "peer at {} is incompatible: {}",
+                self.endpoint, reason
+            ))),
+        }
     }
 
-    /// Forward inference request to next worker
-    pub async fn forward(&self, _request: InferenceRequest) -> Result<()> {
+    /// Forward inference request to next worker in the pipeline
+    ///
+    /// Retries transport and retryable status failures with exponential
+    /// backoff; non-retryable codes (`InvalidArgument`, `Internal`) fail fast.
+    pub async fn forward(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let mut attempt = 0;
+
+        loop {
+            let channel = self.channel().await?;
+
+            match self.forward_once(channel, request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(err) if attempt < self.config.max_retries && err.is_retryable() => {
+                    let delay = self.config.retry_base_delay * 2u32.pow(attempt);
+                    warn!(
+                        "Forward to {} failed ({}), retrying in {:?} (attempt {}/{})",
+                        self.endpoint,
+                        err,
+                        delay,
+                        attempt + 1,
+                        self.config.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+
+                    // Drop the cached channel so the next attempt reconnects
+                    *self.channel.lock().await = None;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Perform a single forward attempt over an established channel
+    async fn forward_once(&self, channel: Channel, request: InferenceRequest) -> Result<InferenceResponse> {
         debug!("Forwarding request to next worker at {}", self.endpoint);
 
-        // For now, just return an error indicating not implemented
-        // TODO: Implement actual gRPC client
-        warn!("gRPC client not yet implemented");
-        Err(ChatLoopError::NotImplemented("gRPC client not yet implemented".to_string()))
+        let mut client = tonic::client::Grpc::new(channel);
+        client
+            .ready()
+            .await
+            .map_err(|e| ChatLoopError::Connection(format!("Channel not ready: {}", e)))?;
+
+        let path = PathAndQuery::from_static("/chatloop.worker.Worker/Forward");
+        let response = client
+            .unary(
+                tonic::Request::new(request),
+                path,
+                JsonCodec::<InferenceRequest, InferenceResponse>::default(),
+            )
+            .await
+            .map_err(status_to_error)?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Forward a hidden-state activation tensor to the next worker in the
+    /// pipeline
+    ///
+    /// Uses [`ActivationCodec`] instead of [`JsonCodec`] so the tensor moves
+    /// as the fixed-layout binary frame described in `activation_codec`'s
+    /// module docs, rather than a per-element JSON array. Shares `forward`'s
+    /// retry/backoff policy and cached-channel handshake.
+    pub async fn forward_activations(
+        &self,
+        request: ForwardRequest,
+        dtype: ActivationDType,
+    ) -> Result<ForwardResponse> {
+        let mut attempt = 0;
+
+        loop {
+            let channel = self.channel().await?;
+
+            match self.forward_activations_once(channel, request.clone(), dtype).await {
+                Ok(response) => return Ok(response),
+                Err(err) if attempt < self.config.max_retries && err.is_retryable() => {
+                    let delay = self.config.retry_base_delay * 2u32.pow(attempt);
+                    warn!(
+                        "Forward-activations to {} failed ({}), retrying in {:?} (attempt {}/{})",
+                        self.endpoint,
+                        err,
+                        delay,
+                        attempt + 1,
+                        self.config.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+
+                    // Drop the cached channel so the next attempt reconnects
+                    *self.channel.lock().await = None;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Perform a single forward-activations attempt over an established channel
+    async fn forward_activations_once(
+        &self,
+        channel: Channel,
+        request: ForwardRequest,
+        dtype: ActivationDType,
+    ) -> Result<ForwardResponse> {
+        debug!("Forwarding activations to next worker at {}", self.endpoint);
+
+        let mut client = tonic::client::Grpc::new(channel);
+        client
+            .ready()
+            .await
+            .map_err(|e| ChatLoopError::Connection(format!("Channel not ready: {}", e)))?;
+
+        let path = PathAndQuery::from_static("/chatloop.worker.Worker/ForwardActivations");
+        let response = client
+            .unary(tonic::Request::new(request), path, ActivationCodec::new(dtype))
+            .await
+            .map_err(status_to_error)?;
+
+        Ok(response.into_inner())
     }
 }
+
+/// Map a remote `tonic::Status` back onto `ChatLoopError`
+///
+/// This is the inverse of `ChatLoopError::to_status`.
+fn status_to_error(status: tonic::Status) -> ChatLoopError {
+    match status.code() {
+        Code::Unavailable => ChatLoopError::WorkerUnavailable(status.message().to_string()),
+        Code::ResourceExhausted => ChatLoopError::Overloaded(status.message().to_string()),
+        Code::DeadlineExceeded => ChatLoopError::Timeout(status.message().to_string()),
+        Code::InvalidArgument => ChatLoopError::InvalidInput(status.message().to_string()),
+        Code::Internal => ChatLoopError::Internal(status.message().to_string()),
+        _ => ChatLoopError::Grpc(status),
+    }
+}
+
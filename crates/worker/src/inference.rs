@@ -3,14 +3,56 @@
 //! This module implements the core inference logic for processing
 //! transformer layers. Optimized for CPU execution with SIMD support.
 
-use crate::batching::{InferenceRequest, RequestBatch};
+use crate::batching::{InferenceRequest, RequestBatch, SequenceId};
 use crate::error::{ChatLoopError, Result};
-use crate::model::{KVCache, ModelPartition};
-use crate::tensor::{Tensor, TensorView, TensorOps};
-use chatloop_common::config::LayerGroupConfig;
+use crate::model::{
+    KVCache, KVCacheAllocator, ModelPartition, QuantizedAttentionWeights,
+    QuantizedInt4AttentionWeights, QuantizedInt4MlpWeights, QuantizedMlpWeights, QuantizedTensor,
+    ShardSpec,
+};
+use crate::tensor::{
+    matmul_int4, matmul_int8_per_channel, quantize_int8_per_channel_symmetric, QuantizedInt4,
+    Tensor, TensorView, TensorOps,
+};
+use chatloop_common::config::{LayerGroupConfig, NormType};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, trace};
 
+/// Which numeric kernel `forward_layer` uses for the attention/MLP
+/// projection matmuls. `Int8` quantizes each layer's weights once (cached as
+/// `QuantizedTensor`, one scale per output row) and dynamically quantizes
+/// activations per row before every matmul, trading a little accuracy for
+/// roughly a quarter of the weight memory and an integer dot product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantMode {
+    /// Plain f32 weights and matmuls (the default).
+    None,
+    /// Int8 weights with per-output-row scales, dynamic per-row activation
+    /// quantization.
+    Int8,
+    /// Group-wise int4 weights (see [`crate::tensor::quantize_int4`]).
+    /// Activations stay f32; `matmul_int4` dequantizes one group at a time
+    /// instead of materializing the full weight matrix.
+    Int4,
+}
+
+/// The quantized attention weights `self_attention` is dispatching to this
+/// call, if any - carries the `Arc` itself so the projection match doesn't
+/// need a second lookup keyed on `quant_mode`.
+enum QuantizedAttention {
+    Int8(Arc<QuantizedAttentionWeights>),
+    Int4(Arc<QuantizedInt4AttentionWeights>),
+}
+
+/// The quantized MLP weights `mlp` is dispatching to this call, if any - see
+/// `QuantizedAttention`.
+enum QuantizedMlp {
+    Int8(Arc<QuantizedMlpWeights>),
+    Int4(Arc<QuantizedInt4MlpWeights>),
+}
+
 /// Inference engine for processing forward passes
 pub struct InferenceEngine {
     /// Model partition for this worker
@@ -19,20 +61,69 @@ pub struct InferenceEngine {
     /// Layer group configuration
     config: LayerGroupConfig,
 
-    /// Active KV caches for ongoing sequences
-    kv_caches: Vec<KVCache>,
+    /// Active KV caches for ongoing sequences, keyed by sequence id
+    kv_caches: HashMap<SequenceId, KVCache>,
+
+    /// Shared pool of physical KV cache blocks backing `kv_caches`
+    kv_allocator: KVCacheAllocator,
+
+    /// Which numeric kernel the attention/MLP projections use
+    quant_mode: QuantMode,
+
+    /// Per-layer int8 attention weights, quantized lazily on first use.
+    /// `Arc`-wrapped so every forward call's lookup clones a refcount
+    /// instead of the full weights (including their `Vec<i8>` buffers).
+    quantized_attention_weights: HashMap<usize, Arc<QuantizedAttentionWeights>>,
+
+    /// Per-layer int8 MLP weights, quantized lazily on first use. `Arc`-wrapped
+    /// for the same reason as `quantized_attention_weights`.
+    quantized_mlp_weights: HashMap<usize, Arc<QuantizedMlpWeights>>,
+
+    /// Per-layer int4 attention weights, quantized lazily on first use. Same
+    /// caching strategy as `quantized_attention_weights`.
+    quantized_int4_attention_weights: HashMap<usize, Arc<QuantizedInt4AttentionWeights>>,
+
+    /// Per-layer int4 MLP weights, quantized lazily on first use. Same
+    /// caching strategy as `quantized_mlp_weights`.
+    quantized_int4_mlp_weights: HashMap<usize, Arc<QuantizedInt4MlpWeights>>,
+
+    /// This worker's tensor-parallel shard assignment, or `None` to load
+    /// each layer's attention/MLP weights whole
+    shard: Option<ShardSpec>,
 }
 
 impl InferenceEngine {
     /// Create a new inference engine
-    pub fn new(model: ModelPartition, config: LayerGroupConfig) -> Self {
-        // Pre-allocate KV cache slots
-        let kv_caches = Vec::with_capacity(1024); // Support up to 1024 concurrent sequences
+    pub fn new(
+        model: ModelPartition,
+        config: LayerGroupConfig,
+        quant_mode: QuantMode,
+        shard: Option<ShardSpec>,
+    ) -> Self {
+        let num_kv_heads = if config.num_kv_heads == 0 { config.num_heads } else { config.num_kv_heads };
+        let kv_allocator = KVCacheAllocator::new(config.total_layers, num_kv_heads, config.head_dim);
 
         Self {
             model,
             config,
-            kv_caches,
+            kv_caches: HashMap::new(),
+            kv_allocator,
+            quant_mode,
+            quantized_attention_weights: HashMap::new(),
+            quantized_mlp_weights: HashMap::new(),
+            quantized_int4_attention_weights: HashMap::new(),
+            quantized_int4_mlp_weights: HashMap::new(),
+            shard,
+        }
+    }
+
+    /// Effective number of KV heads: `config.num_kv_heads`, or `num_heads`
+    /// when unset (`0`), meaning ordinary (non-grouped-query) attention.
+    fn num_kv_heads(&self) -> usize {
+        if self.config.num_kv_heads == 0 {
+            self.config.num_heads
+        } else {
+            self.config.num_kv_heads
         }
     }
 
@@ -71,14 +162,27 @@ impl InferenceEngine {
     }
 
     /// Process a single request through this layer group
+    ///
+    /// Only the newly-arrived tail of `request.tokens` (its last
+    /// `new_tokens` entries) is embedded and run through the layers here:
+    /// on a prefill that's the whole prompt, on a decode step it's just the
+    /// one token generated since the previous call. Earlier positions
+    /// already live in the sequence's KV cache and are attended over there
+    /// instead of being recomputed from scratch every step.
     fn forward_request(&mut self, request: &InferenceRequest) -> Result<Vec<f32>> {
-        let mut hidden_states = self.embed_tokens(&request.tokens)?;
+        let new_tokens = &request.tokens[request.tokens.len() - request.new_tokens..];
+        let mut hidden_states = self.embed_tokens(new_tokens)?;
 
         // Process each layer in this group
         for layer_idx in self.config.start_layer..self.config.end_layer {
             hidden_states = self.forward_layer(layer_idx, &hidden_states, request)?;
         }
 
+        // Every layer has now appended this step's K/V at the cache's
+        // existing `seq_len`; commit the new positions so the next call's
+        // `base_position` (and this sequence's `positions()`) sees them.
+        self.get_kv_cache(request.sequence_id).0.advance(request.new_tokens);
+
         Ok(hidden_states)
     }
 
@@ -116,12 +220,12 @@ impl InferenceEngine {
         // Get layer weights
         let attention_weights = self
             .model
-            .get_attention_weights(layer_idx)
+            .get_attention_weights(layer_idx, self.shard)
             .ok_or_else(|| ChatLoopError::model(format!("No attention weights for layer {}", layer_idx)))?;
 
         let mlp_weights = self
             .model
-            .get_mlp_weights(layer_idx)
+            .get_mlp_weights(layer_idx, self.shard)
             .ok_or_else(|| ChatLoopError::model(format!("No MLP weights for layer {}", layer_idx)))?;
 
         let layer_norm = self
@@ -132,9 +236,9 @@ impl InferenceEngine {
         // Reshape hidden_states
         let seq_len = hidden_states.len() / self.config.hidden_dim;
 
-        // 1. Pre-attention layer norm
+        // 1. Pre-attention norm
         let residual = hidden_states.to_vec();
-        let hidden_states = self.layer_norm(
+        let hidden_states = self.pre_norm(
             hidden_states,
             seq_len,
             self.config.hidden_dim,
@@ -157,9 +261,9 @@ impl InferenceEngine {
             .map(|(&r, &a)| r + a)
             .collect();
 
-        // 4. Pre-MLP layer norm
+        // 4. Pre-MLP norm
         let residual = hidden_states.clone();
-        let hidden_states = self.layer_norm(
+        let hidden_states = self.pre_norm(
             &hidden_states,
             seq_len,
             self.config.hidden_dim,
@@ -167,7 +271,7 @@ impl InferenceEngine {
         )?;
 
         // 5. MLP
-        let mlp_output = self.mlp(&hidden_states, seq_len, &mlp_weights)?;
+        let mlp_output = self.mlp(&hidden_states, seq_len, layer_idx, &mlp_weights)?;
 
         // 6. Residual connection
         let output: Vec<f32> = residual
@@ -179,6 +283,20 @@ impl InferenceEngine {
         Ok(output)
     }
 
+    /// Pre-attention/pre-MLP normalization, dispatched on `config.norm_type`
+    fn pre_norm(
+        &self,
+        hidden_states: &[f32],
+        seq_len: usize,
+        hidden_dim: usize,
+        weight: &[f32],
+    ) -> Result<Vec<f32>> {
+        match self.config.norm_type {
+            NormType::LayerNorm => self.layer_norm(hidden_states, seq_len, hidden_dim, weight),
+            NormType::RmsNorm => self.rms_norm(hidden_states, seq_len, hidden_dim, weight),
+        }
+    }
+
     /// Layer normalization
     fn layer_norm(
         &self,
@@ -221,7 +339,122 @@ impl InferenceEngine {
         Ok(output)
     }
 
-    /// Self-attention mechanism (simplified single-head for clarity)
+    /// RMSNorm: what LLaMA, CodeGeeX4, and other decoder-only checkpoints
+    /// actually use in place of mean-subtracting LayerNorm. No mean
+    /// subtraction and no bias: `out_j = x_j / sqrt(mean(x_j^2) + eps) * weight_j`.
+    fn rms_norm(
+        &self,
+        hidden_states: &[f32],
+        seq_len: usize,
+        hidden_dim: usize,
+        weight: &[f32],
+    ) -> Result<Vec<f32>> {
+        let mut output = Vec::with_capacity(hidden_states.len());
+
+        let epsilon = 1e-5;
+
+        for i in 0..seq_len {
+            let start = i * hidden_dim;
+            let end = start + hidden_dim;
+            let layer = &hidden_states[start..end];
+
+            // Sum of squares
+            let ss: f32 = layer.iter().map(|&x| x * x).sum();
+
+            let scale = 1.0 / (ss / (hidden_dim as f32) + epsilon).sqrt();
+
+            // Normalize and apply weight
+            for j in 0..hidden_dim {
+                output.push(layer[j] * scale * weight[j]);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Dense projection `out[s, o] = sum_i in[s, i] * weight[o, i]`, i.e. a
+    /// row-major `(out_dim, in_dim)` weight matrix applied per sequence
+    /// position. The same shape `mlp`'s gate/up/down projections use,
+    /// shared here for `self_attention`'s Q/K/V/O projections.
+    fn project(&self, input: &[f32], seq_len: usize, in_dim: usize, out_dim: usize, weight: &[f32]) -> Vec<f32> {
+        let mut output = vec![0.0f32; seq_len * out_dim];
+
+        for s in 0..seq_len {
+            let in_start = s * in_dim;
+            let layer = &input[in_start..in_start + in_dim];
+
+            for o in 0..out_dim {
+                let mut sum = 0.0f32;
+                for i in 0..in_dim {
+                    sum += layer[i] * weight[o * in_dim + i];
+                }
+                output[s * out_dim + o] = sum;
+            }
+        }
+
+        output
+    }
+
+    /// Dynamically-quantized counterpart of `project`: quantizes `input`
+    /// per row to int8 (`scale = max(|row|)/127`, same scheme
+    /// `QuantizedTensor` uses for weights), then runs the real
+    /// `matmul_int8_per_channel` GEMM against `weight.data_t` (`weight.data`
+    /// transposed to `(in_dim, out_dim)` so it matches the kernel's `(k, n)`
+    /// `b` layout). Per-row activation scales aren't something the kernel's
+    /// single `a_scale` argument can express, so it's called with
+    /// `a_scale = 1.0` and each output row is rescaled by its own
+    /// `a_scales[s]` afterward - equivalent to folding `a_scale` into the
+    /// post-multiply instead of the accumulation. Used in place of
+    /// `project` when `quant_mode == QuantMode::Int8`.
+    fn project_int8(input: &[f32], seq_len: usize, in_dim: usize, weight: &QuantizedTensor) -> Vec<f32> {
+        let (a_data, a_scales) = quantize_int8_per_channel_symmetric(input, &[seq_len, in_dim], 0);
+
+        let mut output = matmul_int8_per_channel(
+            &a_data,
+            1.0,
+            0,
+            &weight.data_t,
+            &weight.scales,
+            seq_len,
+            in_dim,
+            weight.out_dim,
+        )
+        .data;
+
+        for s in 0..seq_len {
+            let a_scale = a_scales[s];
+            for o in 0..weight.out_dim {
+                output[s * weight.out_dim + o] *= a_scale;
+            }
+        }
+
+        output
+    }
+
+    /// Int4 counterpart of `project_int8`: runs the real `matmul_int4`
+    /// kernel directly against `weight`, which is already `(out_dim,
+    /// in_dim)` - the same `(n, k)` layout `matmul_int4` expects - so unlike
+    /// `project_int8` no transposed copy of the weight is needed. Only the
+    /// weights are quantized; activations stay plain f32. Used in place of
+    /// `project` when `quant_mode == QuantMode::Int4`.
+    fn project_int4(input: &[f32], seq_len: usize, in_dim: usize, weight: &QuantizedInt4) -> Result<Vec<f32>> {
+        let view = TensorView::new(input, vec![seq_len, in_dim]);
+        Ok(matmul_int4(&view, weight)?.data)
+    }
+
+    /// Multi-head (optionally grouped-query) self-attention: projects
+    /// `hidden_states` (just this step's new tokens) through
+    /// `weights.{q,k,v}_proj`, splits Q into `num_heads` heads and K/V into
+    /// `num_kv_heads` heads (each of `head_dim`), appends the new K/V into
+    /// `request.sequence_id`'s KV cache, runs causal attention over the
+    /// full cached history (earlier positions plus what was just appended)
+    /// independently per head - broadcasting each K/V head across its
+    /// `num_heads / num_kv_heads` query heads when `num_kv_heads <
+    /// num_heads` - concatenates the per-head outputs, and applies
+    /// `weights.o_proj` back to `hidden_dim`. When `weights.alibi_slopes`
+    /// is present, positions use ALiBi instead of RoPE: `apply_rope`
+    /// becomes a no-op and each head's logits are biased by
+    /// `-slopes[h] * (query_pos - key_pos)` before softmax.
     fn self_attention(
         &mut self,
         hidden_states: &[f32],
@@ -233,148 +466,371 @@ impl InferenceEngine {
         let hidden_dim = self.config.hidden_dim;
         let head_dim = self.config.head_dim;
         let num_heads = self.config.num_heads;
+        let num_kv_heads = self.num_kv_heads();
+        let heads_per_kv_head = num_heads / num_kv_heads;
+        let rope_base = self.config.rope_base;
+
+        let q_dim = num_heads * head_dim;
+        let kv_dim = num_kv_heads * head_dim;
+
+        // ALiBi models bias attention logits by key/query distance instead
+        // of rotating Q/K, so the two schemes are mutually exclusive:
+        // `apply_rope` becomes a no-op below whenever slopes are present.
+        let alibi_slopes = weights.alibi_slopes.as_deref();
+
+        let quantized = match self.quant_mode {
+            QuantMode::Int8 => Some(QuantizedAttention::Int8(self.get_quantized_attention_weights(
+                layer_idx, weights, hidden_dim, q_dim, kv_dim,
+            ))),
+            QuantMode::Int4 => Some(QuantizedAttention::Int4(self.get_quantized_int4_attention_weights(
+                layer_idx, weights, hidden_dim, q_dim, kv_dim,
+            ))),
+            QuantMode::None => None,
+        };
 
-        // Simplified: process as single-head attention
-        // In production, would use multi-head attention with proper Q/K/V projection
-
-        // Project to Q, K, V (simplified - just reshape)
-        let q = hidden_states.to_vec();
-        let k = hidden_states.to_vec();
-        let v = hidden_states.to_vec();
-
-        // Compute attention scores
-        let mut output = vec![0.0f32; hidden_states.len()];
+        // Project to Q, K, V
+        let (q, k, v) = match &quantized {
+            Some(QuantizedAttention::Int8(qw)) => (
+                Self::project_int8(hidden_states, seq_len, hidden_dim, &qw.q_proj),
+                Self::project_int8(hidden_states, seq_len, hidden_dim, &qw.k_proj),
+                Self::project_int8(hidden_states, seq_len, hidden_dim, &qw.v_proj),
+            ),
+            Some(QuantizedAttention::Int4(qw)) => (
+                Self::project_int4(hidden_states, seq_len, hidden_dim, &qw.q_proj)?,
+                Self::project_int4(hidden_states, seq_len, hidden_dim, &qw.k_proj)?,
+                Self::project_int4(hidden_states, seq_len, hidden_dim, &qw.v_proj)?,
+            ),
+            None => (
+                self.project(hidden_states, seq_len, hidden_dim, q_dim, &weights.q_proj),
+                self.project(hidden_states, seq_len, hidden_dim, kv_dim, &weights.k_proj),
+                self.project(hidden_states, seq_len, hidden_dim, kv_dim, &weights.v_proj),
+            ),
+        };
 
+        // Absolute position of the first new token in this step: continues
+        // from wherever this sequence's KV cache already stands (0 on the
+        // very first prefill call, the prompt length on the first decode
+        // step, and so on).
+        let base_position = self.get_kv_cache(request.sequence_id).0.seq_len();
+
+        // RoPE-rotate and cache every new token's K/V for this layer before
+        // computing any attention, so a causal query at step i can see both
+        // the already-cached prefix and the other new tokens appended here
+        // at positions <= i. `seq_len` only advances once every layer has
+        // appended (see forward_request), so re-fetching the cache inside
+        // this loop always resolves the same block for a given position.
         for i in 0..seq_len {
-            let q_start = i * hidden_dim;
-            let q_end = q_start + head_dim;
-            let q_vec = &q[q_start..q_end];
+            let mut k_row = k[i * kv_dim..(i + 1) * kv_dim].to_vec();
+            if alibi_slopes.is_none() {
+                for h in 0..num_kv_heads {
+                    let start = h * head_dim;
+                    Self::apply_rope(&mut k_row[start..start + head_dim], base_position + i, rope_base);
+                }
+            }
+            let v_row = &v[i * kv_dim..(i + 1) * kv_dim];
 
-            let mut attn_output = vec![0.0f32; head_dim];
-            let mut attn_sum = 0.0f32;
+            let (cache, allocator) = self.get_kv_cache(request.sequence_id);
+            cache.append(allocator, layer_idx, &k_row, v_row)?;
+        }
 
-            for j in 0..seq_len {
-                let k_start = j * hidden_dim;
-                let k_end = k_start + head_dim;
-                let k_vec = &k[k_start..k_end];
+        let mut attn_out = vec![0.0f32; seq_len * q_dim];
 
-                // Dot product
-                let score: f32 = q_vec.iter().zip(k_vec.iter()).map(|(&q, &k)| q * k).sum();
+        for i in 0..seq_len {
+            let position = base_position + i;
 
-                // Scale
-                let score = score / (head_dim as f32).sqrt();
+            for h in 0..num_heads {
+                let kv_h = h / heads_per_kv_head;
 
-                // Softmax (simplified - just exp)
-                let attn_weight = score.exp();
-                attn_sum += attn_weight;
+                let q_start = i * q_dim + h * head_dim;
+                let q_end = q_start + head_dim;
+                let mut q_vec = q[q_start..q_end].to_vec();
+                if alibi_slopes.is_none() {
+                    Self::apply_rope(&mut q_vec, position, rope_base);
+                }
 
-                let v_start = j * hidden_dim;
-                let v_end = v_start + head_dim;
-                let v_vec = &v[v_start..v_end];
+                // Streaming (flash-attention-style) softmax: track a running
+                // max `running_max`, a running denominator `running_sum`,
+                // and a running value accumulator, rescaling the
+                // already-accumulated state whenever a new key raises the
+                // max. This avoids ever materializing the full
+                // (seq_len x seq_len) score matrix and keeps `exp` arguments
+                // bounded by the running max instead of overflowing on large
+                // raw scores.
+                let mut head_output = vec![0.0f32; head_dim];
+                let mut running_max = f32::NEG_INFINITY;
+                let mut running_sum = 0.0f32;
+
+                let (cache, allocator) = self.get_kv_cache(request.sequence_id);
+
+                // Causal mask: position i can only attend to cached keys at
+                // positions <= i, including the one just appended above.
+                for pos in 0..=position {
+                    let k_vec = cache
+                        .get_keys(allocator, layer_idx, pos)
+                        .ok_or_else(|| ChatLoopError::tensor("Missing cached key for position"))?;
+                    let k_vec = &k_vec[kv_h * head_dim..(kv_h + 1) * head_dim];
+
+                    // Dot product
+                    let score: f32 = q_vec.iter().zip(k_vec.iter()).map(|(&q, &k)| q * k).sum();
+
+                    // Scale
+                    let score = score / (head_dim as f32).sqrt();
+
+                    // ALiBi: penalize each key by its distance from the
+                    // query, scaled by this head's slope `m_h`. `position`
+                    // is always >= `pos` under the causal mask, so this is
+                    // never a bonus.
+                    let score = match alibi_slopes {
+                        Some(slopes) => score - slopes[h] * (position - pos) as f32,
+                        None => score,
+                    };
+
+                    let new_max = running_max.max(score);
+                    let correction = (running_max - new_max).exp();
+                    let weight = (score - new_max).exp();
+
+                    running_sum = running_sum * correction + weight;
+
+                    let v_vec = cache
+                        .get_values(allocator, layer_idx, pos)
+                        .ok_or_else(|| ChatLoopError::tensor("Missing cached value for position"))?;
+                    let v_vec = &v_vec[kv_h * head_dim..(kv_h + 1) * head_dim];
+
+                    for (idx, &val) in v_vec.iter().enumerate() {
+                        head_output[idx] = head_output[idx] * correction + weight * val;
+                    }
+
+                    running_max = new_max;
+                }
 
-                // Accumulate weighted values
-                for (idx, &val) in v_vec.iter().enumerate() {
-                    attn_output[idx] += attn_weight * val;
+                // Normalize
+                for val in head_output.iter_mut() {
+                    *val /= running_sum;
                 }
-            }
 
-            // Normalize
-            for val in attn_output.iter_mut() {
-                *val /= attn_sum;
+                // Copy to the concatenated per-head output
+                let out_start = i * q_dim + h * head_dim;
+                let out_end = out_start + head_dim;
+                attn_out[out_start..out_end].copy_from_slice(&head_output);
             }
-
-            // Copy to output
-            let out_start = i * hidden_dim;
-            let out_end = out_start + head_dim;
-            output[out_start..out_end].copy_from_slice(&attn_output);
         }
 
+        // Output projection back to hidden_dim
+        let output = match &quantized {
+            Some(QuantizedAttention::Int8(qw)) => Self::project_int8(&attn_out, seq_len, q_dim, &qw.o_proj),
+            Some(QuantizedAttention::Int4(qw)) => Self::project_int4(&attn_out, seq_len, q_dim, &qw.o_proj)?,
+            None => self.project(&attn_out, seq_len, q_dim, hidden_dim, &weights.o_proj),
+        };
+
         Ok(output)
     }
 
-    /// Feed-forward network (simplified)
+    /// Rotary position embedding (RoPE): rotates each consecutive pair of a
+    /// head vector's dimensions by an angle that grows with the token's
+    /// absolute position, so Q/K dot products carry positional information
+    /// without anything being added to the values themselves. For pair `i`
+    /// (`0..head_dim/2`), `theta_i = rope_base^(-2i/head_dim)` and the pair
+    /// `(x[2i], x[2i+1])` is rotated by `position * theta_i`.
+    fn apply_rope(vec: &mut [f32], position: usize, rope_base: f32) {
+        let head_dim = vec.len();
+
+        for i in 0..head_dim / 2 {
+            let theta = rope_base.powf(-2.0 * (i as f32) / (head_dim as f32));
+            let angle = position as f32 * theta;
+            let (sin, cos) = angle.sin_cos();
+
+            let x0 = vec[2 * i];
+            let x1 = vec[2 * i + 1];
+            vec[2 * i] = x0 * cos - x1 * sin;
+            vec[2 * i + 1] = x0 * sin + x1 * cos;
+        }
+    }
+
+    /// Feed-forward network (simplified): `down_proj(SiLU(gate_proj(x)) *
+    /// up_proj(x))`, dispatching each projection to `project`,
+    /// `project_int8`, or `project_int4` depending on `quant_mode`.
     fn mlp(
-        &self,
+        &mut self,
         hidden_states: &[f32],
         seq_len: usize,
+        layer_idx: usize,
         weights: &crate::model::MlpWeights,
     ) -> Result<Vec<f32>> {
         let hidden_dim = self.config.hidden_dim;
         let intermediate_dim = self.config.intermediate_dim;
 
-        let mut output = Vec::with_capacity(hidden_states.len());
+        let quantized = match self.quant_mode {
+            QuantMode::Int8 => Some(QuantizedMlp::Int8(self.get_quantized_mlp_weights(
+                layer_idx, weights, hidden_dim, intermediate_dim,
+            ))),
+            QuantMode::Int4 => Some(QuantizedMlp::Int4(self.get_quantized_int4_mlp_weights(
+                layer_idx, weights, hidden_dim, intermediate_dim,
+            ))),
+            QuantMode::None => None,
+        };
 
-        for i in 0..seq_len {
-            let start = i * hidden_dim;
-            let end = start + hidden_dim;
-            let layer = &hidden_states[start..end];
+        let (gate, up) = match &quantized {
+            Some(QuantizedMlp::Int8(qw)) => (
+                Self::project_int8(hidden_states, seq_len, hidden_dim, &qw.gate_proj),
+                Self::project_int8(hidden_states, seq_len, hidden_dim, &qw.up_proj),
+            ),
+            Some(QuantizedMlp::Int4(qw)) => (
+                Self::project_int4(hidden_states, seq_len, hidden_dim, &qw.gate_proj)?,
+                Self::project_int4(hidden_states, seq_len, hidden_dim, &qw.up_proj)?,
+            ),
+            None => (
+                self.project(hidden_states, seq_len, hidden_dim, intermediate_dim, &weights.gate_proj),
+                self.project(hidden_states, seq_len, hidden_dim, intermediate_dim, &weights.up_proj),
+            ),
+        };
 
-            // Gate projection (with SiLU activation)
-            let mut gate = vec![0.0f32; intermediate_dim];
-            for j in 0..intermediate_dim {
-                let mut sum = 0.0f32;
-                for k in 0..hidden_dim {
-                    sum += layer[k] * weights.gate_proj[j * hidden_dim + k];
-                }
-                // SiLU activation
-                gate[j] = sum / (1.0 + (-sum).exp());
-            }
+        // SiLU(gate) * up
+        let hidden: Vec<f32> = gate
+            .iter()
+            .zip(up.iter())
+            .map(|(&g, &u)| (g / (1.0 + (-g).exp())) * u)
+            .collect();
 
-            // Up projection
-            let mut up = vec![0.0f32; intermediate_dim];
-            for j in 0..intermediate_dim {
-                let mut sum = 0.0f32;
-                for k in 0..hidden_dim {
-                    sum += layer[k] * weights.up_proj[j * hidden_dim + k];
-                }
-                up[j] = sum;
-            }
+        let output = match &quantized {
+            Some(QuantizedMlp::Int8(qw)) => Self::project_int8(&hidden, seq_len, intermediate_dim, &qw.down_proj),
+            Some(QuantizedMlp::Int4(qw)) => Self::project_int4(&hidden, seq_len, intermediate_dim, &qw.down_proj)?,
+            None => self.project(&hidden, seq_len, intermediate_dim, hidden_dim, &weights.down_proj),
+        };
 
-            // Element-wise multiply
-            let mut hidden = vec![0.0f32; intermediate_dim];
-            for j in 0..intermediate_dim {
-                hidden[j] = gate[j] * up[j];
-            }
+        Ok(output)
+    }
 
-            // Down projection
-            let mut layer_out = vec![0.0f32; hidden_dim];
-            for j in 0..hidden_dim {
-                let mut sum = 0.0f32;
-                for k in 0..intermediate_dim {
-                    sum += hidden[k] * weights.down_proj[j * intermediate_dim + k];
-                }
-                layer_out[j] = sum;
-            }
+    /// Get or create `sequence_id`'s KV cache
+    ///
+    /// Returns the cache alongside the shared allocator its blocks are
+    /// drawn from, since every `KVCache` method that touches block data
+    /// needs both.
+    fn get_kv_cache(&mut self, sequence_id: SequenceId) -> (&mut KVCache, &mut KVCacheAllocator) {
+        let total_layers = self.config.total_layers;
+        let num_kv_heads = self.num_kv_heads();
+        let head_dim = self.config.head_dim;
 
-            output.extend(layer_out);
-        }
+        let cache = self
+            .kv_caches
+            .entry(sequence_id)
+            .or_insert_with(|| KVCache::new(total_layers, num_kv_heads, head_dim));
 
-        Ok(output)
+        (cache, &mut self.kv_allocator)
     }
 
-    /// Get or create a KV cache for a sequence
-    fn get_kv_cache(&mut self, seq_len: usize) -> &mut KVCache {
-        // Simplified: just use index 0
-        // In production, would properly manage per-sequence caches
-        if self.kv_caches.is_empty() {
-            let cache = KVCache::new(
-                self.config.total_layers,
-                self.config.num_heads,
-                self.config.head_dim,
-                2048, // max sequence length
-            );
-            self.kv_caches.push(cache);
+    /// Release a finished sequence's KV cache, freeing its blocks back to
+    /// the shared allocator
+    ///
+    /// Callers (e.g. the generation loop once a sequence hits EOS or
+    /// `max_tokens`) should call this as soon as a sequence is done so its
+    /// blocks - and any prompt-prefix blocks still shared via `fork` -
+    /// become available to other sequences.
+    pub fn finish_sequence(&mut self, sequence_id: SequenceId) {
+        if let Some(mut cache) = self.kv_caches.remove(&sequence_id) {
+            cache.reset(&mut self.kv_allocator);
         }
+    }
+
+    /// Get this layer's int8-quantized attention weights, quantizing and
+    /// caching them on first use. Returns a cheap `Arc` clone, not a deep
+    /// copy of the cached weights.
+    fn get_quantized_attention_weights(
+        &mut self,
+        layer_idx: usize,
+        weights: &crate::model::AttentionWeights,
+        hidden_dim: usize,
+        q_dim: usize,
+        kv_dim: usize,
+    ) -> Arc<QuantizedAttentionWeights> {
+        self.quantized_attention_weights
+            .entry(layer_idx)
+            .or_insert_with(|| Arc::new(QuantizedAttentionWeights::quantize(weights, hidden_dim, q_dim, kv_dim)))
+            .clone()
+    }
 
-        &mut self.kv_caches[0]
+    /// Get this layer's int8-quantized MLP weights, quantizing and caching
+    /// them on first use. Returns a cheap `Arc` clone, not a deep copy of
+    /// the cached weights.
+    fn get_quantized_mlp_weights(
+        &mut self,
+        layer_idx: usize,
+        weights: &crate::model::MlpWeights,
+        hidden_dim: usize,
+        intermediate_dim: usize,
+    ) -> Arc<QuantizedMlpWeights> {
+        self.quantized_mlp_weights
+            .entry(layer_idx)
+            .or_insert_with(|| Arc::new(QuantizedMlpWeights::quantize(weights, hidden_dim, intermediate_dim)))
+            .clone()
+    }
+
+    /// Get this layer's int4-quantized attention weights, quantizing and
+    /// caching them on first use. Returns a cheap `Arc` clone, not a deep
+    /// copy of the cached weights.
+    fn get_quantized_int4_attention_weights(
+        &mut self,
+        layer_idx: usize,
+        weights: &crate::model::AttentionWeights,
+        hidden_dim: usize,
+        q_dim: usize,
+        kv_dim: usize,
+    ) -> Arc<QuantizedInt4AttentionWeights> {
+        self.quantized_int4_attention_weights
+            .entry(layer_idx)
+            .or_insert_with(|| Arc::new(QuantizedInt4AttentionWeights::quantize(weights, hidden_dim, q_dim, kv_dim)))
+            .clone()
+    }
+
+    /// Get this layer's int4-quantized MLP weights, quantizing and caching
+    /// them on first use. Returns a cheap `Arc` clone, not a deep copy of
+    /// the cached weights.
+    fn get_quantized_int4_mlp_weights(
+        &mut self,
+        layer_idx: usize,
+        weights: &crate::model::MlpWeights,
+        hidden_dim: usize,
+        intermediate_dim: usize,
+    ) -> Arc<QuantizedInt4MlpWeights> {
+        self.quantized_int4_mlp_weights
+            .entry(layer_idx)
+            .or_insert_with(|| Arc::new(QuantizedInt4MlpWeights::quantize(weights, hidden_dim, intermediate_dim)))
+            .clone()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::PositionEncoding;
     use chatloop_common::config::LayerGroupConfig;
+    use std::io::Write;
     use std::path::PathBuf;
+    use tempfile::NamedTempFile;
+
+    /// Build a throwaway single-tensor safetensors file and load it as a
+    /// [`ModelPartition`], so tests that only exercise `quant_mode`-agnostic
+    /// helpers (`layer_norm`, `project`, ...) get a real engine instead of
+    /// panicking on `unimplemented!()`. The fixture tensor's name has no
+    /// `model.layers.N.` prefix, so `index_layer_group_tensors` skips it and
+    /// `config` otherwise doesn't matter to these tests.
+    fn test_model_partition(config: LayerGroupConfig) -> ModelPartition {
+        let header = serde_json::json!({
+            "weight": {
+                "dtype": "F32",
+                "shape": [2, 2],
+                "data_offsets": [0, 16]
+            }
+        });
+        let header_json = serde_json::to_string(&header).unwrap();
+        let header_len = header_json.len() as u64;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&header_len.to_le_bytes()).unwrap();
+        file.write_all(header_json.as_bytes()).unwrap();
+        file.write_all(&[0u8; 16]).unwrap();
+
+        ModelPartition::load(file.path(), config, PositionEncoding::Rotary).unwrap()
+    }
 
     #[test]
     fn test_layer_norm() {
@@ -386,12 +842,16 @@ mod tests {
             head_dim: 128,
             hidden_dim: 4096,
             intermediate_dim: 11008,
+            norm_type: NormType::LayerNorm,
+            rope_base: 10000.0,
+            num_kv_heads: 0,
         };
 
         let engine = InferenceEngine::new(
-            // Would load actual model in production
-            unimplemented!(),
+            test_model_partition(config.clone()),
             config,
+            QuantMode::None,
+            None,
         );
 
         let hidden_states = vec![1.0f32; 128 * 4096];
@@ -402,4 +862,154 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 128 * 4096);
     }
+
+    #[test]
+    fn test_rms_norm() {
+        let config = LayerGroupConfig {
+            start_layer: 0,
+            end_layer: 1,
+            total_layers: 32,
+            num_heads: 32,
+            head_dim: 128,
+            hidden_dim: 4096,
+            intermediate_dim: 11008,
+            norm_type: NormType::RmsNorm,
+            rope_base: 10000.0,
+            num_kv_heads: 0,
+        };
+
+        let engine = InferenceEngine::new(
+            test_model_partition(config.clone()),
+            config,
+            QuantMode::None,
+            None,
+        );
+
+        let hidden_states = vec![2.0f32; 4];
+        let weight = vec![1.0f32; 4];
+
+        // mean(x_j^2) == 4.0, so scale == 1 / sqrt(4.0 + eps) ~= 0.5
+        let result = engine.rms_norm(&hidden_states, 1, 4, &weight).unwrap();
+
+        assert_eq!(result.len(), 4);
+        for &v in &result {
+            assert!((v - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_apply_rope_position_zero_is_a_no_op() {
+        let mut vec = vec![1.0f32, 2.0, 3.0, 4.0];
+        InferenceEngine::apply_rope(&mut vec, 0, 10000.0);
+
+        assert_eq!(vec, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_apply_rope_rotates_each_pair_by_its_own_frequency() {
+        // head_dim = 4 -> pairs (0,1) with theta_0 = base^0 = 1, (2,3) with
+        // theta_1 = base^(-2/4) = base^(-0.5). At position 1, pair 0 rotates by
+        // angle 1.0 and pair 1 by angle base^-0.5 (a much smaller angle).
+        let base = 10000.0f32;
+        let mut vec = vec![1.0f32, 0.0, 1.0, 0.0];
+        InferenceEngine::apply_rope(&mut vec, 1, base);
+
+        let theta0 = base.powf(0.0);
+        let theta1 = base.powf(-0.5);
+        let (sin0, cos0) = theta0.sin_cos();
+        let (sin1, cos1) = theta1.sin_cos();
+
+        assert!((vec[0] - cos0).abs() < 1e-6);
+        assert!((vec[1] - sin0).abs() < 1e-6);
+        assert!((vec[2] - cos1).abs() < 1e-6);
+        assert!((vec[3] - sin1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_apply_rope_preserves_pair_magnitude() {
+        // Rotation must be norm-preserving regardless of position/base.
+        let mut vec = vec![3.0f32, 4.0, -1.0, 2.0];
+        let original_norms: Vec<f32> = vec
+            .chunks(2)
+            .map(|pair| (pair[0] * pair[0] + pair[1] * pair[1]).sqrt())
+            .collect();
+
+        InferenceEngine::apply_rope(&mut vec, 7, 10000.0);
+
+        let rotated_norms: Vec<f32> = vec
+            .chunks(2)
+            .map(|pair| (pair[0] * pair[0] + pair[1] * pair[1]).sqrt())
+            .collect();
+
+        for (orig, rotated) in original_norms.iter().zip(rotated_norms.iter()) {
+            assert!((orig - rotated).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_project_computes_row_major_out_by_in_matmul() {
+        let config = LayerGroupConfig {
+            start_layer: 0,
+            end_layer: 1,
+            total_layers: 32,
+            num_heads: 32,
+            head_dim: 128,
+            hidden_dim: 4096,
+            intermediate_dim: 11008,
+            norm_type: NormType::RmsNorm,
+            rope_base: 10000.0,
+            num_kv_heads: 0,
+        };
+
+        let engine = InferenceEngine::new(
+            test_model_partition(config.clone()),
+            config,
+            QuantMode::None,
+            None,
+        );
+
+        // 2 positions, in_dim 3, out_dim 2; weight is (out_dim, in_dim).
+        let input = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let weight = vec![1.0f32, 0.0, 0.0, 0.0, 1.0, 0.0];
+
+        let result = engine.project(&input, 2, 3, 2, &weight);
+
+        assert_eq!(result, vec![1.0, 2.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_project_int8_matches_project_within_quantization_error() {
+        let config = LayerGroupConfig {
+            start_layer: 0,
+            end_layer: 1,
+            total_layers: 32,
+            num_heads: 32,
+            head_dim: 128,
+            hidden_dim: 4096,
+            intermediate_dim: 11008,
+            norm_type: NormType::RmsNorm,
+            rope_base: 10000.0,
+            num_kv_heads: 0,
+        };
+
+        let engine = InferenceEngine::new(
+            test_model_partition(config.clone()),
+            config,
+            QuantMode::None,
+            None,
+        );
+
+        let input = vec![1.0f32, -2.0, 3.0, 0.5, 4.0, -1.5];
+        let weight = vec![2.0f32, 0.0, -1.0, 0.5, 3.0, 1.0];
+
+        let expected = engine.project(&input, 2, 3, 2, &weight);
+
+        let quantized_weight = QuantizedTensor::quantize(&weight, 2, 3);
+        let actual = InferenceEngine::project_int8(&input, 2, 3, &quantized_weight);
+
+        assert_eq!(actual.len(), expected.len());
+        for (&a, &e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.2, "expected ~{e}, got {a}");
+        }
+    }
 }
@@ -3,11 +3,28 @@
 //! Distributed LLM inference worker that processes a partition of model layers.
 //! Uses memory-mapped weights and efficient batching for low-latency inference.
 
+#[cfg(feature = "metrics")]
+pub mod admin;
 pub mod batching;
+pub mod grpc;
 pub mod inference;
+pub mod manager;
 pub mod model;
+pub mod progress;
+pub mod resource;
 pub mod tensor;
+pub mod tranquility;
 
-pub use batching::{BatchScheduler, InferenceRequest, Priority, RequestBatch};
-pub use inference::InferenceEngine;
-pub use model::{KVCache, ModelPartition};
+pub use batching::{
+    BatchScheduler, GeneratedToken, InferenceRequest, Priority, RequestBatch, ResponseStream,
+    SequenceId,
+};
+pub use grpc::WorkerClient as PipelineClient;
+pub use inference::{InferenceEngine, QuantMode};
+pub use manager::{
+    Worker, WorkerCommand, WorkerManager, WorkerState, WorkerStateKind, WorkerTaskSnapshot,
+};
+pub use model::{KVCache, ModelPartition, PositionEncoding, ShardSpec};
+pub use progress::{BatchProgressEvent, ProgressAggregates, ProgressPublisher};
+pub use resource::ResourceMonitor;
+pub use tranquility::TranquilityControl;
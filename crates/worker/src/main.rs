@@ -3,13 +3,34 @@
 //! This is the main entry point for the ChatLoop inference worker.
 //! It loads a model partition, starts the gRPC server, and processes inference requests.
 
+use chatloop_common::config::QuantizationType;
 use chatloop_common::{ChatLoopConfig, ChatLoopError, Result};
-use chatloop_worker::{BatchScheduler, InferenceEngine, ModelPartition};
+use chatloop_proto::{ActivationDType, ForwardRequest, WorkerVersion, MAX_PROTOCOL_VERSION};
+use chatloop_worker::{
+    BatchScheduler, InferenceEngine, InferenceRequest, ModelPartition, PipelineClient,
+    PositionEncoding, ProgressPublisher, QuantMode, RequestBatch, ResourceMonitor, ShardSpec,
+    TranquilityControl, Worker, WorkerCommand, WorkerManager, WorkerState,
+};
+use rand::Rng;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::signal;
-use tracing::{error, info, warn};
+use tokio::sync::mpsc;
+use tokio::sync::Mutex as AsyncMutex;
+use tracing::{error, info, trace, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// How many buffered commands `WorkerCommand` senders may queue before
+/// `send` blocks; control commands are rare and latency-insensitive, so a
+/// small bound is plenty
+const COMMAND_CHANNEL_CAPACITY: usize = 8;
+
+/// How often the background resource sampler refreshes `memory_used`/`cpu_utilization`
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
@@ -27,17 +48,31 @@ async fn main() -> Result<()> {
     let config_path = std::env::var("CHATLOOP_CONFIG")
         .unwrap_or_else(|_| "configs/worker-config.yaml".to_string());
 
-    let config = ChatLoopConfig::from_file(&config_path)?;
+    let config = ChatLoopConfig::from_file_with_env_overrides(&config_path)?;
     config.validate()?;
 
+    // Get worker-specific config
+    let worker_config = config.worker.as_ref()
+        .ok_or_else(|| ChatLoopError::config("Worker config not found"))?;
+
+    if let Some(performance) = config.performance.as_ref() {
+        chatloop_worker::tensor::plugin::KERNEL_REGISTRY
+            .load_from_paths(&worker_config.worker_id, &performance.kernel_plugins)?;
+        if !chatloop_worker::tensor::plugin::KERNEL_REGISTRY.is_empty() {
+            info!(
+                "Loaded tensor-kernel plugins: {:?}",
+                chatloop_worker::tensor::plugin::KERNEL_REGISTRY.loaded()
+            );
+        }
+    }
+
     info!(
         "Worker configuration loaded: mode={}, bind={}:{}",
         config.mode, config.bind_address, config.port
     );
 
-    // Get worker-specific config
-    let worker_config = config.worker.as_ref()
-        .ok_or_else(|| ChatLoopError::config("Worker config not found"))?;
+    #[cfg(feature = "metrics")]
+    chatloop_common::metrics::exporter::spawn_if_enabled(&config);
 
     // Load model partition
     info!(
@@ -46,9 +81,16 @@ async fn main() -> Result<()> {
         worker_config.layer_group.end_layer
     );
 
+    let position_encoding = config
+        .model
+        .as_ref()
+        .map(|m| PositionEncoding::from_architecture(&m.architecture))
+        .unwrap_or(PositionEncoding::Rotary);
+
     let model_partition = ModelPartition::load(
         &worker_config.weights_path,
         worker_config.layer_group.clone(),
+        position_encoding,
     )?;
 
     info!(
@@ -56,29 +98,117 @@ async fn main() -> Result<()> {
         model_partition.memory_usage_bytes() as f64 / (1024.0 * 1024.0 * 1024.0)
     );
 
+    // Identity this worker presents during the handshake it runs before
+    // forwarding activations to `next_worker_endpoint`, so a peer running an
+    // incompatible build or model shard is rejected rather than silently fed
+    // mismatched hidden states.
+    let local_worker_version = WorkerVersion {
+        model_id: config.model.as_ref().map(|m| m.model_id.clone()).unwrap_or_default(),
+        protocol_version: MAX_PROTOCOL_VERSION,
+        weights_digest: model_partition.weights_digest().to_string(),
+    };
+
+    let next_worker_client = worker_config
+        .next_worker_endpoint
+        .clone()
+        .map(|endpoint| Arc::new(PipelineClient::new(endpoint, local_worker_version.clone())));
+
     // Create inference engine
+    let quant_mode = match config.model.as_ref().map(|m| m.quantization) {
+        Some(QuantizationType::Int8) => QuantMode::Int8,
+        Some(QuantizationType::Int4) => QuantMode::Int4,
+        Some(QuantizationType::None) | None => QuantMode::None,
+    };
+
+    // Activations forwarded to the next worker are narrowed to the same
+    // dtype this engine quantizes its own weights to, so a quantized
+    // pipeline doesn't pay full fp32 wire cost between every stage. Int4
+    // has no dedicated wire dtype, so its activations narrow to int8 same
+    // as Int8's.
+    let activation_dtype = match quant_mode {
+        QuantMode::None => ActivationDType::Fp32,
+        QuantMode::Int8 | QuantMode::Int4 => ActivationDType::Int8,
+    };
+
+    let shard = worker_config
+        .tensor_parallel
+        .map(|tp| ShardSpec { rank: tp.rank, world_size: tp.world_size, dim: 0 });
+
     let inference_engine = InferenceEngine::new(
         model_partition,
         worker_config.layer_group.clone(),
+        quant_mode,
+        shard,
     );
 
     // Create batch scheduler
-    let batch_scheduler = BatchScheduler::new(worker_config.batching.clone());
+    let batch_scheduler =
+        BatchScheduler::new(worker_config.worker_id.clone(), worker_config.batching.clone());
+
+    let resource_monitor = Arc::new(ResourceMonitor::new(worker_config.worker_id.clone()));
+    Arc::clone(&resource_monitor).start_sampler(RESOURCE_SAMPLE_INTERVAL);
+
+    // Start worker tasks under the task manager, which tracks each task's
+    // running state and consecutive-failure count for introspection
+    let worker_manager = Arc::new(WorkerManager::new());
+
+    let (command_tx, command_rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
 
-    // Start worker tasks
-    let worker_handle = tokio::spawn(run_worker_loop(
-        inference_engine,
-        batch_scheduler,
-        worker_config.clone(),
+    let retry_policy = RetryPolicy {
+        max_retries: worker_config.max_batch_retries,
+        base_delay: Duration::from_millis(worker_config.batch_retry_base_delay_ms),
+        max_delay: Duration::from_millis(worker_config.batch_retry_max_delay_ms),
+    };
+
+    let tranquility = Arc::new(TranquilityControl::new(
+        worker_config.batching.tranquility,
+        worker_config.tranquility_state_path.clone(),
     ));
 
-    // Wait for shutdown signal
+    let progress = Arc::new(ProgressPublisher::new());
+
+    let inference_loop = InferenceLoopWorker::new(
+        Arc::new(AsyncMutex::new(inference_engine)),
+        Arc::new(batch_scheduler),
+        Arc::clone(&resource_monitor),
+        retry_policy,
+        Arc::clone(&tranquility),
+        Arc::clone(&progress),
+        worker_config.batching.batch_parallelism,
+        next_worker_client,
+        activation_dtype,
+        command_rx,
+    );
+    let mut worker_handle = worker_manager.spawn(Box::new(inference_loop));
+
+    #[cfg(feature = "metrics")]
+    chatloop_worker::admin::spawn_if_enabled(
+        worker_config,
+        Arc::clone(&worker_manager),
+        command_tx.clone(),
+        Arc::clone(&progress),
+    );
+
+    // Wait for shutdown signal. Ctrl+C exits immediately, same as before;
+    // SIGTERM instead asks the inference loop to drain - stop accepting new
+    // requests but finish in-flight/queued batches - so a Kubernetes-style
+    // rolling restart doesn't drop the batch mid-flight.
     tokio::select! {
         _ = signal::ctrl_c() => {
-            info!("Received shutdown signal");
+            info!("Received Ctrl+C, shutting down immediately");
         }
-        result = worker_handle => {
-            result??;
+        _ = terminate_signal() => {
+            info!("Received SIGTERM, draining in-flight and queued batches");
+            if command_tx.send(WorkerCommand::Drain).await.is_err() {
+                warn!("Inference loop already exited; nothing to drain");
+            } else if let Err(e) = (&mut worker_handle).await {
+                error!("Worker loop task panicked while draining: {}", e);
+            }
+        }
+        result = &mut worker_handle => {
+            if let Err(e) = result {
+                error!("Worker loop task panicked: {}", e);
+            }
         }
     }
 
@@ -86,61 +216,410 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Main worker processing loop
-async fn run_worker_loop(
-    mut inference_engine: InferenceEngine,
-    batch_scheduler: BatchScheduler,
-    worker_config: chatloop_common::config::WorkerConfig,
-) -> Result<()> {
-    info!("Starting worker processing loop");
-
-    loop {
-        // Get next batch of requests
-        match batch_scheduler.next_batch().await {
-            Ok(Some(batch)) => {
-                // Process the batch
-                let start = std::time::Instant::now();
-
-                match inference_engine.forward_batch(&batch) {
-                    Ok(outputs) => {
-                        let duration = start.elapsed();
-
-                        info!(
-                            "Processed batch of {} requests in {:?} ({:.2} ms/request)",
-                            batch.len(),
-                            duration,
-                            duration.as_millis() as f64 / batch.len() as f64
-                        );
+/// Resolves once SIGTERM is received; never resolves on non-Unix platforms,
+/// where only Ctrl+C is available
+#[cfg(unix)]
+async fn terminate_signal() {
+    match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+        Ok(mut stream) => {
+            stream.recv().await;
+        }
+        Err(e) => {
+            warn!("Failed to install SIGTERM handler: {}", e);
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn terminate_signal() {
+    std::future::pending::<()>().await;
+}
 
-                        // In production, would send outputs to next worker or return to client
-                        for (i, output) in outputs.iter().enumerate() {
-                            trace!(
-                                "Request {} output: {} elements, first={}",
-                                i,
-                                output.len(),
-                                output.first().unwrap_or(&0.0)
-                            );
+/// Drives the batch-scheduler -> inference-engine pipeline as a
+/// `WorkerManager` task
+///
+/// Each `step` pulls at most one batch and dispatches its `forward_batch`
+/// to the blocking thread pool (via `spawn_blocking`) instead of running it
+/// inline on the async reactor, since inference is CPU-bound and would
+/// otherwise stall every other task on this runtime. `batch_parallelism`
+/// bounds how many batches may be in flight on the blocking pool at once:
+/// once that many are outstanding, `step` waits for the oldest to finish
+/// before dispatching the next. The engine itself is shared behind a
+/// `Mutex` because its KV caches are mutated per forward pass, so in-flight
+/// batches still serialize on the actual compute - parallelism here
+/// overlaps batch scheduling and dispatch with that compute rather than
+/// running forward passes for two batches at the same instant.
+///
+/// `step` also races `batch_scheduler.next_batch()` against `commands`, a
+/// channel of [`WorkerCommand`]s fed by `main`'s shutdown handling and the
+/// admin API: `Pause`/`Resume` toggle whether new batches are pulled at
+/// all, `Drain` stops new admissions on the scheduler while letting `step`
+/// keep draining whatever's already queued until it reports `Done`, and
+/// `SetTranquility` updates the shared [`TranquilityControl`] each
+/// `process_batch` call consults to throttle itself after a batch finishes.
+/// Every dispatched batch is also reported to `progress`, a
+/// [`ProgressPublisher`] any number of dashboards/routers can subscribe to
+/// for real-time throughput instead of scraping logs.
+struct InferenceLoopWorker {
+    inference_engine: Arc<AsyncMutex<InferenceEngine>>,
+    batch_scheduler: Arc<BatchScheduler>,
+    resource_monitor: Arc<ResourceMonitor>,
+    retry_policy: RetryPolicy,
+    tranquility: Arc<TranquilityControl>,
+    progress: Arc<ProgressPublisher>,
+    batch_parallelism: usize,
+    next_worker_client: Option<Arc<PipelineClient>>,
+    activation_dtype: ActivationDType,
+    in_flight: VecDeque<tokio::task::JoinHandle<()>>,
+    commands: mpsc::Receiver<WorkerCommand>,
+    paused: bool,
+    draining: bool,
+}
+
+impl InferenceLoopWorker {
+    fn new(
+        inference_engine: Arc<AsyncMutex<InferenceEngine>>,
+        batch_scheduler: Arc<BatchScheduler>,
+        resource_monitor: Arc<ResourceMonitor>,
+        retry_policy: RetryPolicy,
+        tranquility: Arc<TranquilityControl>,
+        progress: Arc<ProgressPublisher>,
+        batch_parallelism: usize,
+        next_worker_client: Option<Arc<PipelineClient>>,
+        activation_dtype: ActivationDType,
+        commands: mpsc::Receiver<WorkerCommand>,
+    ) -> Self {
+        let batch_parallelism = batch_parallelism.max(1);
+        Self {
+            inference_engine,
+            batch_scheduler,
+            resource_monitor,
+            retry_policy,
+            tranquility,
+            progress,
+            batch_parallelism,
+            next_worker_client,
+            activation_dtype,
+            in_flight: VecDeque::with_capacity(batch_parallelism),
+            commands,
+            paused: false,
+            draining: false,
+        }
+    }
+
+    /// Apply a received command, returning the resulting step state
+    fn apply_command(&mut self, command: WorkerCommand) -> WorkerState {
+        match command {
+            WorkerCommand::Pause => {
+                info!("Inference loop paused");
+                self.paused = true;
+                WorkerState::Active
+            }
+            WorkerCommand::Resume => {
+                info!("Inference loop resumed");
+                self.paused = false;
+                WorkerState::Active
+            }
+            WorkerCommand::Drain => {
+                info!("Inference loop draining: no longer accepting new requests");
+                self.paused = false;
+                self.draining = true;
+                self.batch_scheduler.start_draining();
+                WorkerState::Active
+            }
+            WorkerCommand::Shutdown => {
+                info!("Inference loop stopping immediately");
+                WorkerState::Done
+            }
+            WorkerCommand::SetTranquility(value) => {
+                info!("Setting tranquility multiplier to {}", value);
+                self.tranquility.set(value);
+                WorkerState::Active
+            }
+        }
+    }
+
+    /// Dispatch (or react to the absence of) the batch `next_batch` produced
+    async fn handle_batch_outcome(
+        &mut self,
+        outcome: Result<Option<RequestBatch>>,
+    ) -> Result<WorkerState> {
+        match outcome {
+            Ok(Some(batch)) => {
+                if self.in_flight.len() >= self.batch_parallelism {
+                    if let Some(handle) = self.in_flight.pop_front() {
+                        if let Err(e) = handle.await {
+                            error!("Batch processing task panicked: {}", e);
                         }
                     }
-                    Err(e) => {
-                        error!("Error processing batch: {}", e);
-                        // Continue processing other batches
-                    }
                 }
+
+                let in_flight = self.in_flight.len() + 1;
+                let batch_id = self.progress.batch_started(batch.len(), in_flight);
+
+                self.in_flight.push_back(tokio::spawn(process_batch(
+                    Arc::clone(&self.inference_engine),
+                    Arc::clone(&self.batch_scheduler),
+                    Arc::clone(&self.resource_monitor),
+                    self.retry_policy,
+                    Arc::clone(&self.tranquility),
+                    Arc::clone(&self.progress),
+                    self.next_worker_client.clone(),
+                    self.activation_dtype,
+                    batch_id,
+                    in_flight,
+                    batch,
+                )));
+
+                Ok(WorkerState::Active)
             }
             Ok(None) => {
                 // No batch available (timeout or shutdown)
                 trace!("No batch available, continuing");
-                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok(WorkerState::Idle(Duration::from_millis(10)))
+            }
+            Err(e) if matches!(e, ChatLoopError::Timeout(_)) => {
+                trace!("Batch timeout, continuing");
+                Ok(WorkerState::Idle(Duration::from_millis(10)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Worker for InferenceLoopWorker {
+    fn name(&self) -> &str {
+        "inference_loop"
+    }
+
+    fn step<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = Result<WorkerState>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.draining && self.batch_scheduler.drained() && self.in_flight.is_empty() {
+                info!("Drain complete, stopping inference loop");
+                return Ok(WorkerState::Done);
             }
-            Err(e) => {
-                if matches!(e, ChatLoopError::Timeout(_)) {
-                    trace!("Batch timeout, continuing");
-                } else {
-                    error!("Error getting batch: {}", e);
+
+            if self.paused {
+                return Ok(match self.commands.recv().await {
+                    Some(command) => self.apply_command(command),
+                    None => WorkerState::Done,
+                });
+            }
+
+            tokio::select! {
+                command = self.commands.recv() => {
+                    Ok(match command {
+                        Some(command) => self.apply_command(command),
+                        None => WorkerState::Done,
+                    })
+                }
+                outcome = self.batch_scheduler.next_batch() => {
+                    self.handle_batch_outcome(outcome).await
+                }
+            }
+        })
+    }
+}
+
+/// Retry tuning for batches that fail with a retryable `forward_batch`
+/// error, read from `WorkerConfig` at startup
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+/// Upper bound on the random jitter added on top of the computed backoff
+/// delay, as a fraction of that delay
+const RETRY_JITTER_FRACTION: f64 = 0.2;
+
+/// `delay = min(base * 2^attempt, max_delay)` plus up to 20% random jitter,
+/// so many requests retrying at once don't all hammer the engine in lockstep
+fn retry_delay(policy: RetryPolicy, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt);
+    let backoff = policy
+        .base_delay
+        .checked_mul(factor)
+        .unwrap_or(policy.max_delay)
+        .min(policy.max_delay);
+
+    let jitter = rand::thread_rng().gen_range(0.0..RETRY_JITTER_FRACTION);
+    backoff.mul_f64(1.0 + jitter)
+}
+
+/// Run one batch's forward pass on the blocking thread pool and record its
+/// latency. On a retryable error, each request is requeued with its
+/// `retry_count` bumped and a backoff delay; a request that has exhausted
+/// `retry_policy.max_retries`, or that failed non-retryably, is dropped with
+/// an error sent to its `response_tx` instead of being retried forever, so
+/// one bad batch doesn't take down the worker loop. Regardless of outcome,
+/// sleeps for `duration * tranquility` afterward so the configured
+/// tranquility throttle caps how busy this batch's "lane" keeps the worker,
+/// and publishes a `Completed` [`BatchProgressEvent`] onto `progress` with
+/// the per-request token counts and updated rolling aggregates, mirroring
+/// the `Started` event `handle_batch_outcome` published when this batch was
+/// dispatched.
+async fn process_batch(
+    inference_engine: Arc<AsyncMutex<InferenceEngine>>,
+    batch_scheduler: Arc<BatchScheduler>,
+    resource_monitor: Arc<ResourceMonitor>,
+    retry_policy: RetryPolicy,
+    tranquility: Arc<TranquilityControl>,
+    progress: Arc<ProgressPublisher>,
+    next_worker_client: Option<Arc<PipelineClient>>,
+    activation_dtype: ActivationDType,
+    batch_id: u64,
+    in_flight: usize,
+    batch: RequestBatch,
+) {
+    let start = std::time::Instant::now();
+    let batch_len = batch.len();
+    let total_tokens: usize =
+        batch.requests.iter().map(|r| r.new_tokens).sum::<usize>().max(1);
+    let requests = batch.requests.clone();
+    let request_token_counts: Vec<usize> = requests.iter().map(|r| r.new_tokens).collect();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let mut engine = inference_engine.blocking_lock();
+        resource_monitor.track(|| engine.forward_batch(&batch))
+    })
+    .await;
+
+    let success = match result {
+        Ok(Ok(outputs)) => {
+            let duration = start.elapsed();
+            batch_scheduler.record_forward_latency(duration / total_tokens as u32);
+
+            info!(
+                "Processed batch of {} requests in {:?} ({:.2} ms/request)",
+                batch_len,
+                duration,
+                duration.as_millis() as f64 / batch_len as f64
+            );
+
+            match next_worker_client.as_ref() {
+                Some(client) => {
+                    forward_to_next_worker(client, activation_dtype, &requests, outputs).await;
+                }
+                None => {
+                    // Last stage in the pipeline: nothing to forward, return
+                    // to client is handled elsewhere via `response_tx`
+                    for (i, output) in outputs.iter().enumerate() {
+                        trace!(
+                            "Request {} output: {} elements, first={}",
+                            i,
+                            output.len(),
+                            output.first().unwrap_or(&0.0)
+                        );
+                    }
                 }
-                tokio::time::sleep(Duration::from_millis(10)).await;
             }
+
+            true
+        }
+        Ok(Err(e)) => {
+            error!("Error processing batch: {}", e);
+            requeue_or_drop(batch_scheduler, retry_policy, requests, e);
+            false
         }
+        Err(e) => {
+            error!("Batch forward pass panicked: {}", e);
+            let panic_err = ChatLoopError::Internal(format!("Batch forward pass panicked: {}", e));
+            requeue_or_drop(batch_scheduler, retry_policy, requests, panic_err);
+            false
+        }
+    };
+
+    progress.batch_completed(batch_id, request_token_counts, start.elapsed(), success, in_flight);
+
+    let throttle = tranquility.throttle_delay(start.elapsed());
+    if !throttle.is_zero() {
+        tokio::time::sleep(throttle).await;
+    }
+}
+
+/// Forward each request's output hidden states to `next_worker_endpoint` as
+/// a [`ForwardRequest`], one per request in `requests`/`outputs` order
+///
+/// The local forward pass already succeeded by the time this is called, so
+/// a per-request forwarding failure is logged rather than fed back into
+/// `requeue_or_drop` - retrying here would redo the (expensive, already
+/// successful) local compute just because the downstream worker hiccuped.
+async fn forward_to_next_worker(
+    client: &PipelineClient,
+    dtype: ActivationDType,
+    requests: &[InferenceRequest],
+    outputs: Vec<Vec<f32>>,
+) {
+    for (request, hidden_states) in requests.iter().zip(outputs) {
+        let forward_request = ForwardRequest {
+            request_id: request.request_id.clone(),
+            sequence_id: request.sequence_id,
+            hidden_states,
+        };
+
+        if let Err(e) = client.forward_activations(forward_request, dtype).await {
+            warn!(
+                "Failed to forward activations for request {} to next worker: {}",
+                request.request_id, e
+            );
+        }
+    }
+}
+
+/// Requeue each retryable, not-yet-exhausted request from a failed batch
+/// after its own backoff delay; non-retryable or exhausted requests are
+/// dropped and told so via `response_tx`
+fn requeue_or_drop(
+    batch_scheduler: Arc<BatchScheduler>,
+    retry_policy: RetryPolicy,
+    requests: Vec<InferenceRequest>,
+    error: ChatLoopError,
+) {
+    for mut request in requests {
+        if !error.is_retryable() {
+            warn!("Request {} failed non-retryably: {}", request.request_id, error);
+            notify_failure(&request, &error);
+            continue;
+        }
+
+        if request.retry_count >= retry_policy.max_retries {
+            warn!(
+                "Request {} exhausted {} retries, dropping: {}",
+                request.request_id, retry_policy.max_retries, error
+            );
+            notify_failure(&request, &error);
+            continue;
+        }
+
+        request.retry_count += 1;
+        let delay = retry_delay(retry_policy, request.retry_count);
+        let attempt = request.retry_count;
+        let max_retries = retry_policy.max_retries;
+        let batch_scheduler = Arc::clone(&batch_scheduler);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let request_id = request.request_id.clone();
+            if let Err(e) = batch_scheduler.try_submit(request) {
+                warn!(
+                    "Failed to requeue request {} (attempt {}/{}): {}",
+                    request_id, attempt, max_retries, e
+                );
+            }
+        });
+    }
+}
+
+/// Tell a request's caller (if any is waiting via `response_tx`) that it
+/// failed for good
+fn notify_failure(request: &InferenceRequest, error: &ChatLoopError) {
+    if let Some(tx) = &request.response_tx {
+        let _ = tx.try_send(Err(ChatLoopError::Internal(format!(
+            "Request {} failed permanently: {}",
+            request.request_id, error
+        ))));
     }
 }
@@ -0,0 +1,206 @@
+//! Worker task manager with runtime introspection
+//!
+//! Every background task the worker runs (the inference loop today; future
+//! prefetch/eviction tasks later) implements [`Worker`] and is driven by
+//! [`WorkerManager::spawn`], which records its last reported [`WorkerState`]
+//! and consecutive-failure count so [`WorkerManager::snapshot`] can report
+//! whether each task is actively processing, idle for lack of work, done, or
+//! quietly failing - instead of a background task's errors only ever
+//! reaching a log line.
+
+use chatloop_common::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How long a task that returned `Err` waits before `step` is retried
+const RETRY_BACKOFF_ON_ERROR: Duration = Duration::from_millis(10);
+
+/// What a [`Worker::step`] call accomplished, driving the manager's
+/// scheduling of the next call
+pub enum WorkerState {
+    /// Did useful work this step; call `step` again immediately
+    Active,
+
+    /// Found nothing to do; wait this long before calling `step` again
+    Idle(Duration),
+
+    /// This task has permanently finished and should not be stepped again
+    Done,
+}
+
+impl WorkerState {
+    fn kind(&self) -> WorkerStateKind {
+        match self {
+            WorkerState::Active => WorkerStateKind::Active,
+            WorkerState::Idle(_) => WorkerStateKind::Idle,
+            WorkerState::Done => WorkerStateKind::Done,
+        }
+    }
+}
+
+/// Serializable projection of [`WorkerState`], without the `Idle` duration,
+/// reported by [`WorkerManager::snapshot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerStateKind {
+    Active,
+    Idle,
+    Done,
+}
+
+/// Live-operations commands a task's command channel can carry, sourced
+/// from either a shutdown signal or the admin API
+///
+/// Not every [`Worker`] needs to understand every variant - the inference
+/// loop is the only task with a command channel today - but the type lives
+/// here rather than in the binary crate so both `main` and the admin HTTP
+/// handlers (in the library crate) can share it.
+#[derive(Debug, Clone, Copy)]
+pub enum WorkerCommand {
+    /// Stop pulling new batches, but keep the task alive
+    Pause,
+
+    /// Resume pulling batches after a `Pause`
+    Resume,
+
+    /// Stop accepting new requests, finish all in-flight/queued batches,
+    /// then report `Done`
+    Drain,
+
+    /// Stop immediately, abandoning any queued (but not in-flight) work
+    Shutdown,
+
+    /// Set the tranquility throttle multiplier: after processing a batch
+    /// that took `duration`, the loop sleeps for `duration * value` before
+    /// pulling the next one. See `crate::tranquility` for full semantics.
+    SetTranquility(f64),
+}
+
+/// A background task the [`WorkerManager`] drives to completion
+///
+/// `step` is a boxed future rather than `async fn` so `Worker` stays
+/// object-safe, mirroring the coordinator's `DiscoveryBackend` trait - this
+/// crate has no async-trait dependency.
+pub trait Worker: Send {
+    /// Stable name identifying this task in [`WorkerManager::snapshot`]
+    fn name(&self) -> &str;
+
+    /// Perform one unit of work and report what happened
+    fn step<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = Result<WorkerState>> + Send + 'a>>;
+}
+
+/// Runtime record [`WorkerManager::snapshot`] reports for one task
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerTaskSnapshot {
+    pub name: String,
+    pub state: WorkerStateKind,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_step_secs_ago: f64,
+}
+
+struct TaskRecord {
+    state: WorkerStateKind,
+    consecutive_failures: u32,
+    last_error: Option<String>,
+    last_step_at: Instant,
+}
+
+impl TaskRecord {
+    fn new() -> Self {
+        Self {
+            state: WorkerStateKind::Idle,
+            consecutive_failures: 0,
+            last_error: None,
+            last_step_at: Instant::now(),
+        }
+    }
+}
+
+/// Drives registered [`Worker`] tasks and tracks their runtime state for
+/// introspection
+///
+/// Each `spawn`ed task gets its own background `tokio::task` looping `step`
+/// until it reports `Done`; the manager only holds the shared record the
+/// outcome of each step is written into, not the task itself.
+#[derive(Default)]
+pub struct WorkerManager {
+    tasks: RwLock<HashMap<String, TaskRecord>>,
+}
+
+impl WorkerManager {
+    /// Create an empty manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `worker` and drive it to completion in a background task
+    pub fn spawn(self: &Arc<Self>, mut worker: Box<dyn Worker>) -> tokio::task::JoinHandle<()> {
+        let name = worker.name().to_string();
+        self.tasks.write().unwrap().insert(name.clone(), TaskRecord::new());
+
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let outcome = worker.step().await;
+                match manager.record_outcome(&name, outcome) {
+                    Some(sleep_for) => tokio::time::sleep(sleep_for).await,
+                    None => break,
+                }
+            }
+        })
+    }
+
+    /// Apply one `step` outcome to `name`'s record, returning how long to
+    /// sleep before the next call, or `None` once the task is `Done`
+    fn record_outcome(&self, name: &str, outcome: Result<WorkerState>) -> Option<Duration> {
+        let mut tasks = self.tasks.write().unwrap();
+        let record = tasks.get_mut(name)?;
+
+        match outcome {
+            Ok(state) => {
+                record.state = state.kind();
+                record.consecutive_failures = 0;
+                record.last_error = None;
+                record.last_step_at = Instant::now();
+
+                match state {
+                    WorkerState::Active => Some(Duration::ZERO),
+                    WorkerState::Idle(wait) => Some(wait),
+                    WorkerState::Done => None,
+                }
+            }
+            Err(e) => {
+                record.consecutive_failures += 1;
+                record.last_error = Some(e.to_string());
+                record.last_step_at = Instant::now();
+                warn!(
+                    "Worker task '{}' step failed ({} consecutive failures): {}",
+                    name, record.consecutive_failures, e
+                );
+                Some(RETRY_BACKOFF_ON_ERROR)
+            }
+        }
+    }
+
+    /// Current state and error stats for every registered task
+    pub fn snapshot(&self) -> Vec<WorkerTaskSnapshot> {
+        self.tasks
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, record)| WorkerTaskSnapshot {
+                name: name.clone(),
+                state: record.state,
+                consecutive_failures: record.consecutive_failures,
+                last_error: record.last_error.clone(),
+                last_step_secs_ago: record.last_step_at.elapsed().as_secs_f64(),
+            })
+            .collect()
+    }
+}
@@ -4,13 +4,138 @@
 //! Each worker loads only its assigned layer group, minimizing memory usage.
 
 use crate::error::{ChatLoopError, Result};
-use crate::tensor::safetensors::{SafeTensorBuffer, SafeTensorRef, TensorDType};
+use crate::tensor::gguf::{GgufBuffer, GGUF_MAGIC};
+use crate::tensor::ops::{quantize_int4, quantize_int8_per_channel_symmetric, QuantizedInt4};
+use crate::tensor::safetensors::{SafeTensorBuffer, SafeTensorHeader, SafeTensorView, TensorDType};
 use chatloop_common::config::LayerGroupConfig;
 use std::collections::HashMap;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{debug, info};
 
+/// On-disk weight container format
+///
+/// Wraps whichever memory-mapped buffer the weights file turned out to be,
+/// so `ModelPartition::load` and `index_layer_group_tensors` work the same
+/// way regardless of which one is in play.
+pub enum ModelFormat {
+    /// `safetensors` container (JSON header + raw tensor bytes)
+    SafeTensors(SafeTensorBuffer),
+    /// GGUF/GGML container, used by most community-quantized checkpoints
+    Gguf(GgufBuffer),
+}
+
+impl ModelFormat {
+    /// Detect the format from the file's magic bytes and memory-map it
+    ///
+    /// GGUF files start with the 4-byte magic `GGUF`; a safetensors file's
+    /// first 8 bytes are a little-endian JSON header length instead, so
+    /// anything that doesn't match the GGUF magic is opened as safetensors.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        let mut magic = [0u8; 4];
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to open file {}: {}", path.display(), e)))?;
+        file.read_exact(&mut magic)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to read file {}: {}", path.display(), e)))?;
+
+        if magic == GGUF_MAGIC {
+            Ok(ModelFormat::Gguf(GgufBuffer::open(path)?))
+        } else {
+            Ok(ModelFormat::SafeTensors(SafeTensorBuffer::open(path)?))
+        }
+    }
+
+    /// Get the header
+    pub fn header(&self) -> &SafeTensorHeader {
+        match self {
+            ModelFormat::SafeTensors(buffer) => buffer.header(),
+            ModelFormat::Gguf(buffer) => buffer.header(),
+        }
+    }
+
+    /// Get tensor names
+    pub fn tensor_names(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        match self {
+            ModelFormat::SafeTensors(buffer) => Box::new(buffer.tensor_names()),
+            ModelFormat::Gguf(buffer) => Box::new(buffer.tensor_names()),
+        }
+    }
+
+    /// Get a zero-copy view of a tensor
+    pub fn get_tensor(&self, name: &str) -> Option<SafeTensorView<'_>> {
+        match self {
+            ModelFormat::SafeTensors(buffer) => buffer.get_tensor(name),
+            ModelFormat::Gguf(buffer) => buffer.get_tensor(name),
+        }
+    }
+}
+
+/// Positional encoding scheme used by a model's attention layers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionEncoding {
+    /// Rotary position embeddings (RoPE), used by most LLaMA-family models
+    Rotary,
+    /// Attention with Linear Biases: a fixed per-head linear penalty on
+    /// attention logits based on key/query distance, with no position
+    /// embedding added to the input at all
+    Alibi,
+}
+
+impl PositionEncoding {
+    /// Pick the position encoding scheme from a model's architecture name
+    ///
+    /// ALiBi-native architectures (BLOOM, MPT, Falcon) are detected by
+    /// name; everything else defaults to rotary, which covers the
+    /// LLaMA-family models this worker targets primarily.
+    pub fn from_architecture(architecture: &str) -> Self {
+        let arch = architecture.to_lowercase();
+
+        if arch.contains("bloom") || arch.contains("mpt") || arch.contains("falcon") {
+            PositionEncoding::Alibi
+        } else {
+            PositionEncoding::Rotary
+        }
+    }
+}
+
+/// Compute the per-head ALiBi slopes `m_h` for `num_heads` attention heads
+///
+/// Uses the geometric sequence from the ALiBi paper,
+/// `m_h = 2^(-8*(h+1)/num_heads)` for `h` in `0..num_heads`. When
+/// `num_heads` isn't a power of two, slopes are generated for the closest
+/// power of two below it, then the remaining heads interpolate by taking
+/// every other slope from a doubled-resolution sequence — the standard
+/// fallback from the reference implementation.
+pub fn alibi_slopes(num_heads: usize) -> Vec<f32> {
+    fn slopes_for_power_of_two(n: usize) -> Vec<f32> {
+        (0..n)
+            .map(|h| 2f32.powf(-8.0 * (h as f32 + 1.0) / n as f32))
+            .collect()
+    }
+
+    if num_heads == 0 {
+        return Vec::new();
+    }
+
+    if num_heads.is_power_of_two() {
+        return slopes_for_power_of_two(num_heads);
+    }
+
+    let closest_pow2 = num_heads.next_power_of_two() / 2;
+    let base = slopes_for_power_of_two(closest_pow2);
+
+    let extra_needed = num_heads - closest_pow2;
+    let extra = slopes_for_power_of_two(2 * closest_pow2)
+        .into_iter()
+        .step_by(2)
+        .take(extra_needed);
+
+    base.into_iter().chain(extra).collect()
+}
+
 /// Model partition containing a layer group
 ///
 /// This struct manages the weights for a specific layer group,
@@ -20,7 +145,7 @@ pub struct ModelPartition {
     pub config: LayerGroupConfig,
 
     /// Memory-mapped tensor buffer
-    tensor_buffer: SafeTensorRef,
+    tensor_buffer: Arc<ModelFormat>,
 
     /// Cached tensor views for this layer group
     tensors: HashMap<String, TensorCache>,
@@ -30,6 +155,46 @@ pub struct ModelPartition {
 
     /// Memory usage in bytes
     memory_usage_bytes: usize,
+
+    /// sha256 digest of the weights file, used for peer version negotiation
+    weights_digest: String,
+
+    /// Positional encoding scheme this model's attention layers expect
+    position_encoding: PositionEncoding,
+}
+
+/// Tensor-parallel shard assignment for a single worker
+///
+/// `LayerGroupConfig` splits the model by contiguous layer ranges (pipeline
+/// parallelism); `ShardSpec` is the orthogonal split within a single
+/// layer's weight matrices across `world_size` workers, each holding rank
+/// `rank`'s `1/world_size` slice along `dim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardSpec {
+    /// This worker's index among the tensor-parallel group
+    pub rank: usize,
+
+    /// Number of workers sharing each layer's weight matrices
+    pub world_size: usize,
+
+    /// Dimension to shard along: `0` for column-sharding (output rows),
+    /// `1` for row-sharding along the contraction dimension
+    pub dim: usize,
+}
+
+impl ShardSpec {
+    /// Compute this rank's `(start, len)` half-open range over `dim_size`
+    /// elements, distributing any remainder across the lowest-numbered
+    /// ranks so every element is covered exactly once.
+    pub fn range(&self, dim_size: usize) -> (usize, usize) {
+        let base = dim_size / self.world_size;
+        let remainder = dim_size % self.world_size;
+
+        let start = self.rank * base + remainder.min(self.rank);
+        let len = base + if self.rank < remainder { 1 } else { 0 };
+
+        (start, len)
+    }
 }
 
 /// Cached tensor with metadata
@@ -59,6 +224,7 @@ impl ModelPartition {
     pub fn load<P: AsRef<Path>>(
         weights_path: P,
         config: LayerGroupConfig,
+        position_encoding: PositionEncoding,
     ) -> Result<Self> {
         let weights_path = weights_path.as_ref();
         info!(
@@ -66,8 +232,8 @@ impl ModelPartition {
             config.start_layer, config.end_layer, weights_path.display()
         );
 
-        // Memory-map the weights file
-        let buffer = SafeTensorBuffer::open(weights_path)
+        // Memory-map the weights file, detecting safetensors vs. GGUF from its magic
+        let buffer = ModelFormat::open(weights_path)
             .map_err(|e| ChatLoopError::model(format!("Failed to load weights: {}", e)))?;
 
         // Determine model dtype from first tensor
@@ -89,10 +255,13 @@ impl ModelPartition {
             .map(|m| m.len() as usize)
             .unwrap_or(0);
 
+        let weights_digest = Self::hash_weights(&buffer)?;
+
         info!(
-            "Model partition loaded: {} tensors, {} MB",
+            "Model partition loaded: {} tensors, {} MB, digest={}",
             tensors.len(),
-            memory_usage_bytes / (1024 * 1024)
+            memory_usage_bytes / (1024 * 1024),
+            weights_digest
         );
 
         Ok(Self {
@@ -101,12 +270,46 @@ impl ModelPartition {
             tensors,
             dtype,
             memory_usage_bytes,
+            weights_digest,
+            position_encoding,
         })
     }
 
+    /// Positional encoding scheme this model's attention layers expect
+    pub fn position_encoding(&self) -> PositionEncoding {
+        self.position_encoding
+    }
+
+    /// Compute a sha256 digest of the memory-mapped weights
+    ///
+    /// Used for worker version negotiation: two workers are only considered
+    /// compatible if this digest matches exactly.
+    fn hash_weights(buffer: &ModelFormat) -> Result<String> {
+        use sha2::{Digest, Sha256};
+
+        // Tensor names come out of a HashMap in arbitrary order; sort them so
+        // two processes loading the same file always derive the same digest.
+        let mut names: Vec<&String> = buffer.tensor_names().collect();
+        names.sort();
+
+        let mut hasher = Sha256::new();
+        for name in names {
+            if let Some(view) = buffer.get_tensor(name) {
+                hasher.update(view.data());
+            }
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// sha256 digest of the loaded weights, for worker version negotiation
+    pub fn weights_digest(&self) -> &str {
+        &self.weights_digest
+    }
+
     /// Index all tensors belonging to this layer group
     fn index_layer_group_tensors(
-        buffer: &SafeTensorBuffer,
+        buffer: &ModelFormat,
         config: &LayerGroupConfig,
     ) -> Result<HashMap<String, TensorCache>> {
         let mut tensors = HashMap::new();
@@ -120,14 +323,32 @@ impl ModelPartition {
                     if layer_idx >= config.start_layer && layer_idx < config.end_layer {
                         debug!("Indexing tensor: {}", name);
 
+                        // Affine int8 quantization params, if present, live in
+                        // the SafeTensor `__metadata__` block under
+                        // per-tensor keys rather than in the tensor entry
+                        // itself.
+                        let scale = buffer
+                            .header()
+                            .metadata
+                            .get(&format!("{}.scale", name))
+                            .and_then(|v| v.parse::<f32>().ok());
+                        let zero_point = buffer
+                            .header()
+                            .metadata
+                            .get(&format!("{}.zero_point", name))
+                            .and_then(|v| v.parse::<i32>().ok());
+
                         tensors.insert(
                             name.clone(),
                             TensorCache {
                                 name: name.clone(),
                                 shape: info.shape.clone(),
-                                quantized: matches!(info.get_dtype(), Some(TensorDType::I8) | Some(TensorDType::I4)),
-                                scale: None,  // TODO: Extract from metadata
-                                zero_point: None,
+                                quantized: matches!(
+                                    info.get_dtype(),
+                                    Some(TensorDType::I8) | Some(TensorDType::Q4_0) | Some(TensorDType::Q8_0)
+                                ),
+                                scale,
+                                zero_point,
                             },
                         );
                     }
@@ -138,30 +359,65 @@ impl ModelPartition {
         Ok(tensors)
     }
 
-    /// Get a tensor view by name
-    ///
-    /// Returns a zero-copy view into the memory-mapped data.
+    /// Get a tensor view by name, dequantizing if necessary
     pub fn get_tensor(&self, name: &str) -> Option<Vec<f32>> {
+        let mut out = Vec::new();
+        self.get_tensor_into(name, &mut out)?;
+        Some(out)
+    }
+
+    /// Get a tensor by name, dequantizing directly into a caller-provided buffer
+    ///
+    /// Equivalent to [`ModelPartition::get_tensor`] but lets a caller reuse
+    /// scratch space (e.g. a per-layer projection buffer) across calls
+    /// instead of allocating a fresh `Vec` for every big weight matrix.
+    pub fn get_tensor_into(&self, name: &str, out: &mut Vec<f32>) -> Option<()> {
         let view = self.tensor_buffer.get_tensor(name)?;
+        let num_elements = view.len();
 
-        // Convert to f32 based on dtype
-        let data = match view.dtype() {
-            TensorDType::F32 => unsafe { view.as_f32_slice().to_vec() },
-            TensorDType::F16 => unsafe {
-                view.as_f16_slice()
-                    .iter()
-                    .map(|x| x.to_f32())
-                    .collect()
-            },
+        match view.dtype() {
+            TensorDType::F32 => {
+                out.clear();
+                out.extend_from_slice(unsafe { view.as_f32_slice() });
+            }
+            TensorDType::F16 => {
+                out.clear();
+                out.extend(unsafe { view.as_f16_slice() }.iter().map(|x| x.to_f32()));
+            }
+            TensorDType::Q4_0 => dequantize_q4_0_into(view.data(), num_elements, out),
+            TensorDType::Q8_0 => dequantize_q8_0_into(view.data(), num_elements, out),
             TensorDType::I8 => {
-                // Dequantize if needed
-                // For now, convert directly (assumes weights are pre-scaled)
-                unimplemented!("Int8 weight loading not yet implemented")
+                // Per-tensor affine quantization params come from the
+                // SafeTensor `__metadata__` block (see
+                // `index_layer_group_tensors`); default to an identity
+                // affine transform if a tensor wasn't given explicit params.
+                let cache = self.tensors.get(name);
+                let scale = cache.and_then(|c| c.scale).unwrap_or(1.0);
+                let zero_point = cache.and_then(|c| c.zero_point).unwrap_or(0);
+
+                dequantize_i8_affine_into(view.data(), scale, zero_point, out);
             }
             _ => return None,
-        };
+        }
 
-        Some(data)
+        Some(())
+    }
+
+    /// Get a tensor by name, sharded along one dimension for tensor parallelism
+    ///
+    /// Slices the memory-mapped view down to this rank's `1/world_size`
+    /// share *before* dequantizing (see [`SafeTensorView::sub_view`]), so
+    /// the full weight matrix is never materialized on this worker — only
+    /// its shard is. Block-quantized (`Q4_0`/`Q8_0`) weights aren't
+    /// supported here, since a quantization block can span multiple
+    /// logical elements; for those, fall back to `get_tensor` and shard
+    /// the dequantized result.
+    pub fn get_tensor_sharded(&self, name: &str, shard: ShardSpec) -> Option<Vec<f32>> {
+        let view = self.tensor_buffer.get_tensor(name)?;
+        let dim_size = *view.shape().get(shard.dim)?;
+        let (start, len) = shard.range(dim_size);
+
+        view.sub_view(shard.dim, start, len)?.to_f32_vec()
     }
 
     /// Get multiple tensors at once (more efficient)
@@ -211,39 +467,60 @@ impl ModelPartition {
     }
 
     /// Get attention weights for a specific layer
-    pub fn get_attention_weights(&self, layer_idx: usize) -> Option<AttentionWeights> {
+    ///
+    /// With `shard` set, `q_proj`/`k_proj`/`v_proj` are column-sharded
+    /// (each rank gets output rows `shard.range(out_features)`) and
+    /// `o_proj` is row-sharded along its contraction dimension instead, so
+    /// a rank's partial attention output can be summed with the other
+    /// ranks' without any further slicing. `shard: None` loads each matrix
+    /// whole, as before.
+    pub fn get_attention_weights(&self, layer_idx: usize, shard: Option<ShardSpec>) -> Option<AttentionWeights> {
         if layer_idx < self.config.start_layer || layer_idx >= self.config.end_layer {
             return None;
         }
 
         let prefix = format!("model.layers.{}", layer_idx);
+        let col = shard.map(|s| ShardSpec { dim: 0, ..s });
+        let row = shard.map(|s| ShardSpec { dim: 1, ..s });
 
-        // Query projection weight
-        let q_proj = self.get_tensor(&format!("{}.attention.wq.weight", prefix))?;
-        let k_proj = self.get_tensor(&format!("{}.attention.wk.weight", prefix))?;
-        let v_proj = self.get_tensor(&format!("{}.attention.wv.weight", prefix))?;
-        let o_proj = self.get_tensor(&format!("{}.attention.wo.weight", prefix))?;
+        let q_proj = self.get_weight(&format!("{}.attention.wq.weight", prefix), col)?;
+        let k_proj = self.get_weight(&format!("{}.attention.wk.weight", prefix), col)?;
+        let v_proj = self.get_weight(&format!("{}.attention.wv.weight", prefix), col)?;
+        let o_proj = self.get_weight(&format!("{}.attention.wo.weight", prefix), row)?;
+
+        let alibi_slopes = match self.position_encoding {
+            PositionEncoding::Alibi => Some(alibi_slopes(self.config.num_heads)),
+            PositionEncoding::Rotary => None,
+        };
 
         Some(AttentionWeights {
             q_proj,
             k_proj,
             v_proj,
             o_proj,
+            alibi_slopes,
         })
     }
 
     /// Get MLP weights for a specific layer
-    pub fn get_mlp_weights(&self, layer_idx: usize) -> Option<MlpWeights> {
+    ///
+    /// With `shard` set, `gate_proj`/`up_proj` are column-sharded and
+    /// `down_proj` is row-sharded along its contraction dimension, mirroring
+    /// [`ModelPartition::get_attention_weights`]. `shard: None` loads each
+    /// matrix whole, as before.
+    pub fn get_mlp_weights(&self, layer_idx: usize, shard: Option<ShardSpec>) -> Option<MlpWeights> {
         if layer_idx < self.config.start_layer || layer_idx >= self.config.end_layer {
             return None;
         }
 
         let prefix = format!("model.layers.{}", layer_idx);
+        let col = shard.map(|s| ShardSpec { dim: 0, ..s });
+        let row = shard.map(|s| ShardSpec { dim: 1, ..s });
 
         // Gate and up projections (for SwiGLU)
-        let gate_proj = self.get_tensor(&format!("{}.feed_forward.gate_proj.weight", prefix))?;
-        let up_proj = self.get_tensor(&format!("{}.feed_forward.up_proj.weight", prefix))?;
-        let down_proj = self.get_tensor(&format!("{}.feed_forward.down_proj.weight", prefix))?;
+        let gate_proj = self.get_weight(&format!("{}.feed_forward.gate_proj.weight", prefix), col)?;
+        let up_proj = self.get_weight(&format!("{}.feed_forward.up_proj.weight", prefix), col)?;
+        let down_proj = self.get_weight(&format!("{}.feed_forward.down_proj.weight", prefix), row)?;
 
         Some(MlpWeights {
             gate_proj,
@@ -252,6 +529,14 @@ impl ModelPartition {
         })
     }
 
+    /// Get a weight matrix, sharded if `shard` is set and whole otherwise
+    fn get_weight(&self, name: &str, shard: Option<ShardSpec>) -> Option<Vec<f32>> {
+        match shard {
+            Some(shard) => self.get_tensor_sharded(name, shard),
+            None => self.get_tensor(name),
+        }
+    }
+
     /// Get layer norm weights
     pub fn get_layer_norm(&self, layer_idx: usize) -> Option<LayerNormWeights> {
         if layer_idx < self.config.start_layer || layer_idx >= self.config.end_layer {
@@ -284,6 +569,12 @@ pub struct AttentionWeights {
 
     /// Output projection weights
     pub o_proj: Vec<f32>,
+
+    /// Per-head ALiBi slopes (`m_h`), present only when this model uses
+    /// ALiBi instead of rotary position embeddings. The attention kernel
+    /// adds `-slopes[h] * (query_pos - key_pos)` to head `h`'s logits
+    /// before softmax.
+    pub alibi_slopes: Option<Vec<f32>>,
 }
 
 /// MLP/FFN weights for a single layer
@@ -299,6 +590,154 @@ pub struct MlpWeights {
     pub down_proj: Vec<f32>,
 }
 
+/// A row-major `(out_dim, in_dim)` weight matrix quantized to `i8` with one
+/// `f32` scale per output row: `scale = max(|w|) / 127`,
+/// `q = round(w / scale)` clamped to `[-127, 127]`. Used in place of
+/// [`AttentionWeights`]/[`MlpWeights`]'s plain `Vec<f32>` matrices when
+/// `QuantMode::Int8` shrinks a worker's resident partition and speeds up the
+/// projection matmuls in `mlp`/`self_attention`.
+#[derive(Debug, Clone)]
+pub struct QuantizedTensor {
+    /// `i8` codes, row-major `(out_dim, in_dim)`
+    pub data: Vec<i8>,
+
+    /// `data` transposed to row-major `(in_dim, out_dim)`, precomputed once
+    /// at quantization time so [`crate::tensor::matmul_int8_per_channel`]
+    /// can consume it directly as its `b` operand without transposing on
+    /// every projection
+    pub data_t: Vec<i8>,
+
+    /// Per-output-row scale, one entry per `out_dim`
+    pub scales: Vec<f32>,
+
+    /// Number of output rows
+    pub out_dim: usize,
+
+    /// Number of input columns
+    pub in_dim: usize,
+}
+
+impl QuantizedTensor {
+    /// Quantize a row-major `(out_dim, in_dim)` f32 weight matrix
+    pub fn quantize(weight: &[f32], out_dim: usize, in_dim: usize) -> Self {
+        let (data, scales) = quantize_int8_per_channel_symmetric(weight, &[out_dim, in_dim], 0);
+
+        let mut data_t = vec![0i8; data.len()];
+        for o in 0..out_dim {
+            for i in 0..in_dim {
+                data_t[i * out_dim + o] = data[o * in_dim + i];
+            }
+        }
+
+        Self { data, data_t, scales, out_dim, in_dim }
+    }
+}
+
+/// Int8 counterpart of [`AttentionWeights`], one [`QuantizedTensor`] per
+/// projection
+#[derive(Debug, Clone)]
+pub struct QuantizedAttentionWeights {
+    pub q_proj: QuantizedTensor,
+    pub k_proj: QuantizedTensor,
+    pub v_proj: QuantizedTensor,
+    pub o_proj: QuantizedTensor,
+}
+
+impl QuantizedAttentionWeights {
+    /// Quantize `weights`' four projections. Each projection's `(out_dim,
+    /// in_dim)` must be supplied explicitly since a flat `Vec<f32>` doesn't
+    /// carry its own shape: q_proj/k_proj/v_proj are `(q_dim or kv_dim,
+    /// hidden_dim)` and o_proj is `(hidden_dim, q_dim)`.
+    pub fn quantize(
+        weights: &AttentionWeights,
+        hidden_dim: usize,
+        q_dim: usize,
+        kv_dim: usize,
+    ) -> Self {
+        Self {
+            q_proj: QuantizedTensor::quantize(&weights.q_proj, q_dim, hidden_dim),
+            k_proj: QuantizedTensor::quantize(&weights.k_proj, kv_dim, hidden_dim),
+            v_proj: QuantizedTensor::quantize(&weights.v_proj, kv_dim, hidden_dim),
+            o_proj: QuantizedTensor::quantize(&weights.o_proj, hidden_dim, q_dim),
+        }
+    }
+}
+
+/// Int8 counterpart of [`MlpWeights`], one [`QuantizedTensor`] per projection
+#[derive(Debug, Clone)]
+pub struct QuantizedMlpWeights {
+    pub gate_proj: QuantizedTensor,
+    pub up_proj: QuantizedTensor,
+    pub down_proj: QuantizedTensor,
+}
+
+impl QuantizedMlpWeights {
+    /// Quantize `weights`' three projections: gate_proj/up_proj are
+    /// `(intermediate_dim, hidden_dim)` and down_proj is `(hidden_dim,
+    /// intermediate_dim)`.
+    pub fn quantize(weights: &MlpWeights, hidden_dim: usize, intermediate_dim: usize) -> Self {
+        Self {
+            gate_proj: QuantizedTensor::quantize(&weights.gate_proj, intermediate_dim, hidden_dim),
+            up_proj: QuantizedTensor::quantize(&weights.up_proj, intermediate_dim, hidden_dim),
+            down_proj: QuantizedTensor::quantize(&weights.down_proj, hidden_dim, intermediate_dim),
+        }
+    }
+}
+
+/// Group size `quantize_int4` splits each weight row into; see that
+/// function's doc comment for the scale/zero-point scheme this implies.
+const INT4_GROUP_SIZE: usize = 64;
+
+/// Int4 counterpart of [`AttentionWeights`], one [`QuantizedInt4`] per
+/// projection. Each projection keeps its [`QuantizedTensor`]-style `(out_dim,
+/// in_dim)` shape, since [`crate::tensor::matmul_int4`] expects that same
+/// `(n, k)` layout directly.
+#[derive(Debug, Clone)]
+pub struct QuantizedInt4AttentionWeights {
+    pub q_proj: QuantizedInt4,
+    pub k_proj: QuantizedInt4,
+    pub v_proj: QuantizedInt4,
+    pub o_proj: QuantizedInt4,
+}
+
+impl QuantizedInt4AttentionWeights {
+    /// Quantize `weights`' four projections; see
+    /// [`QuantizedAttentionWeights::quantize`] for each projection's shape.
+    pub fn quantize(
+        weights: &AttentionWeights,
+        hidden_dim: usize,
+        q_dim: usize,
+        kv_dim: usize,
+    ) -> Self {
+        Self {
+            q_proj: quantize_int4(&weights.q_proj, vec![q_dim, hidden_dim], INT4_GROUP_SIZE),
+            k_proj: quantize_int4(&weights.k_proj, vec![kv_dim, hidden_dim], INT4_GROUP_SIZE),
+            v_proj: quantize_int4(&weights.v_proj, vec![kv_dim, hidden_dim], INT4_GROUP_SIZE),
+            o_proj: quantize_int4(&weights.o_proj, vec![hidden_dim, q_dim], INT4_GROUP_SIZE),
+        }
+    }
+}
+
+/// Int4 counterpart of [`MlpWeights`], one [`QuantizedInt4`] per projection
+#[derive(Debug, Clone)]
+pub struct QuantizedInt4MlpWeights {
+    pub gate_proj: QuantizedInt4,
+    pub up_proj: QuantizedInt4,
+    pub down_proj: QuantizedInt4,
+}
+
+impl QuantizedInt4MlpWeights {
+    /// Quantize `weights`' three projections; see
+    /// [`QuantizedMlpWeights::quantize`] for each projection's shape.
+    pub fn quantize(weights: &MlpWeights, hidden_dim: usize, intermediate_dim: usize) -> Self {
+        Self {
+            gate_proj: quantize_int4(&weights.gate_proj, vec![intermediate_dim, hidden_dim], INT4_GROUP_SIZE),
+            up_proj: quantize_int4(&weights.up_proj, vec![intermediate_dim, hidden_dim], INT4_GROUP_SIZE),
+            down_proj: quantize_int4(&weights.down_proj, vec![hidden_dim, intermediate_dim], INT4_GROUP_SIZE),
+        }
+    }
+}
+
 /// Layer normalization weights
 #[derive(Debug, Clone)]
 pub struct LayerNormWeights {
@@ -309,133 +748,500 @@ pub struct LayerNormWeights {
     pub ffn_norm: Vec<f32>,
 }
 
-/// KV cache for a single sequence
+/// Number of token positions held by one physical KV cache block
 ///
-/// This stores cached keys and values for efficient autoregressive generation.
+/// Chosen to match typical paged-attention implementations: large enough
+/// to amortize per-block bookkeeping, small enough that a partially-filled
+/// tail block doesn't waste much memory.
+pub const KV_BLOCK_SIZE: usize = 16;
+
+/// One physical block of cached keys/values, covering [`KV_BLOCK_SIZE`]
+/// token positions across every layer
+///
+/// Laid out as `[num_layers, KV_BLOCK_SIZE, num_heads * head_dim]` — the
+/// same per-position granularity the original monolithic `KVCache` used,
+/// just chunked into fixed-size pages.
 #[derive(Debug, Clone)]
-pub struct KVCache {
-    /// Cached keys: [num_layers, num_heads, seq_len, head_dim]
-    pub keys: Vec<Vec<f32>>,
+struct KVBlock {
+    keys: Vec<f32>,
+    values: Vec<f32>,
+
+    /// Number of sequences referencing this block. A write to a block with
+    /// `refcount > 1` must copy it into a fresh block first.
+    refcount: usize,
+}
+
+impl KVBlock {
+    fn new(num_layers: usize, num_heads: usize, head_dim: usize) -> Self {
+        let size = num_layers * KV_BLOCK_SIZE * num_heads * head_dim;
+
+        Self {
+            keys: vec![0.0; size],
+            values: vec![0.0; size],
+            refcount: 1,
+        }
+    }
+}
+
+/// Pool of physical KV cache blocks shared by every sequence on this worker
+///
+/// Blocks are handed out from a free list where possible, so many
+/// concurrent (and prefix-sharing, via [`KVCache::fork`]) sequences draw
+/// from one pool instead of each pre-allocating
+/// `num_layers * num_heads * max_len * head_dim` floats up front.
+pub struct KVCacheAllocator {
+    blocks: Vec<KVBlock>,
+    free: Vec<usize>,
+    num_layers: usize,
+    num_heads: usize,
+    head_dim: usize,
+}
+
+impl KVCacheAllocator {
+    /// Create an empty pool; physical blocks are allocated lazily on demand
+    pub fn new(num_layers: usize, num_heads: usize, head_dim: usize) -> Self {
+        Self {
+            blocks: Vec::new(),
+            free: Vec::new(),
+            num_layers,
+            num_heads,
+            head_dim,
+        }
+    }
+
+    /// Number of physical blocks currently allocated (free and in use)
+    pub fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Number of physical blocks currently on the free list
+    pub fn num_free_blocks(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Hand out a block with a refcount of 1, reusing a freed one if possible
+    fn alloc_block(&mut self) -> usize {
+        if let Some(id) = self.free.pop() {
+            let block = &mut self.blocks[id];
+            block.keys.fill(0.0);
+            block.values.fill(0.0);
+            block.refcount = 1;
+            id
+        } else {
+            self.blocks
+                .push(KVBlock::new(self.num_layers, self.num_heads, self.head_dim));
+            self.blocks.len() - 1
+        }
+    }
 
-    /// Cached values: [num_layers, num_heads, seq_len, head_dim]
-    pub values: Vec<Vec<f32>>,
+    /// Record that another sequence now shares `block_id`, e.g. for a common prompt prefix
+    fn share_block(&mut self, block_id: usize) {
+        self.blocks[block_id].refcount += 1;
+    }
 
-    /// Current sequence length
-    pub seq_len: usize,
+    /// Release one sequence's reference to `block_id`, freeing it once unreferenced
+    fn release_block(&mut self, block_id: usize) {
+        let block = &mut self.blocks[block_id];
+        block.refcount -= 1;
+        if block.refcount == 0 {
+            self.free.push(block_id);
+        }
+    }
 
-    /// Maximum sequence length
-    pub max_len: usize,
+    /// Ensure `block_id` is exclusively owned by the caller, copy-on-write
+    /// cloning it into a fresh block first if it's still shared
+    fn make_unique(&mut self, block_id: usize) -> usize {
+        if self.blocks[block_id].refcount == 1 {
+            return block_id;
+        }
 
-    /// Number of layers
-    pub num_layers: usize,
+        let new_id = self.alloc_block();
+        let (keys, values) = (self.blocks[block_id].keys.clone(), self.blocks[block_id].values.clone());
+        self.blocks[new_id].keys = keys;
+        self.blocks[new_id].values = values;
+        self.release_block(block_id);
 
-    /// Number of attention heads
-    pub num_heads: usize,
+        new_id
+    }
+}
 
-    /// Head dimension
-    pub head_dim: usize,
+/// Paged KV cache for a single sequence
+///
+/// Keys and values live in fixed-size physical blocks owned by a shared
+/// [`KVCacheAllocator`]; `block_table` maps this sequence's logical block
+/// index to a physical block id. Sharing a prompt prefix across sequences
+/// is just two block tables pointing at the same physical block ids (see
+/// [`KVCache::fork`]) with the allocator's refcounts keeping them alive
+/// until every sequence either writes past the shared part (triggering
+/// copy-on-write in [`KVCache::append`]) or releases its cache entirely.
+#[derive(Debug, Clone)]
+pub struct KVCache {
+    block_table: Vec<usize>,
+    seq_len: usize,
+    num_layers: usize,
+    num_heads: usize,
+    head_dim: usize,
 }
 
 impl KVCache {
-    /// Create a new KV cache
-    pub fn new(num_layers: usize, num_heads: usize, head_dim: usize, max_len: usize) -> Self {
-        let total_size = num_layers * num_heads * max_len * head_dim;
-
+    /// Create a new, empty KV cache for a sequence
+    ///
+    /// No physical blocks are allocated until the first `append`.
+    pub fn new(num_layers: usize, num_heads: usize, head_dim: usize) -> Self {
         Self {
-            keys: vec![vec![0.0; total_size],
-            values: vec![vec![0.0; total_size],
+            block_table: Vec::new(),
             seq_len: 0,
-            max_len,
             num_layers,
             num_heads,
             head_dim,
         }
     }
 
-    /// Append a new key-value pair
-    pub fn append(&mut self, layer_idx: usize, keys: &[f32], values: &[f32]) -> Result<()> {
+    /// Current sequence length (number of positions appended)
+    pub fn seq_len(&self) -> usize {
+        self.seq_len
+    }
+
+    /// Fork a copy-on-write clone of this cache sharing its physical blocks
+    ///
+    /// Used when a new sequence continues from a common prompt prefix: the
+    /// fork starts out pointing at exactly the same blocks as `self`, and
+    /// only diverges once one of them appends past the shared prefix.
+    pub fn fork(&self, allocator: &mut KVCacheAllocator) -> Self {
+        for &block_id in &self.block_table {
+            allocator.share_block(block_id);
+        }
+
+        Self {
+            block_table: self.block_table.clone(),
+            seq_len: self.seq_len,
+            num_layers: self.num_layers,
+            num_heads: self.num_heads,
+            head_dim: self.head_dim,
+        }
+    }
+
+    /// Release this sequence's block references back to the allocator
+    ///
+    /// Call once a sequence is done generating, so prefix blocks shared
+    /// with other sequences (via `fork`) can be freed once every sequence
+    /// referencing them has released.
+    pub fn reset(&mut self, allocator: &mut KVCacheAllocator) {
+        for &block_id in &self.block_table {
+            allocator.release_block(block_id);
+        }
+
+        self.block_table.clear();
+        self.seq_len = 0;
+    }
+
+    /// Append a new key-value pair for `layer_idx` at the next (not yet
+    /// committed) position
+    ///
+    /// A transformer forward pass calls this once per layer for the same
+    /// position before the position is committed - the write location is
+    /// keyed off `seq_len`, which `append` itself never advances. Call
+    /// [`KVCache::advance`] once every layer has written its K/V for a
+    /// position, or this position's entries get silently overwritten by
+    /// the next one.
+    pub fn append(
+        &mut self,
+        allocator: &mut KVCacheAllocator,
+        layer_idx: usize,
+        keys: &[f32],
+        values: &[f32],
+    ) -> Result<()> {
         if layer_idx >= self.num_layers {
             return Err(ChatLoopError::tensor("Layer index out of bounds"));
         }
 
-        if self.seq_len >= self.max_len {
-            return Err(ChatLoopError::tensor("KV cache full"));
+        let logical_block = self.seq_len / KV_BLOCK_SIZE;
+        let pos_in_block = self.seq_len % KV_BLOCK_SIZE;
+
+        // Grab a new block from the pool only when the current one fills
+        // (or this is the very first append)
+        if logical_block == self.block_table.len() {
+            self.block_table.push(allocator.alloc_block());
         }
 
-        let offset = layer_idx * self.num_heads * self.max_len * self.head_dim
-            + self.seq_len * self.head_dim;
+        // Copy-on-write: a block still shared with another sequence (e.g.
+        // via `fork`) must be cloned before we mutate it
+        let block_id = allocator.make_unique(self.block_table[logical_block]);
+        self.block_table[logical_block] = block_id;
 
-        // Copy keys and values
-        let key_start = offset;
-        let key_end = key_start + keys.len();
-        self.keys[key_start..key_end].copy_from_slice(keys);
+        let offset = layer_idx * KV_BLOCK_SIZE * self.num_heads * self.head_dim
+            + pos_in_block * self.num_heads * self.head_dim;
 
-        let val_start = offset;
-        let val_end = val_start + values.len();
-        self.values[val_start..val_end].copy_from_slice(values);
+        let block = &mut allocator.blocks[block_id];
 
-        self.seq_len += 1;
+        let key_end = offset + keys.len();
+        block.keys[offset..key_end].copy_from_slice(keys);
+
+        let val_end = offset + values.len();
+        block.values[offset..val_end].copy_from_slice(values);
 
         Ok(())
     }
 
-    /// Get keys for a specific layer and position
-    pub fn get_keys(&self, layer_idx: usize, pos: usize) -> Option<&[f32]> {
-        if pos >= self.seq_len {
-            return None;
-        }
+    /// Commit `count` positions appended since the last `advance`, making
+    /// them visible to `get_keys`/`get_values`/`positions`
+    ///
+    /// Split out from `append` because a forward pass appends once per
+    /// layer for the same position(s) - advancing `seq_len` only after
+    /// every layer is done keeps every layer's `append` call computing the
+    /// same block/offset for a given position instead of racing ahead a
+    /// layer at a time.
+    pub fn advance(&mut self, count: usize) {
+        self.seq_len += count;
+    }
 
-        let offset = layer_idx * self.num_heads * self.max_len * self.head_dim + pos * self.head_dim;
-        let end = offset + self.num_heads * self.head_dim;
+    /// Logical positions of every cached entry, in append order
+    ///
+    /// Paired 1:1 with what `get_keys`/`get_values` return for each index
+    /// in `0..seq_len`. ALiBi needs a cached entry's absolute position to
+    /// compute the `query_pos - key_pos` bias term during autoregressive
+    /// decode, where the query is always the single newest position.
+    pub fn positions(&self) -> std::ops::Range<usize> {
+        0..self.seq_len
+    }
+
+    /// Get cached keys for `layer_idx` at position `pos`
+    pub fn get_keys<'a>(&self, allocator: &'a KVCacheAllocator, layer_idx: usize, pos: usize) -> Option<&'a [f32]> {
+        self.position_slice(allocator, layer_idx, pos, true)
+    }
 
-        Some(&self.keys[offset..end])
+    /// Get cached values for `layer_idx` at position `pos`
+    pub fn get_values<'a>(&self, allocator: &'a KVCacheAllocator, layer_idx: usize, pos: usize) -> Option<&'a [f32]> {
+        self.position_slice(allocator, layer_idx, pos, false)
     }
 
-    /// Get values for a specific layer and position
-    pub fn get_values(&self, layer_idx: usize, pos: usize) -> Option<&[f32]> {
+    fn position_slice<'a>(
+        &self,
+        allocator: &'a KVCacheAllocator,
+        layer_idx: usize,
+        pos: usize,
+        is_keys: bool,
+    ) -> Option<&'a [f32]> {
         if pos >= self.seq_len {
             return None;
         }
 
-        let offset = layer_idx * self.num_heads * self.max_len * self.head_dim + pos * self.head_dim;
+        let logical_block = pos / KV_BLOCK_SIZE;
+        let pos_in_block = pos % KV_BLOCK_SIZE;
+        let block_id = *self.block_table.get(logical_block)?;
+        let block = &allocator.blocks[block_id];
+
+        let offset = layer_idx * KV_BLOCK_SIZE * self.num_heads * self.head_dim
+            + pos_in_block * self.num_heads * self.head_dim;
         let end = offset + self.num_heads * self.head_dim;
 
-        Some(&self.values[offset..end])
+        Some(if is_keys { &block.keys[offset..end] } else { &block.values[offset..end] })
     }
+}
 
-    /// Reset the cache
-    pub fn reset(&mut self) {
-        self.seq_len = 0;
-        self.keys.fill(0.0);
-        self.values.fill(0.0);
+/// Number of elements per quantization block, shared by Q4_0 and Q8_0
+const QBLOCK_ELEMS: usize = 32;
+
+/// Dequantize Q8_0-packed bytes into `out`
+///
+/// Each block is `[f16 scale d][32 x i8 q]` with `x_i = q_i * d`. The last
+/// block may be partial if `num_elements` isn't a multiple of 32.
+fn dequantize_q8_0_into(raw: &[u8], num_elements: usize, out: &mut Vec<f32>) {
+    let (block_elems, block_bytes) = TensorDType::Q8_0.block_layout().unwrap();
+
+    out.clear();
+    out.reserve(num_elements);
+
+    let mut remaining = num_elements;
+    for block in raw.chunks(block_bytes) {
+        if remaining == 0 {
+            break;
+        }
+
+        let scale = half::f16::from_bits(u16::from_le_bytes([block[0], block[1]])).to_f32();
+        let take = remaining.min(block_elems);
+
+        for &byte in &block[2..2 + take] {
+            out.push(byte as i8 as f32 * scale);
+        }
+
+        remaining -= take;
     }
+}
 
-    /// Resize the cache (clears data)
-    pub fn resize(&mut self, new_max_len: usize) {
-        let total_size = self.num_layers * self.num_heads * new_max_len * self.head_dim;
+/// Dequantize Q4_0-packed bytes into `out`
+///
+/// Each block is `[f16 scale d][16 bytes]`, two 4-bit codes per byte (low
+/// nibble first), with `x_i = (nibble_i - 8) * d`. The last block may be
+/// partial if `num_elements` isn't a multiple of 32.
+fn dequantize_q4_0_into(raw: &[u8], num_elements: usize, out: &mut Vec<f32>) {
+    let (block_elems, block_bytes) = TensorDType::Q4_0.block_layout().unwrap();
+
+    out.clear();
+    out.reserve(num_elements);
+
+    let mut remaining = num_elements;
+    for block in raw.chunks(block_bytes) {
+        if remaining == 0 {
+            break;
+        }
 
-        self.keys = vec![vec![0.0; total_size];
-        self.values = vec![vec![0.0; total_size];
-        self.max_len = new_max_len;
-        self.seq_len = 0;
+        let scale = half::f16::from_bits(u16::from_le_bytes([block[0], block[1]])).to_f32();
+        let take = remaining.min(block_elems);
+        let packed = &block[2..];
+
+        for i in 0..take {
+            let byte = packed[i / 2];
+            let nibble = if i % 2 == 0 { byte & 0x0F } else { (byte >> 4) & 0x0F };
+            out.push((nibble as f32 - 8.0) * scale);
+        }
+
+        remaining -= take;
     }
 }
 
+/// Dequantize affine (asymmetric) int8 bytes into `out`: `x = (q - zero_point) * scale`
+fn dequantize_i8_affine_into(raw: &[u8], scale: f32, zero_point: i32, out: &mut Vec<f32>) {
+    out.clear();
+    out.extend(raw.iter().map(|&q| (q as i8 as i32 - zero_point) as f32 * scale));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dequantize_i8_affine() {
+        // q=0 is the zero point here, so it dequantizes to 0.0
+        let raw = vec![0u8, 1u8, (-1i8) as u8, 10u8];
+        let mut out = Vec::new();
+        dequantize_i8_affine_into(&raw, 0.5, 0, &mut out);
+        assert_eq!(out, vec![0.0, 0.5, -0.5, 5.0]);
+    }
+
+    #[test]
+    fn test_dequantize_i8_affine_with_zero_point() {
+        let raw = vec![128u8, 138u8]; // as i8: -128, -118
+        let mut out = Vec::new();
+        dequantize_i8_affine_into(&raw, 2.0, -128, &mut out);
+        assert_eq!(out, vec![0.0, 20.0]);
+    }
+
+    #[test]
+    fn test_alibi_slopes_power_of_two() {
+        let slopes = alibi_slopes(8);
+        assert_eq!(slopes.len(), 8);
+        assert!((slopes[0] - 2f32.powf(-1.0)).abs() < 1e-6);
+        assert!((slopes[7] - 2f32.powf(-8.0)).abs() < 1e-6);
+        // Slopes decrease monotonically
+        assert!(slopes.windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_alibi_slopes_non_power_of_two() {
+        let slopes = alibi_slopes(12);
+        assert_eq!(slopes.len(), 12);
+        // First 8 slopes match the power-of-two-below case exactly
+        assert_eq!(&slopes[..8], alibi_slopes(8).as_slice());
+    }
+
+    #[test]
+    fn test_position_encoding_from_architecture() {
+        assert_eq!(PositionEncoding::from_architecture("bloom"), PositionEncoding::Alibi);
+        assert_eq!(PositionEncoding::from_architecture("MPT"), PositionEncoding::Alibi);
+        assert_eq!(PositionEncoding::from_architecture("llama"), PositionEncoding::Rotary);
+    }
+
     #[test]
     fn test_kv_cache() {
-        let mut cache = KVCache::new(32, 32, 128, 2048);
+        let mut allocator = KVCacheAllocator::new(32, 32, 128);
+        let mut cache = KVCache::new(32, 32, 128);
 
-        assert_eq!(cache.seq_len, 0);
+        assert_eq!(cache.seq_len(), 0);
 
         let keys = vec![0.1f32; 32 * 128];
         let values = vec![0.2f32; 32 * 128];
 
-        cache.append(0, &keys, &values).unwrap();
+        cache.append(&mut allocator, 0, &keys, &values).unwrap();
+        cache.advance(1);
 
-        assert_eq!(cache.seq_len, 1);
+        assert_eq!(cache.seq_len(), 1);
+        assert_eq!(cache.get_keys(&allocator, 0, 0).unwrap(), keys.as_slice());
+        assert_eq!(cache.get_values(&allocator, 0, 0).unwrap(), values.as_slice());
+    }
+
+    #[test]
+    fn test_kv_cache_allocates_new_block_on_fill() {
+        let mut allocator = KVCacheAllocator::new(1, 1, 4);
+        let mut cache = KVCache::new(1, 1, 4);
+
+        for i in 0..KV_BLOCK_SIZE {
+            let v = vec![i as f32; 4];
+            cache.append(&mut allocator, 0, &v, &v).unwrap();
+            cache.advance(1);
+        }
+        assert_eq!(allocator.num_blocks(), 1);
+
+        // One more position spills into a second physical block
+        let v = vec![99.0f32; 4];
+        cache.append(&mut allocator, 0, &v, &v).unwrap();
+        cache.advance(1);
+        assert_eq!(allocator.num_blocks(), 2);
+        assert_eq!(cache.get_keys(&allocator, 0, KV_BLOCK_SIZE).unwrap(), v.as_slice());
+    }
+
+    #[test]
+    fn test_kv_cache_fork_shares_blocks_until_written() {
+        let mut allocator = KVCacheAllocator::new(1, 1, 4);
+        let mut prompt_cache = KVCache::new(1, 1, 4);
+
+        let shared = vec![1.0f32; 4];
+        prompt_cache.append(&mut allocator, 0, &shared, &shared).unwrap();
+        prompt_cache.advance(1);
+
+        let mut a = prompt_cache.fork(&mut allocator);
+        let mut b = prompt_cache.fork(&mut allocator);
+        assert_eq!(allocator.num_blocks(), 1);
+
+        // `a` writes its own next token; still shares the prompt's block with `b`
+        let a_tok = vec![2.0f32; 4];
+        a.append(&mut allocator, 0, &a_tok, &a_tok).unwrap();
+        a.advance(1);
+        assert_eq!(b.get_keys(&allocator, 0, 0).unwrap(), shared.as_slice());
+
+        a.reset(&mut allocator);
+        b.reset(&mut allocator);
+        prompt_cache.reset(&mut allocator);
+        assert_eq!(allocator.num_free_blocks(), allocator.num_blocks());
+    }
+
+    #[test]
+    fn test_dequantize_q8_0() {
+        // single partial block (4 of 32 elements), scale = 2.0
+        let scale = half::f16::from_f32(2.0).to_bits().to_le_bytes();
+        let mut raw = vec![scale[0], scale[1]];
+        raw.extend_from_slice(&[1u8, 2u8, (-1i8) as u8, (-2i8) as u8]);
+        raw.resize(2 + QBLOCK_ELEMS, 0);
+
+        let mut out = Vec::new();
+        dequantize_q8_0_into(&raw, 4, &mut out);
+
+        assert_eq!(out, vec![2.0, 4.0, -2.0, -4.0]);
+    }
+
+    #[test]
+    fn test_dequantize_q4_0() {
+        // single partial block (4 of 32 elements), scale = 1.0
+        // nibbles 8, 9, 7, 6 -> biased values 0, 1, -1, -2
+        let scale = half::f16::from_f32(1.0).to_bits().to_le_bytes();
+        let mut raw = vec![scale[0], scale[1]];
+        raw.push(8 | (9 << 4));
+        raw.push(7 | (6 << 4));
+        raw.resize(2 + QBLOCK_ELEMS / 2, 0);
+
+        let mut out = Vec::new();
+        dequantize_q4_0_into(&raw, 4, &mut out);
+
+        assert_eq!(out, vec![0.0, 1.0, -1.0, -2.0]);
     }
 }
@@ -0,0 +1,176 @@
+//! Streaming batch-progress events for live monitoring
+//!
+//! `InferenceLoopWorker`/`process_batch` publish a [`BatchProgressEvent`]
+//! each time a batch starts and finishes, so a dashboard or the router can
+//! render real-time throughput and in-flight batch count instead of scraping logs.
+//! [`ProgressPublisher`] fans these out over a `tokio::sync::broadcast`
+//! channel - any number of subscribers can [`ProgressPublisher::subscribe`]
+//! independently, and a slow subscriber only drops its own backlog instead
+//! of blocking the worker loop. Each `Completed` event carries rolling
+//! [`ProgressAggregates`] (batches/sec, mean ms/request, in-flight count)
+//! alongside the per-batch detail, so a consumer gets both without having
+//! to maintain its own rolling window.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// How many unpublished events a lagging subscriber may fall behind before
+/// `tokio::sync::broadcast` starts dropping its oldest ones
+const PROGRESS_CHANNEL_CAPACITY: usize = 256;
+
+/// Smoothing factor for the batches/sec and mean-ms/request rolling
+/// averages; matches `batching::LATENCY_EMA_ALPHA`
+const PROGRESS_EMA_ALPHA: f64 = 0.1;
+
+/// A single step in a batch's lifecycle, published onto [`ProgressPublisher`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BatchProgressEvent {
+    /// A batch was just dispatched to the blocking pool
+    Started {
+        batch_id: u64,
+        num_requests: usize,
+        in_flight: usize,
+    },
+
+    /// A batch finished, successfully or not
+    Completed {
+        batch_id: u64,
+        num_requests: usize,
+
+        /// Per-request token/element counts, in the batch's original order
+        request_token_counts: Vec<usize>,
+
+        elapsed_ms: u64,
+        success: bool,
+        in_flight: usize,
+
+        /// Rolling aggregates as of this event
+        aggregates: ProgressAggregates,
+    },
+}
+
+/// Rolling throughput aggregates, recomputed after every completed batch
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ProgressAggregates {
+    pub batches_per_sec: f64,
+    pub mean_ms_per_request: f64,
+    pub in_flight: usize,
+}
+
+/// Publishes [`BatchProgressEvent`]s and maintains the rolling aggregates
+/// included in every `Completed` event
+///
+/// The EMA state is stored as the bit pattern of an `f64` inside an
+/// `AtomicU64`, the same trick `BatchScheduler` uses for its latency EMA,
+/// since there's no stable `AtomicF64`.
+pub struct ProgressPublisher {
+    sender: broadcast::Sender<BatchProgressEvent>,
+    next_batch_id: AtomicU64,
+    batch_interval_ema_ns_bits: AtomicU64,
+    ms_per_request_ema_bits: AtomicU64,
+    last_batch_started_at: Mutex<Option<Instant>>,
+}
+
+impl Default for ProgressPublisher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressPublisher {
+    /// Create a publisher with no subscribers yet; events published before
+    /// the first `subscribe()` call are simply not observed by anyone
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(PROGRESS_CHANNEL_CAPACITY);
+        Self {
+            sender,
+            next_batch_id: AtomicU64::new(0),
+            batch_interval_ema_ns_bits: AtomicU64::new(0),
+            ms_per_request_ema_bits: AtomicU64::new(0),
+            last_batch_started_at: Mutex::new(None),
+        }
+    }
+
+    /// Subscribe to this publisher's event stream
+    pub fn subscribe(&self) -> broadcast::Receiver<BatchProgressEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Record and publish the start of a newly-dispatched batch, returning
+    /// its `batch_id` so the caller can report its completion later
+    pub fn batch_started(&self, num_requests: usize, in_flight: usize) -> u64 {
+        let batch_id = self.next_batch_id.fetch_add(1, Ordering::Relaxed);
+
+        let now = Instant::now();
+        let mut last_started_at = self.last_batch_started_at.lock().unwrap();
+        if let Some(previous) = *last_started_at {
+            let interval_ns = now.duration_since(previous).as_nanos() as f64;
+            update_ema(&self.batch_interval_ema_ns_bits, interval_ns);
+        }
+        *last_started_at = Some(now);
+        drop(last_started_at);
+
+        // No subscribers is the common case outside an active monitoring
+        // session; `send` failing just means nobody is listening right now.
+        let _ = self.sender.send(BatchProgressEvent::Started { batch_id, num_requests, in_flight });
+
+        batch_id
+    }
+
+    /// Record and publish the completion of a batch started by
+    /// `batch_started`
+    pub fn batch_completed(
+        &self,
+        batch_id: u64,
+        request_token_counts: Vec<usize>,
+        elapsed: Duration,
+        success: bool,
+        in_flight: usize,
+    ) {
+        let num_requests = request_token_counts.len();
+        if num_requests > 0 {
+            let ms_per_request = elapsed.as_secs_f64() * 1000.0 / num_requests as f64;
+            update_ema(&self.ms_per_request_ema_bits, ms_per_request);
+        }
+
+        let aggregates = ProgressAggregates {
+            batches_per_sec: self.batches_per_sec(),
+            mean_ms_per_request: f64::from_bits(self.ms_per_request_ema_bits.load(Ordering::Relaxed)),
+            in_flight,
+        };
+
+        let _ = self.sender.send(BatchProgressEvent::Completed {
+            batch_id,
+            num_requests,
+            request_token_counts,
+            elapsed_ms: elapsed.as_millis() as u64,
+            success,
+            in_flight,
+            aggregates,
+        });
+    }
+
+    fn batches_per_sec(&self) -> f64 {
+        let interval_ns = f64::from_bits(self.batch_interval_ema_ns_bits.load(Ordering::Relaxed));
+        if interval_ns <= 0.0 {
+            0.0
+        } else {
+            1_000_000_000.0 / interval_ns
+        }
+    }
+}
+
+/// Exponential moving average update shared by both rolling aggregates:
+/// seeds from the first sample rather than averaging against a zero baseline
+fn update_ema(cell: &AtomicU64, sample: f64) {
+    let previous = f64::from_bits(cell.load(Ordering::Relaxed));
+    let updated = if previous == 0.0 {
+        sample
+    } else {
+        PROGRESS_EMA_ALPHA * sample + (1.0 - PROGRESS_EMA_ALPHA) * previous
+    };
+    cell.store(updated.to_bits(), Ordering::Relaxed);
+}
@@ -0,0 +1,167 @@
+//! Background and point-in-time resource sampling for worker metrics
+//!
+//! `memory_used` and `cpu_utilization` are declared in `WorkerMetrics` but
+//! nothing updates them; [`ResourceMonitor::start_sampler`] fixes that with
+//! a periodic poll. A fixed-interval sample can still step right over a
+//! short-lived spike (e.g. the peak RSS of a single forward pass), so
+//! [`ResourceMonitor::track`] additionally wraps a hot section with a
+//! tighter polling thread and records the peak it observed.
+//!
+//! The platform-specific pieces (`/proc/self/statm`, `getrusage`) are Linux
+//! only; other targets get a no-op fallback so the crate still builds.
+
+use chatloop_common::metrics::{WithWorker, METRICS};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How often [`ResourceMonitor::track`] polls RSS while an operation is in flight
+const TRACK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::mem::MaybeUninit;
+
+    /// Current resident set size, in bytes, from `/proc/self/statm`
+    pub fn current_rss_bytes() -> Option<u64> {
+        let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+        let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if page_size <= 0 {
+            return None;
+        }
+        Some(pages * page_size as u64)
+    }
+
+    /// Peak RSS (`ru_maxrss`, in bytes) and total CPU time (`ru_utime` +
+    /// `ru_stime`, in seconds) via `getrusage(RUSAGE_SELF)`
+    pub fn rusage_snapshot() -> Option<(u64, f64)> {
+        let mut usage = MaybeUninit::<libc::rusage>::uninit();
+        let rc = unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) };
+        if rc != 0 {
+            return None;
+        }
+        let usage = unsafe { usage.assume_init() };
+
+        // ru_maxrss is reported in KB on Linux
+        let peak_rss_bytes = usage.ru_maxrss as u64 * 1024;
+        let cpu_seconds = (usage.ru_utime.tv_sec + usage.ru_stime.tv_sec) as f64
+            + (usage.ru_utime.tv_usec + usage.ru_stime.tv_usec) as f64 / 1_000_000.0;
+
+        Some((peak_rss_bytes, cpu_seconds))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    /// No-op fallback: this platform has no portable RSS reader
+    pub fn current_rss_bytes() -> Option<u64> {
+        None
+    }
+
+    /// No-op fallback: this platform has no `getrusage`
+    pub fn rusage_snapshot() -> Option<(u64, f64)> {
+        None
+    }
+}
+
+/// Samples process resource usage into `WorkerMetrics`, labeled by `worker_id`
+pub struct ResourceMonitor {
+    worker_id: String,
+}
+
+impl ResourceMonitor {
+    /// Create a monitor that labels every metric it records with `worker_id`
+    pub fn new(worker_id: impl Into<String>) -> Self {
+        Self {
+            worker_id: worker_id.into(),
+        }
+    }
+
+    /// Spawn a background task that refreshes `memory_used` and
+    /// `cpu_utilization` every `interval`
+    ///
+    /// Returns immediately; the task runs until the process exits.
+    /// `cpu_utilization` is the percentage of a single core consumed since
+    /// the previous sample, derived from the change in `getrusage` CPU time
+    /// over the elapsed wall-clock time.
+    pub fn start_sampler(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut last_cpu_seconds = platform::rusage_snapshot().map(|(_, cpu)| cpu);
+            let mut last_sampled_at = Instant::now();
+
+            loop {
+                tokio::time::sleep(interval).await;
+
+                if let Some(rss) = platform::current_rss_bytes() {
+                    METRICS
+                        .worker
+                        .memory_used
+                        .with_worker(&self.worker_id)
+                        .set(rss as i64);
+                }
+
+                if let Some((_, cpu_seconds)) = platform::rusage_snapshot() {
+                    let elapsed = last_sampled_at.elapsed().as_secs_f64();
+                    if let Some(last) = last_cpu_seconds {
+                        if elapsed > 0.0 {
+                            let percent = ((cpu_seconds - last) / elapsed * 100.0).max(0.0);
+                            METRICS
+                                .worker
+                                .cpu_utilization
+                                .with_worker(&self.worker_id)
+                                .set(percent as i64);
+                        }
+                    }
+                    last_cpu_seconds = Some(cpu_seconds);
+                    last_sampled_at = Instant::now();
+                }
+            }
+        })
+    }
+
+    /// Run `f`, polling RSS every 50ms while it's in flight and recording
+    /// the peak observed into `peak_memory_used`
+    ///
+    /// On platforms with no RSS reader, `f` still runs normally; the
+    /// histogram just never receives a sample there.
+    pub fn track<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let peak_bytes = Arc::new(AtomicU64::new(0));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let poller = {
+            let peak_bytes = Arc::clone(&peak_bytes);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    if let Some(rss) = platform::current_rss_bytes() {
+                        peak_bytes.fetch_max(rss, Ordering::Relaxed);
+                    }
+                    std::thread::sleep(TRACK_POLL_INTERVAL);
+                }
+            })
+        };
+
+        let result = f();
+
+        done.store(true, Ordering::Relaxed);
+        if poller.join().is_err() {
+            warn!("Resource sampler thread panicked while tracking a tracked operation");
+        }
+
+        let peak_bytes = peak_bytes.load(Ordering::Relaxed);
+        if peak_bytes > 0 {
+            METRICS
+                .worker
+                .peak_memory_used
+                .with_worker(&self.worker_id)
+                .observe(peak_bytes as f64);
+        }
+
+        result
+    }
+}
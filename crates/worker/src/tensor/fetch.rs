@@ -0,0 +1,257 @@
+//! Content-addressable streaming loader for SafeTensor weights
+//!
+//! Lets a worker fetch model shards on demand from a content-addressable
+//! blob store instead of requiring the full SafeTensor file to already be
+//! present on local disk. Shards are named by their sha256 digest, streamed
+//! in fixed-size chunks, and written into a local cache directory that is
+//! then memory-mapped the same way as a locally-resident file, so multiple
+//! workers on the same host end up sharing one page-cache copy.
+
+use super::safetensors::SafeTensorBuffer;
+use chatloop_common::{ChatLoopError, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Size of each streamed chunk, in bytes
+pub const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Source of shard bytes, keyed by content digest
+///
+/// This is the seam a real gRPC bytestream client plugs into (`read_chunk`
+/// streams one `CHUNK_SIZE` frame at a time, resumable by `offset`); tests
+/// and local development can plug in an in-memory or filesystem-backed
+/// implementation instead. Implementations should report transient network
+/// failures as `ChatLoopError::FetchRetryable` so `ShardLoader::fetch` can be
+/// retried from the last acknowledged offset; local cache/disk failures are
+/// reported as `ChatLoopError::MemoryMap`.
+pub trait ShardSource: Send + Sync {
+    /// Total size of the shard in bytes, if known up front
+    fn size<'a>(&'a self, digest: &'a str) -> Pin<Box<dyn Future<Output = Result<u64>> + Send + 'a>>;
+
+    /// Read up to `CHUNK_SIZE` bytes starting at `offset`
+    ///
+    /// Returns fewer bytes than requested only at end-of-shard (an empty
+    /// result signals completion).
+    fn read_chunk<'a>(
+        &'a self,
+        digest: &'a str,
+        offset: u64,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send + 'a>>;
+}
+
+/// Fetches model shards from a `ShardSource` into a local content-addressable cache
+pub struct ShardLoader<S: ShardSource> {
+    source: S,
+    cache_dir: PathBuf,
+    in_flight: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl<S: ShardSource> ShardLoader<S> {
+    /// Create a new loader backed by `source`, caching shards under `cache_dir`
+    pub fn new(source: S, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            source,
+            cache_dir: cache_dir.into(),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cache_path(&self, digest: &str) -> PathBuf {
+        self.cache_dir.join(digest)
+    }
+
+    fn partial_path(&self, digest: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}.partial", digest))
+    }
+
+    /// Fetch and memory-map the shard identified by `digest`
+    ///
+    /// If the shard is already cached and verified, this opens it directly
+    /// with no network traffic. Concurrent fetches for the same digest
+    /// through this loader are deduplicated and share one download; a partially
+    /// downloaded shard resumes from its last byte offset instead of
+    /// restarting from scratch.
+    pub async fn fetch(&self, digest: &str) -> Result<SafeTensorBuffer> {
+        let final_path = self.cache_path(digest);
+        if final_path.exists() {
+            debug!("Shard {} already cached at {}", digest, final_path.display());
+            return SafeTensorBuffer::open(&final_path);
+        }
+
+        let lock = self.dedup_lock(digest).await;
+        let _guard = lock.lock().await;
+
+        // Another task may have finished fetching this digest while we waited
+        if final_path.exists() {
+            return SafeTensorBuffer::open(&final_path);
+        }
+
+        std::fs::create_dir_all(&self.cache_dir)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to create shard cache dir: {}", e)))?;
+
+        let partial_path = self.partial_path(digest);
+        let mut offset = std::fs::metadata(&partial_path).map(|m| m.len()).unwrap_or(0);
+        let total_size = self.source.size(digest).await?;
+
+        if offset > total_size {
+            warn!("Partial shard {} is longer than expected, restarting", digest);
+            offset = 0;
+        }
+
+        info!(
+            "Fetching shard {} ({} bytes total, resuming from offset {})",
+            digest, total_size, offset
+        );
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&partial_path)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to open partial shard: {}", e)))?;
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to seek partial shard: {}", e)))?;
+
+        while offset < total_size {
+            let chunk = self.source.read_chunk(digest, offset).await?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            file.write_all(&chunk)
+                .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to write shard chunk: {}", e)))?;
+            offset += chunk.len() as u64;
+
+            debug!("Shard {}: {}/{} bytes fetched", digest, offset, total_size);
+        }
+
+        file.flush()
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to flush shard: {}", e)))?;
+        drop(file);
+
+        self.verify_digest(&partial_path, digest)?;
+
+        std::fs::rename(&partial_path, &final_path)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to finalize shard: {}", e)))?;
+
+        info!("Shard {} fetched and verified", digest);
+
+        SafeTensorBuffer::open(&final_path)
+    }
+
+    /// Get (or create) the per-digest lock used to deduplicate concurrent fetches
+    async fn dedup_lock(&self, digest: &str) -> Arc<Mutex<()>> {
+        let mut in_flight = self.in_flight.lock().await;
+        in_flight
+            .entry(digest.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+
+    /// Verify the fetched file's content matches its expected digest
+    fn verify_digest(&self, path: &Path, expected_digest: &str) -> Result<()> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to read shard for verification: {}", e)))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        let actual_digest = format!("{:x}", hasher.finalize());
+
+        if actual_digest != expected_digest {
+            return Err(ChatLoopError::MemoryMap(format!(
+                "Shard digest mismatch: expected {}, got {}",
+                expected_digest, actual_digest
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    /// In-memory shard source for tests, chunked at `CHUNK_SIZE`
+    struct MemorySource {
+        shards: HashMap<String, Vec<u8>>,
+        reads: StdMutex<Vec<(String, u64)>>,
+    }
+
+    impl ShardSource for MemorySource {
+        fn size<'a>(&'a self, digest: &'a str) -> Pin<Box<dyn Future<Output = Result<u64>> + Send + 'a>> {
+            Box::pin(async move {
+                self.shards
+                    .get(digest)
+                    .map(|d| d.len() as u64)
+                    .ok_or_else(|| ChatLoopError::MemoryMap("Unknown digest".to_string()))
+            })
+        }
+
+        fn read_chunk<'a>(
+            &'a self,
+            digest: &'a str,
+            offset: u64,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send + 'a>> {
+            Box::pin(async move {
+                self.reads.lock().unwrap().push((digest.to_string(), offset));
+                let data = self
+                    .shards
+                    .get(digest)
+                    .ok_or_else(|| ChatLoopError::MemoryMap("Unknown digest".to_string()))?;
+
+                let start = offset as usize;
+                if start >= data.len() {
+                    return Ok(Vec::new());
+                }
+                let end = (start + CHUNK_SIZE).min(data.len());
+                Ok(data[start..end].to_vec())
+            })
+        }
+    }
+
+    fn digest_of(data: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(data);
+        format!("{:x}", hasher.finalize())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_resumes_partial_download() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 256) as u8).collect();
+        let digest = digest_of(&data);
+
+        let mut shards = HashMap::new();
+        shards.insert(digest.clone(), data.clone());
+        let source = MemorySource {
+            shards,
+            reads: StdMutex::new(Vec::new()),
+        };
+
+        let cache_dir = tempfile::tempdir().unwrap();
+        let loader = ShardLoader::new(source, cache_dir.path());
+
+        // Simulate a prior partial download
+        let partial_path = cache_dir.path().join(format!("{}.partial", digest));
+        std::fs::write(&partial_path, &data[..5_000]).unwrap();
+
+        let buffer_result = loader.fetch(&digest).await;
+
+        // The shard data here isn't a valid SafeTensor file, so opening it
+        // fails downstream, but the fetch/verify/resume machinery above that
+        // must have succeeded for rename to have happened.
+        assert!(buffer_result.is_err());
+        assert!(cache_dir.path().join(&digest).exists());
+        assert!(!partial_path.exists());
+
+        let reads = loader.source.reads.lock().unwrap();
+        assert_eq!(reads[0], (digest.clone(), 5_000));
+    }
+}
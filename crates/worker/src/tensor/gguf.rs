@@ -0,0 +1,414 @@
+//! GGUF/GGML container format support with memory-mapped files
+//!
+//! Most community-quantized checkpoints ship as GGUF rather than
+//! safetensors. This module provides the same zero-copy, memory-mapped
+//! surface as [`tensor::safetensors`](super::safetensors) — `open`,
+//! `header`, `get_tensor`, `tensor_names` — so callers can work with either
+//! format through [`crate::model::ModelFormat`]. Tensor metadata is parsed
+//! into the existing `SafeTensorHeader`/`TensorInfo` shapes rather than a
+//! parallel set of types, so partition-indexing code that walks
+//! `buffer.header().tensors` works unchanged regardless of which format it
+//! was handed.
+//!
+//! GGUF layout: 4-byte magic `GGUF`, `u32` version, `u64` tensor count,
+//! `u64` metadata-kv count, then that many metadata entries (a
+//! length-prefixed string key followed by a type-tagged value), then that
+//! many tensor entries (name, dims, GGML type id, byte offset relative to
+//! the start of tensor data). Tensor data begins right after the header,
+//! aligned up to the `general.alignment` metadata value (default 32).
+
+use super::safetensors::{SafeTensorHeader, SafeTensorView, TensorDType, TensorInfo};
+use chatloop_common::{ChatLoopError, Result};
+use memmap2::Mmap;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+
+/// Magic bytes at the start of every GGUF file
+pub const GGUF_MAGIC: [u8; 4] = *b"GGUF";
+
+/// Tensor-data alignment used when `general.alignment` is absent
+const DEFAULT_ALIGNMENT: u64 = 32;
+
+/// Memory-mapped GGUF buffer
+///
+/// This provides zero-copy access to tensor data stored in GGUF format,
+/// mirroring [`SafeTensorBuffer`](super::safetensors::SafeTensorBuffer).
+pub struct GgufBuffer {
+    /// Memory-mapped file
+    mmap: Mmap,
+
+    /// Parsed header, in the same shape safetensors files parse into
+    header: SafeTensorHeader,
+}
+
+impl GgufBuffer {
+    /// Open a GGUF file with memory mapping
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        let file = File::open(path)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to open file {}: {}", path.display(), e)))?;
+
+        let mmap = unsafe {
+            Mmap::map(&file)
+                .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to mmap file {}: {}", path.display(), e)))?
+        };
+
+        let header = parse_header(&mmap)?;
+
+        Ok(Self { mmap, header })
+    }
+
+    /// Get the header
+    pub fn header(&self) -> &SafeTensorHeader {
+        &self.header
+    }
+
+    /// Get tensor names
+    pub fn tensor_names(&self) -> impl Iterator<Item = &String> {
+        self.header.tensors.keys()
+    }
+
+    /// Get a zero-copy view of a tensor
+    pub fn get_tensor(&self, name: &str) -> Option<SafeTensorView<'_>> {
+        let info = self.header.tensors.get(name)?;
+        let data_start = info.data_offsets[0];
+        let data_end = info.data_offsets[1];
+
+        if data_end > self.mmap.len() {
+            return None;
+        }
+
+        let dtype = info.get_dtype()?;
+        let data = &self.mmap[data_start..data_end];
+
+        Some(SafeTensorView::new(data, info.shape.clone(), dtype))
+    }
+}
+
+/// Map a GGML tensor type id onto our `TensorDType`
+///
+/// Only the handful of types this worker knows how to handle are mapped;
+/// the many remaining GGML quantization schemes (Q4_1, Q5_K, IQ*, ...)
+/// surface as an explicit error rather than silently misreading bytes.
+fn dtype_from_ggml(type_id: u32) -> Result<TensorDType> {
+    match type_id {
+        0 => Ok(TensorDType::F32),
+        1 => Ok(TensorDType::F16),
+        2 => Ok(TensorDType::Q4_0),
+        8 => Ok(TensorDType::Q8_0),
+        24 => Ok(TensorDType::I8),
+        26 => Ok(TensorDType::I32),
+        other => Err(ChatLoopError::MemoryMap(format!(
+            "Unsupported GGML tensor type id {}",
+            other
+        ))),
+    }
+}
+
+/// A parsed GGUF metadata value
+///
+/// Only scalar kinds are consulted directly (e.g. `general.alignment`);
+/// strings and arrays are still parsed eagerly (GGUF has no way to skip a
+/// variable-length value without reading it). Every value is kept in
+/// `SafeTensorHeader::metadata` as its `Display` rendering.
+#[derive(Debug, Clone)]
+enum GgufValue {
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array(Vec<GgufValue>),
+}
+
+impl GgufValue {
+    fn as_u64(&self) -> Option<u64> {
+        match self {
+            GgufValue::UInt(v) => Some(*v),
+            GgufValue::Int(v) => u64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GgufValue {
+    /// Render a metadata value for [`SafeTensorHeader::metadata`], which
+    /// (mirroring safetensors' `__metadata__`) is a flat string map
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GgufValue::UInt(v) => write!(f, "{}", v),
+            GgufValue::Int(v) => write!(f, "{}", v),
+            GgufValue::Float(v) => write!(f, "{}", v),
+            GgufValue::Bool(v) => write!(f, "{}", v),
+            GgufValue::String(v) => write!(f, "{}", v),
+            GgufValue::Array(items) => write!(
+                f,
+                "[{}]",
+                items.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+}
+
+/// A read cursor over the memory-mapped file, for the little-endian,
+/// length-prefixed primitives GGUF is built from
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.pos + n > self.data.len() {
+            return Err(ChatLoopError::MemoryMap("GGUF file truncated".to_string()));
+        }
+        let slice = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i8(&mut self) -> Result<i8> {
+        Ok(self.u8()? as i8)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        Ok(i16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u64()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Invalid UTF-8 in GGUF string: {}", e)))
+    }
+}
+
+/// Read one metadata value of the given GGUF type tag, recursing into
+/// `ARRAY` elements
+fn read_value(cur: &mut Cursor<'_>, type_id: u32) -> Result<GgufValue> {
+    match type_id {
+        0 => Ok(GgufValue::UInt(cur.u8()? as u64)),
+        1 => Ok(GgufValue::Int(cur.i8()? as i64)),
+        2 => Ok(GgufValue::UInt(cur.u16()? as u64)),
+        3 => Ok(GgufValue::Int(cur.i16()? as i64)),
+        4 => Ok(GgufValue::UInt(cur.u32()? as u64)),
+        5 => Ok(GgufValue::Int(cur.i32()? as i64)),
+        6 => Ok(GgufValue::Float(cur.f32()? as f64)),
+        7 => Ok(GgufValue::Bool(cur.u8()? != 0)),
+        8 => Ok(GgufValue::String(cur.string()?)),
+        9 => {
+            let elem_type = cur.u32()?;
+            let count = cur.u64()? as usize;
+            let mut elements = Vec::with_capacity(count);
+            for _ in 0..count {
+                elements.push(read_value(cur, elem_type)?);
+            }
+            Ok(GgufValue::Array(elements))
+        }
+        10 => Ok(GgufValue::UInt(cur.u64()?)),
+        11 => Ok(GgufValue::Int(cur.i64()?)),
+        12 => Ok(GgufValue::Float(cur.f64()?)),
+        other => Err(ChatLoopError::MemoryMap(format!(
+            "Unsupported GGUF metadata value type {}",
+            other
+        ))),
+    }
+}
+
+/// Round `pos` up to the next multiple of `alignment`
+fn align_up(pos: usize, alignment: u64) -> usize {
+    let alignment = (alignment.max(1)) as usize;
+    ((pos + alignment - 1) / alignment) * alignment
+}
+
+fn parse_header(mmap: &[u8]) -> Result<SafeTensorHeader> {
+    let mut cur = Cursor::new(mmap);
+
+    if cur.take(4)? != GGUF_MAGIC {
+        return Err(ChatLoopError::MemoryMap("Not a GGUF file: bad magic".to_string()));
+    }
+
+    let _version = cur.u32()?;
+    let tensor_count = cur.u64()? as usize;
+    let metadata_kv_count = cur.u64()? as usize;
+
+    let mut metadata = HashMap::with_capacity(metadata_kv_count);
+    for _ in 0..metadata_kv_count {
+        let key = cur.string()?;
+        let type_id = cur.u32()?;
+        let value = read_value(&mut cur, type_id)?;
+        metadata.insert(key, value);
+    }
+
+    let alignment = metadata
+        .get("general.alignment")
+        .and_then(GgufValue::as_u64)
+        .unwrap_or(DEFAULT_ALIGNMENT);
+
+    struct RawEntry {
+        name: String,
+        shape: Vec<usize>,
+        dtype: TensorDType,
+        relative_offset: usize,
+    }
+
+    let mut entries = Vec::with_capacity(tensor_count);
+    for _ in 0..tensor_count {
+        let name = cur.string()?;
+        let n_dims = cur.u32()? as usize;
+
+        let mut shape = Vec::with_capacity(n_dims);
+        for _ in 0..n_dims {
+            shape.push(cur.u64()? as usize);
+        }
+        // GGUF stores dims fastest-varying first (GGML's convention);
+        // reverse to the outer-to-inner row-major order used elsewhere.
+        shape.reverse();
+
+        let ggml_type = cur.u32()?;
+        let dtype = dtype_from_ggml(ggml_type)?;
+        let relative_offset = cur.u64()? as usize;
+
+        entries.push(RawEntry { name, shape, dtype, relative_offset });
+    }
+
+    let data_start = align_up(cur.pos, alignment);
+    let mut tensors = HashMap::with_capacity(entries.len());
+
+    for entry in entries {
+        let info = TensorInfo {
+            dtype: entry.dtype.as_str().to_string(),
+            shape: entry.shape,
+            data_offsets: vec![0, 0],
+        };
+        let size_bytes = info.size_bytes();
+
+        let start = data_start + entry.relative_offset;
+        let end = start + size_bytes;
+
+        if end > mmap.len() {
+            return Err(ChatLoopError::MemoryMap(format!(
+                "Tensor '{}' data extends past end of file",
+                entry.name
+            )));
+        }
+
+        tensors.insert(entry.name, TensorInfo { data_offsets: vec![start, end], ..info });
+    }
+
+    let string_metadata = metadata.iter().map(|(k, v)| (k.clone(), v.to_string())).collect();
+
+    Ok(SafeTensorHeader { tensors, metadata: string_metadata })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// Build a minimal single-tensor GGUF file: one metadata kv
+    /// (`general.alignment`) and one f32 tensor
+    fn create_test_gguf() -> NamedTempFile {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&GGUF_MAGIC);
+        buf.extend_from_slice(&3u32.to_le_bytes()); // version
+        buf.extend_from_slice(&1u64.to_le_bytes()); // tensor_count
+        buf.extend_from_slice(&1u64.to_le_bytes()); // metadata_kv_count
+
+        // metadata: general.alignment = 32 (UINT32)
+        let key = b"general.alignment";
+        buf.extend_from_slice(&(key.len() as u64).to_le_bytes());
+        buf.extend_from_slice(key);
+        buf.extend_from_slice(&4u32.to_le_bytes()); // type id 4 = UINT32
+        buf.extend_from_slice(&32u32.to_le_bytes());
+
+        // tensor entry: name "weight", shape (2, 2) stored fastest-first as [2, 2],
+        // ggml type 0 (F32), relative offset 0
+        let name = b"weight";
+        buf.extend_from_slice(&(name.len() as u64).to_le_bytes());
+        buf.extend_from_slice(name);
+        buf.extend_from_slice(&2u32.to_le_bytes()); // n_dims
+        buf.extend_from_slice(&2u64.to_le_bytes());
+        buf.extend_from_slice(&2u64.to_le_bytes());
+        buf.extend_from_slice(&0u32.to_le_bytes()); // ggml type F32
+        buf.extend_from_slice(&0u64.to_le_bytes()); // relative offset
+
+        // pad to the 32-byte alignment boundary, then write tensor data
+        while buf.len() % 32 != 0 {
+            buf.push(0);
+        }
+        let data: [u8; 16] = [
+            0, 0, 128, 63, // 1.0
+            0, 0, 0, 64, // 2.0
+            0, 0, 64, 64, // 3.0
+            0, 0, 128, 64, // 4.0
+        ];
+        buf.extend_from_slice(&data);
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&buf).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_gguf_open() {
+        let file = create_test_gguf();
+        let buffer = GgufBuffer::open(file.path()).unwrap();
+
+        assert_eq!(buffer.tensor_names().count(), 1);
+
+        let tensor = buffer.get_tensor("weight").unwrap();
+        assert_eq!(tensor.shape(), vec![2, 2]);
+        assert_eq!(tensor.dtype(), TensorDType::F32);
+
+        let data = unsafe { tensor.as_f32_slice() };
+        assert_eq!(data, &[1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_gguf_rejects_bad_magic() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"NOPE0000").unwrap();
+
+        assert!(GgufBuffer::open(file.path()).is_err());
+    }
+}
@@ -3,12 +3,24 @@
 //! This module provides tensor types and operations optimized for CPU inference.
 //! All operations are designed to be SIMD-friendly and minimize allocations.
 
+pub mod fetch;
+pub mod gguf;
+pub mod plugin;
 pub mod safetensors;
 pub mod ops;
 
-pub use safetensors::{SafeTensorBuffer, SafeTensorView, TensorDType};
-pub use ops::{TensorOps, matmul, quantize_int8, dequantize_int8};
-
+pub use fetch::{ShardLoader, ShardSource};
+pub use gguf::GgufBuffer;
+pub use plugin::{KernelRegistry, KERNEL_REGISTRY};
+pub use safetensors::{SafeTensorBuffer, SafeTensorHeader, SafeTensorView, TensorDType};
+pub use ops::{
+    TensorOps, conv2d, matmul, matmul_int8, matmul_int8_per_channel, matmul_int4, quantize_int8,
+    dequantize_int8, softmax_int8, quantize_int8_per_channel, dequantize_int8_per_channel,
+    quantize_int8_per_channel_symmetric, dequantize_int8_per_channel_symmetric,
+    quantize_int4, dequantize_int4, QuantizedInt4,
+};
+
+use chatloop_common::{ChatLoopError, Result};
 use std::fmt;
 
 /// Tensor shape
@@ -90,6 +102,117 @@ where
             strides: new_strides,
         }
     }
+
+    /// Materialize this view into a packed, row-major contiguous `Tensor`
+    ///
+    /// Walks `shape` in row-major order and resolves each element through
+    /// [`TensorView::index`], so a transposed or otherwise non-contiguous
+    /// view (non-unit, non-default strides) is copied correctly. Use this
+    /// before handing a view to a kernel that assumes `data[i]` is the
+    /// `i`-th row-major element.
+    pub fn contiguous(&self) -> Tensor<T> {
+        let data: Vec<T> = ShapeIter::new(&self.shape)
+            .map(|idx| self.data[self.index(&idx)])
+            .collect();
+
+        Tensor::new(data, self.shape.clone())
+    }
+}
+
+/// Row-major odometer over a shape, yielding one multi-index per element
+///
+/// Used to walk a (possibly broadcast or transposed) view in the logical
+/// order of its shape without assuming the backing data is laid out that
+/// way.
+pub(crate) struct ShapeIter<'a> {
+    shape: &'a [usize],
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<'a> ShapeIter<'a> {
+    pub(crate) fn new(shape: &'a [usize]) -> Self {
+        let done = shape.iter().any(|&dim| dim == 0);
+        Self { shape, idx: vec![0; shape.len()], done }
+    }
+}
+
+impl<'a> Iterator for ShapeIter<'a> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            return None;
+        }
+
+        let current = self.idx.clone();
+
+        // Increment like an odometer, starting from the fastest-varying dim.
+        let mut carry = true;
+        for i in (0..self.shape.len()).rev() {
+            if !carry {
+                break;
+            }
+            self.idx[i] += 1;
+            if self.idx[i] < self.shape[i] {
+                carry = false;
+            } else {
+                self.idx[i] = 0;
+            }
+        }
+        if carry {
+            self.done = true;
+        }
+
+        Some(current)
+    }
+}
+
+/// Align `shape`'s strides to `out_ndim` dimensions, NumPy-style
+///
+/// Dimensions are matched from the trailing (innermost) axis. Axes implied
+/// by the extra leading dimensions of a broadcast target, and any axis
+/// where `shape` is size-1, get a zero stride so the same source element is
+/// revisited for every broadcast position.
+pub(crate) fn broadcast_strides(shape: &[usize], strides: &[isize], out_ndim: usize) -> Strides {
+    let offset = out_ndim - shape.len();
+    let mut out = vec![0isize; out_ndim];
+    for i in 0..shape.len() {
+        out[offset + i] = if shape[i] == 1 { 0 } else { strides[i] };
+    }
+    out
+}
+
+/// Compute the broadcast output shape of two operand shapes, NumPy-style
+///
+/// Shapes are aligned from the trailing dimension; a missing leading
+/// dimension is treated as size 1. Two aligned dimensions are compatible
+/// when they're equal or one of them is 1.
+pub fn broadcast_shapes(a: &[usize], b: &[usize]) -> Result<Shape> {
+    let ndim = a.len().max(b.len());
+    let a_offset = ndim - a.len();
+    let b_offset = ndim - b.len();
+    let mut out = vec![0usize; ndim];
+
+    for i in 0..ndim {
+        let da = if i < a_offset { 1 } else { a[i - a_offset] };
+        let db = if i < b_offset { 1 } else { b[i - b_offset] };
+
+        out[i] = if da == db {
+            da
+        } else if da == 1 {
+            db
+        } else if db == 1 {
+            da
+        } else {
+            return Err(ChatLoopError::tensor(format!(
+                "Cannot broadcast shapes {:?} and {:?}",
+                a, b
+            )));
+        };
+    }
+
+    Ok(out)
 }
 
 /// Compute row-major strides from shape
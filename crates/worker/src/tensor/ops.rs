@@ -2,14 +2,69 @@
 //!
 //! This module provides high-performance tensor operations using SIMD where possible.
 //! All operations are designed to minimize allocations and maximize cache locality.
+//!
+//! Every op addresses elements through [`TensorView::index`] rather than a
+//! `row * cols + col` formula, so a transposed or otherwise strided view
+//! (see [`TensorView::transpose`]) is read correctly without first copying
+//! it. `add` and `mul` additionally support NumPy-style broadcasting: shapes
+//! are aligned from the trailing dimension and a size-1 dimension is
+//! broadcast via a zero stride. Use [`TensorView::contiguous`] to pack a
+//! view into owned, row-major data when a kernel needs that layout directly.
 
 use chatloop_common::{ChatLoopError, Result};
-use crate::tensor::{Shape, Tensor, TensorView};
+use crate::tensor::{broadcast_shapes, broadcast_strides, Shape, ShapeIter, Tensor, TensorView};
 use half::f16;
 use num_traits::{Float, NumCast, Zero};
 use rayon::prelude::*;
 use std::ops::{Add, Div, Mul, Sub};
 
+/// Resolve a multi-index to a flat data offset against a (possibly
+/// broadcast) stride vector
+fn offset_of(idx: &[usize], strides: &[isize]) -> usize {
+    idx.iter()
+        .zip(strides.iter())
+        .map(|(&i, &s)| (i as isize * s) as usize)
+        .sum()
+}
+
+/// Blocked 2D matmul kernel: `a` is (m, k), `b` is (k, n), both already
+/// sliced down to a single matrix (no batch dims). Shared by the top-level
+/// 2D case and by each batch slice of the N-D batched case.
+fn matmul_2d_blocked<T>(a: &TensorView<'_, T>, b: &TensorView<'_, T>, m: usize, k: usize, n: usize) -> Vec<T>
+where
+    T: Float,
+{
+    let mut c_data = vec![T::zero(); m * n];
+    const BLOCK_SIZE: usize = 64;
+
+    for i in (0..m).step_by(BLOCK_SIZE) {
+        let i_end = (i + BLOCK_SIZE).min(m);
+
+        for j in (0..n).step_by(BLOCK_SIZE) {
+            let j_end = (j + BLOCK_SIZE).min(n);
+
+            for l in (0..k).step_by(BLOCK_SIZE) {
+                let l_end = (l + BLOCK_SIZE).min(k);
+
+                // Inner loop - process block
+                for ii in i..i_end {
+                    for jj in j..j_end {
+                        let mut sum = T::zero();
+                        for ll in l..l_end {
+                            let a_val = a.data[a.index(&[ii, ll])];
+                            let b_val = b.data[b.index(&[ll, jj])];
+                            sum = sum + a_val * b_val;
+                        }
+                        c_data[ii * n + jj] = c_data[ii * n + jj] + sum;
+                    }
+                }
+            }
+        }
+    }
+
+    c_data
+}
+
 /// Generic tensor operations trait
 pub trait TensorOps<T>: Send + Sync {
     /// Matrix multiplication: C = A @ B
@@ -17,10 +72,10 @@ pub trait TensorOps<T>: Send + Sync {
     /// A: (m, k), B: (k, n), C: (m, n)
     fn matmul(a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Result<Tensor<T>>;
 
-    /// Element-wise addition
+    /// Element-wise addition, broadcasting shapes NumPy-style
     fn add(a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Result<Tensor<T>>;
 
-    /// Element-wise multiplication
+    /// Element-wise multiplication, broadcasting shapes NumPy-style
     fn mul(a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Result<Tensor<T>>;
 
     /// Scale: result = tensor * scalar
@@ -35,6 +90,13 @@ pub trait TensorOps<T>: Send + Sync {
     /// Softmax along last axis
     fn softmax(tensor: &TensorView<'_, T>) -> Result<Tensor<T>>;
 
+    /// "Quiet" (off-by-one) softmax along last axis
+    ///
+    /// Adds an implicit zero logit to the denominator, `y_i = exp(x_i) / (1
+    /// + sum_j exp(x_j))`, so a row can attend to "nothing" instead of being
+    /// forced to emit a distribution that sums to 1.
+    fn softmax_quiet(tensor: &TensorView<'_, T>) -> Result<Tensor<T>>;
+
     /// Layer normalization
     fn layer_norm(
         tensor: &TensorView<'_, T>,
@@ -52,93 +114,142 @@ macro_rules! impl_tensor_ops_float {
     ($t:ty) => {
         impl TensorOps<$t> for $t {
             fn matmul(a: &TensorView<'_, $t>, b: &TensorView<'_, $t>) -> Result<Tensor<$t>> {
-                if a.shape.len() != 2 || b.shape.len() != 2 {
-                    return Err(ChatLoopError::tensor("Matmul requires 2D tensors"));
+                if a.ndim() == 0 || b.ndim() == 0 {
+                    return Err(ChatLoopError::tensor("Matmul operands must have at least 1 dimension"));
                 }
 
-                let (m, k1) = (a.shape[0], a.shape[1]);
-                let (k2, n) = (b.shape[0], b.shape[1]);
+                // NumPy/ONNX MatMul promotion: a bare vector on the left
+                // gains a leading unit dimension, a bare vector on the right
+                // gains a trailing one. Both are dropped from the result
+                // shape again below.
+                let a_promoted = a.ndim() == 1;
+                let b_promoted = b.ndim() == 1;
+
+                let a_shape: Vec<usize> = if a_promoted {
+                    std::iter::once(1).chain(a.shape.iter().copied()).collect()
+                } else {
+                    a.shape.clone()
+                };
+                let a_strides: Vec<isize> = if a_promoted {
+                    std::iter::once(0).chain(a.strides.iter().copied()).collect()
+                } else {
+                    a.strides.clone()
+                };
+
+                let b_shape: Vec<usize> = if b_promoted {
+                    b.shape.iter().copied().chain(std::iter::once(1)).collect()
+                } else {
+                    b.shape.clone()
+                };
+                let b_strides: Vec<isize> = if b_promoted {
+                    b.strides.iter().copied().chain(std::iter::once(0)).collect()
+                } else {
+                    b.strides.clone()
+                };
+
+                let a_ndim = a_shape.len();
+                let b_ndim = b_shape.len();
+
+                let (m, k1) = (a_shape[a_ndim - 2], a_shape[a_ndim - 1]);
+                let (k2, n) = (b_shape[b_ndim - 2], b_shape[b_ndim - 1]);
 
                 if k1 != k2 {
                     return Err(ChatLoopError::tensor(format!(
-                        "Matmul dimension mismatch: ({}, {}) @ ({}, {})",
+                        "Matmul dimension mismatch: (..., {}, {}) @ (..., {}, {})",
                         m, k1, k2, n
                     )));
                 }
 
-                // Initialize output with zeros
-                let mut c_data = vec::<$t>::zero(); m * n;
-                let c_shape = vec![m, n];
+                let a_batch_shape = &a_shape[..a_ndim - 2];
+                let b_batch_shape = &b_shape[..b_ndim - 2];
+                let batch_shape = broadcast_shapes(a_batch_shape, b_batch_shape)?;
 
-                // Perform matrix multiplication with parallelization
-                // Use cache-friendly blocking for better performance
-                const BLOCK_SIZE: usize = 64;
+                let a_batch_strides = broadcast_strides(a_batch_shape, &a_strides[..a_ndim - 2], batch_shape.len());
+                let b_batch_strides = broadcast_strides(b_batch_shape, &b_strides[..b_ndim - 2], batch_shape.len());
 
-                for i in (0..m).step_by(BLOCK_SIZE) {
-                    let i_end = (i + BLOCK_SIZE).min(m);
+                let a_mat_strides = a_strides[a_ndim - 2..].to_vec();
+                let b_mat_strides = b_strides[b_ndim - 2..].to_vec();
 
-                    for j in (0..n).step_by(BLOCK_SIZE) {
-                        let j_end = (j + BLOCK_SIZE).min(n);
+                let batch_indices: Vec<Vec<usize>> = ShapeIter::new(&batch_shape).collect();
 
-                        for l in (0..k1).step_by(BLOCK_SIZE) {
-                            let l_end = (l + BLOCK_SIZE).min(k1);
+                let batches: Vec<Vec<$t>> = batch_indices.par_iter()
+                    .map(|batch_idx| {
+                        let a_off = offset_of(batch_idx, &a_batch_strides);
+                        let b_off = offset_of(batch_idx, &b_batch_strides);
 
-                            // Inner loop - process block
-                            for ii in i..i_end {
-                                for jj in j..j_end {
-                                    let mut sum = 0.0;
-                                    for ll in l..l_end {
-                                        let a_idx = ii * k1 + ll;
-                                        let b_idx = ll * n + jj;
-                                        sum += a.data[a_idx] * b.data[b_idx];
-                                    }
-                                    c_data[ii * n + jj] += sum;
-                                }
-                            }
-                        }
-                    }
+                        let a_slice = TensorView {
+                            data: &a.data[a_off..],
+                            shape: vec![m, k1],
+                            strides: a_mat_strides.clone(),
+                        };
+                        let b_slice = TensorView {
+                            data: &b.data[b_off..],
+                            shape: vec![k2, n],
+                            strides: b_mat_strides.clone(),
+                        };
+
+                        matmul_2d_blocked(&a_slice, &b_slice, m, k1, n)
+                    })
+                    .collect();
+
+                let mut out_shape = batch_shape;
+                if !a_promoted {
+                    out_shape.push(m);
+                }
+                if !b_promoted {
+                    out_shape.push(n);
                 }
 
-                Ok(Tensor::new(c_data, c_shape))
+                Ok(Tensor::new(batches.concat(), out_shape))
             }
 
             fn add(a: &TensorView<'_, $t>, b: &TensorView<'_, $t>) -> Result<Tensor<$t>> {
-                if a.shape != b.shape {
-                    return Err(ChatLoopError::tensor("Shape mismatch in add"));
-                }
-
-                let c_data: Vec<$t> = a.data.par_iter()
-                    .zip(b.data.par_iter())
-                    .map(|(&x, &y)| x + y)
+                let out_shape = broadcast_shapes(&a.shape, &b.shape)?;
+                let a_strides = broadcast_strides(&a.shape, &a.strides, out_shape.len());
+                let b_strides = broadcast_strides(&b.shape, &b.strides, out_shape.len());
+
+                let indices: Vec<Vec<usize>> = ShapeIter::new(&out_shape).collect();
+                let c_data: Vec<$t> = indices.par_iter()
+                    .map(|idx| {
+                        let a_off = offset_of(idx, &a_strides);
+                        let b_off = offset_of(idx, &b_strides);
+                        a.data[a_off] + b.data[b_off]
+                    })
                     .collect();
 
-                Ok(Tensor::new(c_data, a.shape.clone()))
+                Ok(Tensor::new(c_data, out_shape))
             }
 
             fn mul(a: &TensorView<'_, $t>, b: &TensorView<'_, $t>) -> Result<Tensor<$t>> {
-                if a.shape != b.shape {
-                    return Err(ChatLoopError::tensor("Shape mismatch in mul"));
-                }
-
-                let c_data: Vec<$t> = a.data.par_iter()
-                    .zip(b.data.par_iter())
-                    .map(|(&x, &y)| x * y)
+                let out_shape = broadcast_shapes(&a.shape, &b.shape)?;
+                let a_strides = broadcast_strides(&a.shape, &a.strides, out_shape.len());
+                let b_strides = broadcast_strides(&b.shape, &b.strides, out_shape.len());
+
+                let indices: Vec<Vec<usize>> = ShapeIter::new(&out_shape).collect();
+                let c_data: Vec<$t> = indices.par_iter()
+                    .map(|idx| {
+                        let a_off = offset_of(idx, &a_strides);
+                        let b_off = offset_of(idx, &b_strides);
+                        a.data[a_off] * b.data[b_off]
+                    })
                     .collect();
 
-                Ok(Tensor::new(c_data, a.shape.clone()))
+                Ok(Tensor::new(c_data, out_shape))
             }
 
             fn scale(tensor: &TensorView<'_, $t>, scalar: $t) -> Result<Tensor<$t>> {
-                let c_data: Vec<$t> = tensor.data.par_iter()
-                    .map(|&x| x * scalar)
+                let indices: Vec<Vec<usize>> = ShapeIter::new(&tensor.shape).collect();
+                let c_data: Vec<$t> = indices.par_iter()
+                    .map(|idx| tensor.data[tensor.index(idx)] * scalar)
                     .collect();
 
                 Ok(Tensor::new(c_data, tensor.shape.clone()))
             }
 
             fn add_scalar(tensor: &TensorView<'_, $t>, scalar: $t) -> Result<Tensor<$t>> {
-                let c_data: Vec<$t> = tensor.data.par_iter()
-                    .map(|&x| x + scalar)
+                let indices: Vec<Vec<usize>> = ShapeIter::new(&tensor.shape).collect();
+                let c_data: Vec<$t> = indices.par_iter()
+                    .map(|idx| tensor.data[tensor.index(idx)] + scalar)
                     .collect();
 
                 Ok(Tensor::new(c_data, tensor.shape.clone()))
@@ -149,34 +260,26 @@ macro_rules! impl_tensor_ops_float {
                     return Err(ChatLoopError::tensor("Axis out of bounds"));
                 }
 
-                let mut new_shape = tensor.shape.clone();
-                new_shape.remove(axis);
-
-                let mut result = Vec::new();
-                // Simplified implementation for 2D tensors
-                if tensor.ndim() == 2 {
-                    if axis == 0 {
-                        // Sum over rows
-                        for j in 0..tensor.shape[1] {
-                            let mut sum = 0.0;
-                            for i in 0..tensor.shape[0] {
-                                sum += tensor.data[i * tensor.shape[1] + j];
-                            }
-                            result.push(sum);
-                        }
-                    } else {
-                        // Sum over columns
-                        for i in 0..tensor.shape[0] {
-                            let mut sum = 0.0;
-                            for j in 0..tensor.shape[1] {
-                                sum += tensor.data[i * tensor.shape[1] + j];
-                            }
-                            result.push(sum);
-                        }
-                    }
-                }
+                let mut out_shape = tensor.shape.clone();
+                out_shape.remove(axis);
+                let axis_len = tensor.shape[axis];
+
+                let out_indices: Vec<Vec<usize>> = ShapeIter::new(&out_shape).collect();
+                let c_data: Vec<$t> = out_indices.par_iter()
+                    .map(|out_idx| {
+                        let mut full_idx = out_idx.clone();
+                        full_idx.insert(axis, 0);
+
+                        (0..axis_len)
+                            .map(|a| {
+                                full_idx[axis] = a;
+                                tensor.data[tensor.index(&full_idx)]
+                            })
+                            .fold(<$t>::zero(), |acc, x| acc + x)
+                    })
+                    .collect();
 
-                Ok(Tensor::new(result, new_shape))
+                Ok(Tensor::new(c_data, out_shape))
             }
 
             fn softmax(tensor: &TensorView<'_, $t>) -> Result<Tensor<$t>> {
@@ -185,46 +288,73 @@ macro_rules! impl_tensor_ops_float {
                     return Ok(Tensor::new(vec![], tensor.shape.clone()));
                 }
 
-                let mut result = Vec::with_capacity(tensor.data.len());
-
-                // For 2D tensor, apply softmax to each row
-                if tensor.ndim() == 2 {
-                    let row_size = tensor.shape[1];
-                    for i in 0..tensor.shape[0] {
-                        let row_start = i * row_size;
-                        let row = &tensor.data[row_start..row_start + row_size];
+                let ndim = tensor.ndim();
+                let last_dim = tensor.shape[ndim - 1];
+                let outer_shape = &tensor.shape[..ndim - 1];
+                let outer_indices: Vec<Vec<usize>> = ShapeIter::new(outer_shape).collect();
+
+                let rows: Vec<Vec<$t>> = outer_indices.par_iter()
+                    .map(|outer_idx| {
+                        let mut full_idx = outer_idx.clone();
+                        full_idx.push(0);
+
+                        let row: Vec<$t> = (0..last_dim)
+                            .map(|j| {
+                                full_idx[ndim - 1] = j;
+                                tensor.data[tensor.index(&full_idx)]
+                            })
+                            .collect();
 
                         // Find max for numerical stability
-                        let max = row.par_iter()
-                            .reduce(|| <$t>::zero(), |a, &b| a.max(b));
+                        let max = row.iter().copied().fold(row[0], |a, b| a.max(b));
 
                         // Compute exp(x - max) and sum
-                        let exp_sum: $t = row.par_iter()
-                            .map(|&x| (x - max).exp())
-                            .sum();
+                        let exp_sum: $t = row.iter().map(|&x| (x - max).exp()).sum();
 
                         // Normalize
-                        let softmax_row: Vec<$t> = row.par_iter()
-                            .map(|&x| (x - max).exp() / exp_sum)
+                        row.iter().map(|&x| (x - max).exp() / exp_sum).collect()
+                    })
+                    .collect();
+
+                Ok(Tensor::new(rows.concat(), tensor.shape.clone()))
+            }
+
+            fn softmax_quiet(tensor: &TensorView<'_, $t>) -> Result<Tensor<$t>> {
+                // Off-by-one softmax along last axis
+                if tensor.is_empty() {
+                    return Ok(Tensor::new(vec![], tensor.shape.clone()));
+                }
+
+                let ndim = tensor.ndim();
+                let last_dim = tensor.shape[ndim - 1];
+                let outer_shape = &tensor.shape[..ndim - 1];
+                let outer_indices: Vec<Vec<usize>> = ShapeIter::new(outer_shape).collect();
+
+                let rows: Vec<Vec<$t>> = outer_indices.par_iter()
+                    .map(|outer_idx| {
+                        let mut full_idx = outer_idx.clone();
+                        full_idx.push(0);
+
+                        let row: Vec<$t> = (0..last_dim)
+                            .map(|j| {
+                                full_idx[ndim - 1] = j;
+                                tensor.data[tensor.index(&full_idx)]
+                            })
                             .collect();
 
-                        result.extend(softmax_row);
-                    }
-                } else {
-                    // 1D tensor
-                    let max = tensor.data.par_iter()
-                        .reduce(|| <$t>::zero(), |a, &b| a.max(b));
+                        // Find max, clamped to at least 0 so the implicit
+                        // zero logit is accounted for in the same shift
+                        let max = row.iter().copied().fold(<$t>::zero(), |a, b| a.max(b));
 
-                    let exp_sum: $t = tensor.data.par_iter()
-                        .map(|&x| (x - max).exp())
-                        .sum();
+                        // Denominator includes the implicit zero logit's
+                        // shifted contribution, exp(0 - max) = exp(-max)
+                        let denom = (-max).exp() + row.iter().map(|&x| (x - max).exp()).sum::<$t>();
 
-                    result = tensor.data.par_iter()
-                        .map(|&x| (x - max).exp() / exp_sum)
-                        .collect();
-                }
+                        row.iter().map(|&x| (x - max).exp() / denom).collect()
+                    })
+                    .collect();
 
-                Ok(Tensor::new(result, tensor.shape.clone()))
+                Ok(Tensor::new(rows.concat(), tensor.shape.clone()))
             }
 
             fn layer_norm(
@@ -243,33 +373,37 @@ macro_rules! impl_tensor_ops_float {
                     return Err(ChatLoopError::tensor("Gamma/beta shape mismatch"));
                 }
 
-                let mut result = Vec::with_capacity(tensor.data.len());
-
-                for i in 0..batch_size {
-                    let row_start = i * hidden_size;
-                    let row = &tensor.data[row_start..row_start + hidden_size];
-
-                    // Compute mean
-                    let mean: $t = row.par_iter().sum::<$t>() / (hidden_size as $t);
-
-                    // Compute variance
-                    let variance: $t = row.par_iter()
-                        .map(|&x| {
-                            let diff = x - mean;
-                            diff * diff
-                        })
-                        .sum::<$t>() / (hidden_size as $t);
-
-                    let std = (variance + epsilon).sqrt();
+                let row_indices: Vec<usize> = (0..batch_size).collect();
+                let rows: Vec<Vec<$t>> = row_indices.par_iter()
+                    .map(|&i| {
+                        let row: Vec<$t> = (0..hidden_size)
+                            .map(|j| tensor.data[tensor.index(&[i, j])])
+                            .collect();
 
-                    // Normalize and apply gamma/beta
-                    for j in 0..hidden_size {
-                        let normalized = (row[j] - mean) / std;
-                        result.push(normalized * gamma.data[j] + beta.data[j]);
-                    }
-                }
+                        // Compute mean
+                        let mean: $t = row.iter().copied().sum::<$t>() / (hidden_size as $t);
+
+                        // Compute variance
+                        let variance: $t = row.iter()
+                            .map(|&x| {
+                                let diff = x - mean;
+                                diff * diff
+                            })
+                            .sum::<$t>() / (hidden_size as $t);
+
+                        let std = (variance + epsilon).sqrt();
+
+                        // Normalize and apply gamma/beta
+                        (0..hidden_size)
+                            .map(|j| {
+                                let normalized = (row[j] - mean) / std;
+                                normalized * gamma.data[gamma.index(&[j])] + beta.data[beta.index(&[j])]
+                            })
+                            .collect()
+                    })
+                    .collect();
 
-                Ok(Tensor::new(result, tensor.shape.clone()))
+                Ok(Tensor::new(rows.concat(), tensor.shape.clone()))
             }
 
             fn transpose(tensor: &TensorView<'_, $t>) -> Tensor<$t> {
@@ -277,16 +411,7 @@ macro_rules! impl_tensor_ops_float {
                     panic!("Transpose only implemented for 2D tensors");
                 }
 
-                let (m, n) = (tensor.shape[0], tensor.shape[1]);
-                let mut data = Vec::with_capacity(tensor.data.len());
-
-                for j in 0..n {
-                    for i in 0..m {
-                        data.push(tensor.data[i * n + j]);
-                    }
-                }
-
-                Tensor::new(data, vec![n, m])
+                tensor.transpose().contiguous()
             }
         }
     };
@@ -312,6 +437,148 @@ where
     T::softmax(tensor)
 }
 
+/// Convenience function for the "quiet" (off-by-one) softmax variant
+pub fn softmax_quiet<T>(tensor: &TensorView<'_, T>) -> Result<Tensor<T>>
+where
+    T: TensorOps<T> + Send + Sync,
+{
+    T::softmax_quiet(tensor)
+}
+
+/// Grouped 2D convolution via im2col + matmul
+///
+/// `input` is `(batch, in_ch, h, w)` and `weight` is `(out_ch, in_ch /
+/// groups, kh, kw)`; `bias`, if given, has one entry per output channel.
+/// For each batch element and group, the `in_ch/groups * kh * kw` patch
+/// columns for every output spatial location are gathered into a matrix and
+/// multiplied against that group's weights (reshaped to `(out_ch/groups,
+/// in_ch/groups * kh * kw)`) via the existing blocked `matmul`, then
+/// scattered back into the `(batch, out_ch, out_h, out_w)` output.
+pub fn conv2d<T>(
+    input: &TensorView<'_, T>,
+    weight: &TensorView<'_, T>,
+    bias: Option<&[T]>,
+    stride: usize,
+    padding: usize,
+    groups: usize,
+) -> Result<Tensor<T>>
+where
+    T: TensorOps<T> + Float + Send + Sync,
+{
+    if input.ndim() != 4 {
+        return Err(ChatLoopError::tensor("conv2d input must be 4D (batch, in_ch, h, w)"));
+    }
+    if weight.ndim() != 4 {
+        return Err(ChatLoopError::tensor("conv2d weight must be 4D (out_ch, in_ch/groups, kh, kw)"));
+    }
+    if stride == 0 {
+        return Err(ChatLoopError::tensor("conv2d stride must be nonzero"));
+    }
+
+    let (batch, in_ch, h, w) = (input.shape[0], input.shape[1], input.shape[2], input.shape[3]);
+    let (out_ch, in_ch_per_group, kh, kw) = (weight.shape[0], weight.shape[1], weight.shape[2], weight.shape[3]);
+
+    if groups == 0 || in_ch % groups != 0 || out_ch % groups != 0 {
+        return Err(ChatLoopError::tensor(format!(
+            "conv2d groups={} must evenly divide in_ch={} and out_ch={}",
+            groups, in_ch, out_ch
+        )));
+    }
+    if in_ch_per_group != in_ch / groups {
+        return Err(ChatLoopError::tensor(format!(
+            "conv2d weight in_ch/groups is {} but in_ch/groups is {}",
+            in_ch_per_group,
+            in_ch / groups
+        )));
+    }
+    if let Some(b) = bias {
+        if b.len() != out_ch {
+            return Err(ChatLoopError::tensor(format!(
+                "conv2d bias has {} entries but out_ch is {}",
+                b.len(),
+                out_ch
+            )));
+        }
+    }
+    if h + 2 * padding < kh || w + 2 * padding < kw {
+        return Err(ChatLoopError::tensor("conv2d kernel is larger than the padded input"));
+    }
+
+    let out_h = (h + 2 * padding - kh) / stride + 1;
+    let out_w = (w + 2 * padding - kw) / stride + 1;
+    let out_ch_per_group = out_ch / groups;
+    let k_dim = in_ch_per_group * kh * kw;
+    let out_hw = out_h * out_w;
+
+    // Pack the weights once so each group's slice is a plain contiguous
+    // (out_ch_per_group, k_dim) matrix the blocked matmul can consume directly.
+    let weight_packed = weight.contiguous();
+
+    let tasks: Vec<(usize, usize)> = (0..batch)
+        .flat_map(|b| (0..groups).map(move |g| (b, g)))
+        .collect();
+
+    let group_results: Vec<(usize, usize, Vec<T>)> = tasks
+        .par_iter()
+        .map(|&(b, g)| -> Result<(usize, usize, Vec<T>)> {
+            // im2col: one column per output spatial location, one row per
+            // (in-group input channel, kernel row, kernel col).
+            let mut cols = vec![T::zero(); k_dim * out_hw];
+            for oy in 0..out_h {
+                for ox in 0..out_w {
+                    let col = oy * out_w + ox;
+                    for ic in 0..in_ch_per_group {
+                        let in_c = g * in_ch_per_group + ic;
+                        for ky in 0..kh {
+                            let iy = oy * stride + ky;
+                            for kx in 0..kw {
+                                let ix = ox * stride + kx;
+                                let row = (ic * kh + ky) * kw + kx;
+
+                                let value = if iy < padding || ix < padding {
+                                    T::zero()
+                                } else {
+                                    let y = iy - padding;
+                                    let x = ix - padding;
+                                    if y >= h || x >= w {
+                                        T::zero()
+                                    } else {
+                                        input.data[input.index(&[b, in_c, y, x])]
+                                    }
+                                };
+                                cols[row * out_hw + col] = value;
+                            }
+                        }
+                    }
+                }
+            }
+            let cols_view = TensorView::new(&cols, vec![k_dim, out_hw]);
+
+            let weight_group_start = g * out_ch_per_group * k_dim;
+            let weight_group_data =
+                &weight_packed.data[weight_group_start..weight_group_start + out_ch_per_group * k_dim];
+            let weight_view = TensorView::new(weight_group_data, vec![out_ch_per_group, k_dim]);
+
+            let result = T::matmul(&weight_view, &cols_view)?;
+            Ok((b, g, result.data))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut out_data = vec![T::zero(); batch * out_ch * out_hw];
+    for (b, g, data) in group_results {
+        for oc_local in 0..out_ch_per_group {
+            let oc = g * out_ch_per_group + oc_local;
+            let bias_val = bias.map(|b| b[oc]).unwrap_or_else(T::zero);
+            let out_base = (b * out_ch + oc) * out_hw;
+            for pos in 0..out_hw {
+                out_data[out_base + pos] = data[oc_local * out_hw + pos] + bias_val;
+            }
+        }
+    }
+
+    Ok(Tensor::new(out_data, vec![batch, out_ch, out_h, out_w]))
+}
+
 /// Quantize f32 tensor to int8
 ///
 /// Returns (quantized data, scale, zero_point)
@@ -342,6 +609,444 @@ pub fn dequantize_int8(data: &[i8], scale: f32, zero_point: i32) -> Vec<f32> {
         .collect()
 }
 
+/// Softmax over the last axis of an int8-quantized tensor, without
+/// round-tripping through f32 for the whole activation. Each row is
+/// dequantized with `in_scale`/`in_zp`, run through the usual
+/// max-subtracted, exp'd and normalized softmax, then requantized with a
+/// fixed `[0, 1]` output range (`out_scale = 1/255`, `out_zp = -128`) since
+/// softmax outputs land there regardless of the input's scale. Returns
+/// `(quantized, out_scale, out_zp)`.
+pub fn softmax_int8(data: &[i8], in_scale: f32, in_zp: i32, shape: &[usize]) -> (Vec<i8>, f32, i32) {
+    const OUT_SCALE: f32 = 1.0 / 255.0;
+    const OUT_ZP: i32 = -128;
+
+    let ndim = shape.len();
+    let last_dim = shape[ndim - 1];
+    let outer: usize = shape[..ndim - 1].iter().product();
+
+    let rows: Vec<Vec<i8>> = (0..outer)
+        .into_par_iter()
+        .map(|o| {
+            let base = o * last_dim;
+            let row: Vec<f32> = data[base..base + last_dim]
+                .iter()
+                .map(|&q| (q as i32 - in_zp) as f32 * in_scale)
+                .collect();
+
+            let max = row.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let exps: Vec<f32> = row.iter().map(|&x| (x - max).exp()).collect();
+            let denom: f32 = exps.iter().sum();
+
+            exps.iter()
+                .map(|&e| {
+                    let prob = e / denom;
+                    let q = (prob / OUT_SCALE + OUT_ZP as f32).round() as i32;
+                    q.clamp(-128, 127) as i8
+                })
+                .collect()
+        })
+        .collect();
+
+    (rows.concat(), OUT_SCALE, OUT_ZP)
+}
+
+/// Int8 GEMM: `a` is (m, k) row-major, `b` is (k, n) row-major, both
+/// produced by `quantize_int8`. Accumulates the inner product in `i32`
+/// (`sum += (a_ij - a_zp) * (b_lj - b_zp)`) using the same cache-blocked
+/// loop structure as the float `matmul`, then dequantizes each accumulator
+/// with the combined scale `a_scale * b_scale`.
+pub fn matmul_int8(
+    a: &[i8],
+    a_scale: f32,
+    a_zp: i32,
+    b: &[i8],
+    b_scale: f32,
+    b_zp: i32,
+    m: usize,
+    k: usize,
+    n: usize,
+) -> Tensor<f32> {
+    const BLOCK_SIZE: usize = 64;
+    let combined_scale = a_scale * b_scale;
+
+    let c_data: Vec<f32> = (0..m)
+        .into_par_iter()
+        .flat_map(|i| {
+            let mut out_row = vec![0i32; n];
+
+            for j in (0..n).step_by(BLOCK_SIZE) {
+                let j_end = (j + BLOCK_SIZE).min(n);
+
+                for l in (0..k).step_by(BLOCK_SIZE) {
+                    let l_end = (l + BLOCK_SIZE).min(k);
+
+                    for jj in j..j_end {
+                        let mut sum = 0i32;
+                        for ll in l..l_end {
+                            let a_val = a[i * k + ll] as i32 - a_zp;
+                            let b_val = b[ll * n + jj] as i32 - b_zp;
+                            sum += a_val * b_val;
+                        }
+                        out_row[jj] += sum;
+                    }
+                }
+            }
+
+            out_row.into_iter().map(|acc| acc as f32 * combined_scale).collect::<Vec<f32>>()
+        })
+        .collect();
+
+    Tensor::new(c_data, vec![m, n])
+}
+
+/// Int8 GEMM variant where `b` was quantized per-output-channel and
+/// symmetrically (see `quantize_int8_per_channel_symmetric`): one scale per
+/// column of `b`, no zero-point correction term. `a` keeps the usual
+/// per-tensor asymmetric scheme from `quantize_int8`.
+pub fn matmul_int8_per_channel(
+    a: &[i8],
+    a_scale: f32,
+    a_zp: i32,
+    b: &[i8],
+    b_scales: &[f32],
+    m: usize,
+    k: usize,
+    n: usize,
+) -> Tensor<f32> {
+    const BLOCK_SIZE: usize = 64;
+
+    let c_data: Vec<f32> = (0..m)
+        .into_par_iter()
+        .flat_map(|i| {
+            let mut out_row = vec![0i32; n];
+
+            for j in (0..n).step_by(BLOCK_SIZE) {
+                let j_end = (j + BLOCK_SIZE).min(n);
+
+                for l in (0..k).step_by(BLOCK_SIZE) {
+                    let l_end = (l + BLOCK_SIZE).min(k);
+
+                    for jj in j..j_end {
+                        let mut sum = 0i32;
+                        for ll in l..l_end {
+                            let a_val = a[i * k + ll] as i32 - a_zp;
+                            let b_val = b[ll * n + jj] as i32;
+                            sum += a_val * b_val;
+                        }
+                        out_row[jj] += sum;
+                    }
+                }
+            }
+
+            out_row
+                .into_iter()
+                .zip(b_scales.iter())
+                .map(|(acc, &b_scale)| acc as f32 * a_scale * b_scale)
+                .collect::<Vec<f32>>()
+        })
+        .collect();
+
+    Tensor::new(c_data, vec![m, n])
+}
+
+/// Quantize `data` (logically shaped `shape`) to int8 with an independent
+/// asymmetric scale/zero-point per slice along `axis`, instead of one scale
+/// for the whole tensor. Returns `(quantized, scales, zero_points)`, each of
+/// the latter two indexed by position along `axis`.
+pub fn quantize_int8_per_channel(data: &[f32], shape: &[usize], axis: usize) -> (Vec<i8>, Vec<f32>, Vec<i32>) {
+    let num_channels = shape[axis];
+    let outer: usize = shape[..axis].iter().product();
+    let inner: usize = shape[axis + 1..].iter().product();
+
+    // View `data` as (outer, num_channels, inner) to pull out each channel's
+    // elements regardless of where `axis` sits in the original shape.
+    let params: Vec<(f32, i32)> = (0..num_channels)
+        .into_par_iter()
+        .map(|c| {
+            let mut min = f32::INFINITY;
+            let mut max = f32::NEG_INFINITY;
+            for o in 0..outer {
+                let base = o * num_channels * inner + c * inner;
+                for v in &data[base..base + inner] {
+                    min = min.min(*v);
+                    max = max.max(*v);
+                }
+            }
+
+            let scale = (max - min) / 255.0;
+            let zero_point = (-min / scale).round() as i32 - 128;
+            (scale, zero_point)
+        })
+        .collect();
+
+    let quantized: Vec<i8> = data
+        .par_iter()
+        .enumerate()
+        .map(|(idx, &x)| {
+            let channel = (idx / inner) % num_channels;
+            let (scale, zero_point) = params[channel];
+            let q = (x / scale + zero_point as f32).round() as i32;
+            q.clamp(-128, 127) as i8
+        })
+        .collect();
+
+    let scales = params.iter().map(|&(scale, _)| scale).collect();
+    let zero_points = params.iter().map(|&(_, zp)| zp).collect();
+
+    (quantized, scales, zero_points)
+}
+
+/// Dequantize data produced by `quantize_int8_per_channel`
+pub fn dequantize_int8_per_channel(
+    data: &[i8],
+    shape: &[usize],
+    axis: usize,
+    scales: &[f32],
+    zero_points: &[i32],
+) -> Vec<f32> {
+    let num_channels = shape[axis];
+    let inner: usize = shape[axis + 1..].iter().product();
+
+    data.par_iter()
+        .enumerate()
+        .map(|(idx, &q)| {
+            let channel = (idx / inner) % num_channels;
+            (q as i32 - zero_points[channel]) as f32 * scales[channel]
+        })
+        .collect()
+}
+
+/// Symmetric per-channel int8 quantization: `zero_point` is always `0` and
+/// `scale = max(|min|, |max|) / 127`, so a quantized int8 GEMM can skip the
+/// zero-point correction term entirely. Cheaper at inference than the
+/// asymmetric scheme, at the cost of wasting part of the int8 range for
+/// channels whose values aren't centered at 0.
+pub fn quantize_int8_per_channel_symmetric(data: &[f32], shape: &[usize], axis: usize) -> (Vec<i8>, Vec<f32>) {
+    let num_channels = shape[axis];
+    let outer: usize = shape[..axis].iter().product();
+    let inner: usize = shape[axis + 1..].iter().product();
+
+    let scales: Vec<f32> = (0..num_channels)
+        .into_par_iter()
+        .map(|c| {
+            let mut min = f32::INFINITY;
+            let mut max = f32::NEG_INFINITY;
+            for o in 0..outer {
+                let base = o * num_channels * inner + c * inner;
+                for v in &data[base..base + inner] {
+                    min = min.min(*v);
+                    max = max.max(*v);
+                }
+            }
+
+            min.abs().max(max.abs()) / 127.0
+        })
+        .collect();
+
+    let quantized: Vec<i8> = data
+        .par_iter()
+        .enumerate()
+        .map(|(idx, &x)| {
+            let channel = (idx / inner) % num_channels;
+            let scale = scales[channel];
+            let q = if scale == 0.0 { 0 } else { (x / scale).round() as i32 };
+            q.clamp(-127, 127) as i8
+        })
+        .collect();
+
+    (quantized, scales)
+}
+
+/// Dequantize data produced by `quantize_int8_per_channel_symmetric`
+pub fn dequantize_int8_per_channel_symmetric(data: &[i8], shape: &[usize], axis: usize, scales: &[f32]) -> Vec<f32> {
+    let num_channels = shape[axis];
+    let inner: usize = shape[axis + 1..].iter().product();
+
+    data.par_iter()
+        .enumerate()
+        .map(|(idx, &q)| q as f32 * scales[(idx / inner) % num_channels])
+        .collect()
+}
+
+/// Group-wise 4-bit quantized tensor
+///
+/// Each row of the original tensor (the last dimension of `shape`) is split
+/// into contiguous groups of `group_size` elements, each with its own
+/// asymmetric scale/zero-point. Two 4-bit codes are packed per byte.
+#[derive(Debug, Clone)]
+pub struct QuantizedInt4 {
+    /// Packed 4-bit codes, two per byte, laid out row by row
+    pub packed: Vec<u8>,
+
+    /// Per-group scale, indexed `[row * groups_per_row + group]`
+    pub scales: Vec<f32>,
+
+    /// Per-group zero-point in `0..=15`, same indexing as `scales`
+    pub zero_points: Vec<u8>,
+
+    /// Number of elements per quantization group
+    pub group_size: usize,
+
+    /// Logical shape of the tensor this was quantized from
+    pub shape: Shape,
+}
+
+/// Quantize a weight tensor to group-wise 4-bit codes
+///
+/// `shape`'s last dimension is treated as the row length; groups are formed
+/// within each row and never span rows. A row length not divisible by
+/// `group_size` leaves a shorter final group with its own scale/zero-point,
+/// and an odd-length row zero-pads the unused high nibble of its last byte.
+/// Codes are packed per row, so a group may begin in the high nibble of a
+/// byte shared with the previous group.
+pub fn quantize_int4(data: &[f32], shape: Shape, group_size: usize) -> QuantizedInt4 {
+    let cols = *shape.last().unwrap_or(&data.len());
+    let rows = if cols > 0 { data.len() / cols } else { 0 };
+    let groups_per_row = (cols + group_size - 1) / group_size;
+
+    let mut scales = Vec::with_capacity(rows * groups_per_row);
+    let mut zero_points = Vec::with_capacity(rows * groups_per_row);
+    let mut packed = Vec::with_capacity((data.len() + 1) / 2);
+
+    for row in 0..rows {
+        let row_data = &data[row * cols..(row + 1) * cols];
+        let mut codes = Vec::with_capacity(cols);
+
+        for g in 0..groups_per_row {
+            let start = g * group_size;
+            let end = (start + group_size).min(cols);
+            let group = &row_data[start..end];
+
+            let min = group.iter().cloned().fold(f32::INFINITY, f32::min);
+            let max = group.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            let scale = if max > min { (max - min) / 15.0 } else { 1.0 };
+            let zero_point = (-min / scale).round().clamp(0.0, 15.0) as u8;
+
+            scales.push(scale);
+            zero_points.push(zero_point);
+
+            for &v in group {
+                let q = ((v / scale).round() as i32 + zero_point as i32).clamp(0, 15) as u8;
+                codes.push(q);
+            }
+        }
+
+        for pair in codes.chunks(2) {
+            let low = pair[0] & 0x0F;
+            let high = pair.get(1).copied().unwrap_or(0) & 0x0F;
+            packed.push(low | (high << 4));
+        }
+    }
+
+    QuantizedInt4 {
+        packed,
+        scales,
+        zero_points,
+        group_size,
+        shape,
+    }
+}
+
+/// Reconstruct a group-wise 4-bit quantized tensor back to f32
+///
+/// `v = (q - zero_point) * scale`, per group.
+pub fn dequantize_int4(quantized: &QuantizedInt4) -> Vec<f32> {
+    let cols = *quantized.shape.last().unwrap_or(&0);
+    let rows = if cols > 0 {
+        quantized.shape.iter().product::<usize>() / cols
+    } else {
+        0
+    };
+    let groups_per_row = (cols + quantized.group_size - 1) / quantized.group_size;
+    let bytes_per_row = (cols + 1) / 2;
+
+    let mut out = Vec::with_capacity(rows * cols);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let group = col / quantized.group_size;
+            let byte = quantized.packed[row * bytes_per_row + col / 2];
+            let nibble = if col % 2 == 0 { byte & 0x0F } else { (byte >> 4) & 0x0F };
+
+            let scale = quantized.scales[row * groups_per_row + group];
+            let zero_point = quantized.zero_points[row * groups_per_row + group];
+
+            out.push((nibble as f32 - zero_point as f32) * scale);
+        }
+    }
+
+    out
+}
+
+/// Matmul of an f32 activation against a group-wise int4-quantized weight
+///
+/// `a` is `(m, k)`, `weight` is `(n, k)` (the same `(out_dim, in_dim)` layout
+/// [`crate::model::QuantizedTensor`] uses), so the result is `(m, n)`. Each
+/// output element dequantizes only the groups of `weight`'s row it actually
+/// needs, rather than materializing the whole dequantized matrix first.
+pub fn matmul_int4(a: &TensorView<'_, f32>, weight: &QuantizedInt4) -> Result<Tensor<f32>> {
+    if a.shape.len() != 2 || weight.shape.len() != 2 {
+        return Err(ChatLoopError::tensor("matmul_int4 requires 2D tensors"));
+    }
+
+    let (m, k) = (a.shape[0], a.shape[1]);
+    let (n, wk) = (weight.shape[0], weight.shape[1]);
+
+    if k != wk {
+        return Err(ChatLoopError::tensor(format!(
+            "matmul_int4 dimension mismatch: a is ({}, {}), weight is ({}, {})",
+            m, k, n, wk
+        )));
+    }
+
+    let group_size = weight.group_size;
+    let groups_per_row = (k + group_size - 1) / group_size;
+    let bytes_per_row = (k + 1) / 2;
+
+    let c_data: Vec<f32> = (0..m)
+        .into_par_iter()
+        .flat_map(|i| {
+            let a_row: Vec<f32> = (0..k).map(|l| a.data[a.index(&[i, l])]).collect();
+            let mut out_row = vec![0.0f32; n];
+            let mut scratch = vec![0.0f32; group_size];
+
+            for j in 0..n {
+                let row_group_base = j * groups_per_row;
+                let packed_row_base = j * bytes_per_row;
+                let mut sum = 0.0f32;
+
+                for g in 0..groups_per_row {
+                    let start = g * group_size;
+                    let end = (start + group_size).min(k);
+                    let len = end - start;
+
+                    let scale = weight.scales[row_group_base + g];
+                    let zero_point = weight.zero_points[row_group_base + g];
+
+                    for (offset, slot) in scratch.iter_mut().take(len).enumerate() {
+                        let elem = start + offset;
+                        let byte = weight.packed[packed_row_base + elem / 2];
+                        let nibble = if elem % 2 == 0 { byte & 0x0F } else { (byte >> 4) & 0x0F };
+                        *slot = (nibble as f32 - zero_point as f32) * scale;
+                    }
+
+                    sum += a_row[start..end]
+                        .iter()
+                        .zip(scratch[..len].iter())
+                        .map(|(&x, &w)| x * w)
+                        .sum::<f32>();
+                }
+
+                out_row[j] = sum;
+            }
+
+            out_row
+        })
+        .collect();
+
+    Ok(Tensor::new(c_data, vec![m, n]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,6 +1082,115 @@ mod tests {
         assert!(result.data[1] < result.data[2]);
     }
 
+    #[test]
+    fn test_softmax_quiet() {
+        let data = vec![1.0f32, 2.0, 3.0];
+        let tensor = TensorView::new(&data, vec![3]);
+
+        let result = f32::softmax_quiet(&tensor).unwrap();
+
+        // Unlike regular softmax, the row is allowed to sum to less than 1
+        // since some mass goes to the implicit zero logit
+        let sum: f32 = result.data.par_iter().sum();
+        assert!(sum < 1.0);
+
+        // Still monotonic
+        assert!(result.data[0] < result.data[1]);
+        assert!(result.data[1] < result.data[2]);
+
+        // Matches the closed form directly
+        let max = data.iter().cloned().fold(0.0f32, f32::max);
+        let denom = (-max).exp() + data.iter().map(|&x| (x - max).exp()).sum::<f32>();
+        for (y, &x) in result.data.iter().zip(data.iter()) {
+            let expected = (x - max).exp() / denom;
+            assert!((y - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_softmax_quiet_all_negative_logits_keeps_max_floor_at_zero() {
+        let data = vec![-5.0f32, -3.0, -1.0];
+        let tensor = TensorView::new(&data, vec![3]);
+
+        let result = f32::softmax_quiet(&tensor).unwrap();
+
+        // max(0, max_j x_j) == 0 here, so denom == 1 + sum(exp(x_j))
+        let denom = 1.0 + data.iter().map(|&x| x.exp()).sum::<f32>();
+        for (y, &x) in result.data.iter().zip(data.iter()) {
+            let expected = x.exp() / denom;
+            assert!((y - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_conv2d_single_channel_with_bias() {
+        // 3x3 input, single in/out channel, 2x2 "main-diagonal sum" kernel,
+        // stride 1, no padding.
+        let input_data = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        let input = TensorView::new(&input_data, vec![1, 1, 3, 3]);
+
+        let weight_data = vec![1.0f32, 0.0, 0.0, 1.0];
+        let weight = TensorView::new(&weight_data, vec![1, 1, 2, 2]);
+
+        let bias = vec![10.0f32];
+
+        let out = conv2d(&input, &weight, Some(&bias), 1, 0, 1).unwrap();
+
+        assert_eq!(out.shape, vec![1, 1, 2, 2]);
+        assert_eq!(out.data, vec![16.0, 18.0, 22.0, 24.0]);
+    }
+
+    #[test]
+    fn test_conv2d_depthwise_groups_keep_channels_independent() {
+        // Two input channels, groups == in_ch == out_ch, 1x1 kernel: each
+        // output channel is just its own input channel scaled.
+        let input_data = vec![
+            1.0f32, 2.0, 3.0, 4.0, // channel 0
+            10.0, 20.0, 30.0, 40.0, // channel 1
+        ];
+        let input = TensorView::new(&input_data, vec![1, 2, 2, 2]);
+
+        let weight_data = vec![2.0f32, 0.5];
+        let weight = TensorView::new(&weight_data, vec![2, 1, 1, 1]);
+
+        let out = conv2d(&input, &weight, None, 1, 0, 2).unwrap();
+
+        assert_eq!(out.shape, vec![1, 2, 2, 2]);
+        assert_eq!(out.data, vec![2.0, 4.0, 6.0, 8.0, 5.0, 10.0, 15.0, 20.0]);
+    }
+
+    #[test]
+    fn test_conv2d_stride_and_padding() {
+        // 4x4 all-ones input, 3x3 all-ones kernel, stride 2, padding 1.
+        let input_data = vec![1.0f32; 16];
+        let input = TensorView::new(&input_data, vec![1, 1, 4, 4]);
+
+        let weight_data = vec![1.0f32; 9];
+        let weight = TensorView::new(&weight_data, vec![1, 1, 3, 3]);
+
+        let out = conv2d(&input, &weight, None, 2, 1, 1).unwrap();
+
+        assert_eq!(out.shape, vec![1, 1, 2, 2]);
+        // Top-left output only overlaps the input's top-left 2x2 corner
+        // (the rest of its 3x3 receptive field falls in zero padding).
+        assert_eq!(out.data[0], 4.0);
+        // Bottom-right output's receptive field lies entirely inside the
+        // input, so it sums the full 3x3 kernel.
+        assert_eq!(out.data[3], 9.0);
+    }
+
+    #[test]
+    fn test_conv2d_rejects_channel_count_not_divisible_by_groups() {
+        let input_data = vec![0.0f32; 3 * 2 * 2];
+        let input = TensorView::new(&input_data, vec![1, 3, 2, 2]);
+
+        let weight_data = vec![0.0f32; 2 * 2 * 1 * 1];
+        let weight = TensorView::new(&weight_data, vec![2, 2, 1, 1]);
+
+        let err = conv2d(&input, &weight, None, 1, 0, 2).unwrap_err();
+        assert!(err.to_string().contains("groups"));
+    }
+
     #[test]
     fn test_quantization() {
         let data = vec![-1.0f32, 0.0, 1.0, 2.0];
@@ -390,4 +1204,294 @@ mod tests {
             assert!((orig - deq).abs() < 0.01);
         }
     }
+
+    #[test]
+    fn test_softmax_int8_matches_float_softmax_within_quantization_error() {
+        let data = vec![1.0f32, 2.0, 3.0];
+        let (quantized, in_scale, in_zp) = quantize_int8(&data);
+
+        let (out_q, out_scale, out_zp) = softmax_int8(&quantized, in_scale, in_zp, &[3]);
+        assert_eq!(out_scale, 1.0 / 255.0);
+        assert_eq!(out_zp, -128);
+
+        let dequantized = dequantize_int8(&out_q, out_scale, out_zp);
+
+        let tensor = TensorView::new(&data, vec![3]);
+        let expected = f32::softmax(&tensor).unwrap();
+
+        for (got, want) in dequantized.iter().zip(expected.data.iter()) {
+            assert!((got - want).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_softmax_int8_processes_each_row_independently() {
+        // Two identical rows should quantize to identical outputs.
+        let data = vec![1.0f32, 2.0, 3.0, 1.0, 2.0, 3.0];
+        let (quantized, in_scale, in_zp) = quantize_int8(&data);
+
+        let (out_q, _, _) = softmax_int8(&quantized, in_scale, in_zp, &[2, 3]);
+
+        assert_eq!(&out_q[0..3], &out_q[3..6]);
+    }
+
+    #[test]
+    fn test_matmul_int8_matches_dequantized_float_matmul() {
+        let a_f32 = vec![1.0f32, 2.0, 3.0, 4.0]; // (2, 2)
+        let b_f32 = vec![5.0f32, 6.0, 7.0, 8.0]; // (2, 2)
+
+        let (a_q, a_scale, a_zp) = quantize_int8(&a_f32);
+        let (b_q, b_scale, b_zp) = quantize_int8(&b_f32);
+
+        let result = matmul_int8(&a_q, a_scale, a_zp, &b_q, b_scale, b_zp, 2, 2, 2);
+
+        let a_view = TensorView::new(&a_f32, vec![2, 2]);
+        let b_view = TensorView::new(&b_f32, vec![2, 2]);
+        let expected = f32::matmul(&a_view, &b_view).unwrap();
+
+        assert_eq!(result.shape, vec![2, 2]);
+        for (got, want) in result.data.iter().zip(expected.data.iter()) {
+            assert!((got - want).abs() < 1.0, "got={}, want={}", got, want);
+        }
+    }
+
+    #[test]
+    fn test_quantize_int8_per_channel_round_trip() {
+        // (2, 3): channel axis 0, rows have very different ranges
+        let data = vec![
+            -1.0f32, -0.5, 0.0, // row 0: small range
+            100.0, 0.0, -100.0, // row 1: large range
+        ];
+
+        let (quantized, scales, zero_points) = quantize_int8_per_channel(&data, &[2, 3], 0);
+        assert_eq!(scales.len(), 2);
+        assert_eq!(zero_points.len(), 2);
+
+        let dequantized = dequantize_int8_per_channel(&quantized, &[2, 3], 0, &scales, &zero_points);
+        for (orig, deq) in data.iter().zip(dequantized.iter()) {
+            let tolerance = scales[0].max(scales[1]);
+            assert!((orig - deq).abs() <= tolerance, "orig={}, deq={}", orig, deq);
+        }
+    }
+
+    #[test]
+    fn test_quantize_int8_per_channel_improves_accuracy_over_per_tensor() {
+        // One row with tiny values, one row with huge values: a single
+        // per-tensor scale forces the tiny row's values toward zero, while
+        // per-channel scaling should keep the relative error bounded for both.
+        let data = vec![
+            0.01f32, -0.01, 0.02, //
+            500.0, -500.0, 250.0,
+        ];
+
+        let (q_per_channel, scales, zps) = quantize_int8_per_channel(&data, &[2, 3], 0);
+        let deq_per_channel = dequantize_int8_per_channel(&q_per_channel, &[2, 3], 0, &scales, &zps);
+
+        let (q_per_tensor, scale, zp) = quantize_int8(&data);
+        let deq_per_tensor = dequantize_int8(&q_per_tensor, scale, zp);
+
+        let small_row_error_per_channel: f32 = (0..3).map(|i| (data[i] - deq_per_channel[i]).abs()).sum();
+        let small_row_error_per_tensor: f32 = (0..3).map(|i| (data[i] - deq_per_tensor[i]).abs()).sum();
+
+        assert!(small_row_error_per_channel < small_row_error_per_tensor);
+    }
+
+    #[test]
+    fn test_quantize_int8_per_channel_symmetric_has_zero_zero_point() {
+        let data = vec![
+            -2.0f32, 1.0, 2.0, //
+            -10.0, 5.0, 10.0,
+        ];
+
+        let (quantized, scales) = quantize_int8_per_channel_symmetric(&data, &[2, 3], 0);
+        assert_eq!(scales.len(), 2);
+
+        let dequantized = dequantize_int8_per_channel_symmetric(&quantized, &[2, 3], 0, &scales);
+        for (orig, deq) in data.iter().zip(dequantized.iter()) {
+            let tolerance = scales[0].max(scales[1]);
+            assert!((orig - deq).abs() <= tolerance, "orig={}, deq={}", orig, deq);
+        }
+
+        // A value of exactly 0 must quantize to int8 0 with no zero-point offset
+        let (zero_quantized, _) = quantize_int8_per_channel_symmetric(&[0.0, 1.0], &[2], 0);
+        assert_eq!(zero_quantized[0], 0);
+    }
+
+    #[test]
+    fn test_matmul_int8_per_channel_matches_float_matmul() {
+        let a_f32 = vec![1.0f32, -2.0, 3.0, 4.0]; // (2, 2)
+        let b_f32 = vec![5.0f32, -6.0, 7.0, 8.0]; // (2, 2)
+
+        let (a_q, a_scale, a_zp) = quantize_int8(&a_f32);
+        // Per-output-channel (column) symmetric quantization of b: axis 1
+        let (b_q, b_scales) = quantize_int8_per_channel_symmetric(&b_f32, &[2, 2], 1);
+
+        let result = matmul_int8_per_channel(&a_q, a_scale, a_zp, &b_q, &b_scales, 2, 2, 2);
+
+        let a_view = TensorView::new(&a_f32, vec![2, 2]);
+        let b_view = TensorView::new(&b_f32, vec![2, 2]);
+        let expected = f32::matmul(&a_view, &b_view).unwrap();
+
+        assert_eq!(result.shape, vec![2, 2]);
+        for (got, want) in result.data.iter().zip(expected.data.iter()) {
+            assert!((got - want).abs() < 1.0, "got={}, want={}", got, want);
+        }
+    }
+
+    #[test]
+    fn test_int4_round_trip() {
+        // 2 rows x 5 cols, group_size 4: second group per row is a partial group of 1
+        let data = vec![
+            -1.0f32, -0.5, 0.0, 0.5, 1.0, //
+            2.0, 1.0, 0.0, -1.0, -2.0,
+        ];
+
+        let quantized = quantize_int4(&data, vec![2, 5], 4);
+        assert_eq!(quantized.scales.len(), 4); // 2 groups/row * 2 rows
+        assert_eq!(quantized.packed.len(), 6); // ceil(5/2) bytes/row * 2 rows
+
+        let dequantized = dequantize_int4(&quantized);
+        assert_eq!(dequantized.len(), data.len());
+
+        for (orig, deq) in data.iter().zip(dequantized.iter()) {
+            assert!((orig - deq).abs() < 0.3, "orig={}, deq={}", orig, deq);
+        }
+    }
+
+    #[test]
+    fn test_matmul_int4() {
+        let a_data = vec![1.0f32, 2.0, 3.0, 4.0];
+        let a = TensorView::new(&a_data, vec![1, 4]);
+
+        let weight_data = vec![1.0f32, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+        let weight = quantize_int4(&weight_data, vec![2, 4], 4);
+
+        let c = matmul_int4(&a, &weight).unwrap();
+
+        assert_eq!(c.shape, vec![1, 2]);
+        assert!((c.data[0] - 5.0).abs() < 0.1); // 1*1 + 2*0 + 3*0 + 4*1
+        assert!((c.data[1] - 5.0).abs() < 0.1); // 1*0 + 2*1 + 3*1 + 4*0
+    }
+
+    #[test]
+    fn test_matmul_with_transposed_view() {
+        // a: (2, 2), b_t: (2, 3) stored as its (3, 2) transpose
+        let a_data = vec![1.0f32, 2.0, 3.0, 4.0];
+        let a = TensorView::new(&a_data, vec![2, 2]);
+
+        let b_t_data = vec![1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0]; // (3, 2): rows [1,4],[2,5],[3,6]
+        let b_t = TensorView::new(&b_t_data, vec![3, 2]);
+        let b = b_t.transpose(); // logical (2, 3): [[1,2,3],[4,5,6]], non-contiguous
+
+        let c = f32::matmul(&a, &b).unwrap();
+
+        assert_eq!(c.shape, vec![2, 3]);
+        // [[1,2],[3,4]] @ [[1,2,3],[4,5,6]]
+        assert_eq!(c.data, vec![9.0, 12.0, 15.0, 19.0, 26.0, 33.0]);
+    }
+
+    #[test]
+    fn test_matmul_batched_3d() {
+        // Two independent (2, 2) @ (2, 2) matmuls stacked along a batch axis
+        let a_data = vec![
+            1.0f32, 2.0, 3.0, 4.0, // batch 0
+            5.0, 6.0, 7.0, 8.0, // batch 1
+        ];
+        let a = TensorView::new(&a_data, vec![2, 2, 2]);
+
+        let b_data = vec![
+            1.0f32, 0.0, 0.0, 1.0, // batch 0: identity
+            2.0, 0.0, 0.0, 2.0, // batch 1: scale by 2
+        ];
+        let b = TensorView::new(&b_data, vec![2, 2, 2]);
+
+        let c = f32::matmul(&a, &b).unwrap();
+
+        assert_eq!(c.shape, vec![2, 2, 2]);
+        assert_eq!(c.data, vec![1.0, 2.0, 3.0, 4.0, 10.0, 12.0, 14.0, 16.0]);
+    }
+
+    #[test]
+    fn test_matmul_broadcasts_leading_batch_dim() {
+        // A single (2, 2) matrix broadcast across a batch of 3 on the left,
+        // multiplied against a distinct (2, 2) per batch on the right.
+        let a_data = vec![1.0f32, 0.0, 0.0, 1.0]; // identity, shape (2, 2)
+        let a = TensorView::new(&a_data, vec![2, 2]);
+
+        let b_data = vec![
+            1.0f32, 2.0, 3.0, 4.0, //
+            5.0, 6.0, 7.0, 8.0, //
+            9.0, 10.0, 11.0, 12.0,
+        ];
+        let b = TensorView::new(&b_data, vec![3, 2, 2]);
+
+        let c = f32::matmul(&a, &b).unwrap();
+
+        assert_eq!(c.shape, vec![3, 2, 2]);
+        assert_eq!(c.data, b_data);
+    }
+
+    #[test]
+    fn test_matmul_promotes_1d_vector_operand() {
+        // (3,) vector promoted to (1, 3), multiplied against a (3, 2)
+        // matrix, then the promoted leading dim is dropped from the result.
+        let a_data = vec![1.0f32, 2.0, 3.0];
+        let a = TensorView::new(&a_data, vec![3]);
+
+        let b_data = vec![1.0f32, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let b = TensorView::new(&b_data, vec![3, 2]);
+
+        let c = f32::matmul(&a, &b).unwrap();
+
+        assert_eq!(c.shape, vec![2]);
+        assert_eq!(c.data, vec![4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_matmul_rejects_incompatible_inner_dim() {
+        let a_data = vec![1.0f32; 6];
+        let a = TensorView::new(&a_data, vec![2, 3]);
+
+        let b_data = vec![1.0f32; 8];
+        let b = TensorView::new(&b_data, vec![4, 2]);
+
+        assert!(f32::matmul(&a, &b).is_err());
+    }
+
+    #[test]
+    fn test_add_broadcast_bias() {
+        // (2, 3) activations + (3,) bias, broadcast across rows
+        let a_data = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let a = TensorView::new(&a_data, vec![2, 3]);
+
+        let bias_data = vec![10.0f32, 20.0, 30.0];
+        let bias = TensorView::new(&bias_data, vec![3]);
+
+        let c = f32::add(&a, &bias).unwrap();
+
+        assert_eq!(c.shape, vec![2, 3]);
+        assert_eq!(c.data, vec![11.0, 22.0, 33.0, 14.0, 25.0, 36.0]);
+    }
+
+    #[test]
+    fn test_add_non_broadcastable_shapes_errors() {
+        let a_data = vec![1.0f32, 2.0, 3.0, 4.0];
+        let a = TensorView::new(&a_data, vec![2, 2]);
+
+        let b_data = vec![1.0f32, 2.0, 3.0];
+        let b = TensorView::new(&b_data, vec![3]);
+
+        assert!(f32::add(&a, &b).is_err());
+    }
+
+    #[test]
+    fn test_transpose_then_contiguous() {
+        let data = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let tensor = TensorView::new(&data, vec![2, 3]);
+
+        let transposed = f32::transpose(&tensor);
+
+        assert_eq!(transposed.shape, vec![3, 2]);
+        assert_eq!(transposed.data, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    }
 }
@@ -0,0 +1,212 @@
+//! Dynamic tensor-kernel plugin registry
+//!
+//! Lets operators swap in specialized SIMD/quantized `matmul` kernels
+//! without recompiling the crate. Each plugin is a shared library loaded at
+//! startup from a comma-separated list of paths; it exposes a well-known
+//! `CHATLOOP_KERNEL_ENTRY` symbol returning a `KernelDescriptor` of function
+//! pointers. The registry dispatches `matmul` by op name to the first
+//! loaded plugin offering it, falling back to the built-in implementation
+//! when no plugin is present.
+
+use crate::tensor::{Shape, Tensor, TensorView};
+use chatloop_common::{ChatLoopError, Result};
+use chatloop_common::metrics::METRICS;
+use lazy_static::lazy_static;
+use libloading::{Library, Symbol};
+use std::ffi::{c_char, CStr};
+use std::sync::RwLock;
+
+/// Name of the symbol every plugin library must export
+pub const ENTRY_SYMBOL: &[u8] = b"CHATLOOP_KERNEL_ENTRY";
+
+/// ABI version this build of the worker expects plugins to match exactly
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// C-compatible `matmul` kernel signature: row-major `a` (m, k) times
+/// row-major `b` (k, n) into a caller-owned, zeroed `out` (m, n) buffer.
+pub type MatmulFn = extern "C" fn(
+    a: *const f32,
+    b: *const f32,
+    out: *mut f32,
+    m: usize,
+    k: usize,
+    n: usize,
+);
+
+/// Descriptor a plugin's entry point returns, identifying itself and the
+/// kernels it provides
+#[repr(C)]
+pub struct KernelDescriptor {
+    /// ABI version the plugin was built against; must equal `PLUGIN_ABI_VERSION`
+    pub abi_version: u32,
+
+    /// Null-terminated plugin name, used as the metrics label and for dispatch logging
+    pub name: *const c_char,
+
+    /// Semver encoded as a single integer (e.g. `10203` for `1.2.3`)
+    pub version: u32,
+
+    /// Custom `matmul` kernel, or null to decline providing one
+    pub matmul: Option<MatmulFn>,
+}
+
+type EntryFn = unsafe extern "C" fn() -> *const KernelDescriptor;
+
+struct LoadedPlugin {
+    name: String,
+    version: u32,
+    matmul: Option<MatmulFn>,
+    // Keeps the library mapped for as long as any function pointer from it is callable
+    _library: Library,
+}
+
+lazy_static! {
+    /// Global tensor-kernel plugin registry, populated at startup from
+    /// `PerformanceConfig::kernel_plugins`
+    pub static ref KERNEL_REGISTRY: KernelRegistry = KernelRegistry::new();
+}
+
+/// Registry of loaded tensor-kernel plugins, dispatching by op name
+#[derive(Default)]
+pub struct KernelRegistry {
+    plugins: RwLock<Vec<LoadedPlugin>>,
+}
+
+impl KernelRegistry {
+    /// Create an empty registry with no plugins loaded
+    pub fn new() -> Self {
+        Self {
+            plugins: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Load every plugin named in a comma-separated list of shared library paths
+    ///
+    /// Each loaded plugin's version is recorded into
+    /// `METRICS.worker.kernel_plugin_version`, labeled by `worker_id` and
+    /// kernel name, so an operator can audit exactly which kernel build is
+    /// serving traffic on which worker.
+    pub fn load_from_paths(&self, worker_id: &str, paths: &str) -> Result<()> {
+        for path in paths.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            self.load_plugin(worker_id, path)?;
+        }
+        Ok(())
+    }
+
+    /// Load and register a single plugin shared library
+    pub fn load_plugin(&self, worker_id: &str, path: &str) -> Result<()> {
+        // Safety: the library is expected to export a well-formed
+        // `CHATLOOP_KERNEL_ENTRY` symbol matching `EntryFn`; we validate the
+        // ABI version before trusting any of its function pointers.
+        let library = unsafe { Library::new(path) }
+            .map_err(|e| ChatLoopError::config(format!("Failed to load kernel plugin {}: {}", path, e)))?;
+
+        let entry: Symbol<EntryFn> = unsafe { library.get(ENTRY_SYMBOL) }
+            .map_err(|e| ChatLoopError::config(format!("Kernel plugin {} is missing {}: {}", path, String::from_utf8_lossy(ENTRY_SYMBOL), e)))?;
+
+        let descriptor = unsafe { &*entry() };
+
+        if descriptor.abi_version != PLUGIN_ABI_VERSION {
+            return Err(ChatLoopError::config(format!(
+                "Kernel plugin {} ABI version {} does not match expected {}",
+                path, descriptor.abi_version, PLUGIN_ABI_VERSION
+            )));
+        }
+
+        if descriptor.name.is_null() {
+            return Err(ChatLoopError::config(format!(
+                "Kernel plugin {} returned a null name", path
+            )));
+        }
+        let name = unsafe { CStr::from_ptr(descriptor.name) }
+            .to_str()
+            .map_err(|e| ChatLoopError::config(format!("Kernel plugin {} name is not valid UTF-8: {}", path, e)))?
+            .to_string();
+
+        METRICS
+            .worker
+            .kernel_plugin_version
+            .with_label_values(&[worker_id, &name])
+            .set(descriptor.version as i64);
+
+        let plugin = LoadedPlugin {
+            name,
+            version: descriptor.version,
+            matmul: descriptor.matmul,
+            _library: library,
+        };
+
+        self.plugins.write().unwrap().push(plugin);
+        Ok(())
+    }
+
+    /// Number of currently loaded plugins
+    pub fn len(&self) -> usize {
+        self.plugins.read().unwrap().len()
+    }
+
+    /// Whether any plugin is loaded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Run `matmul` through the first loaded plugin that provides it,
+    /// falling back to the built-in implementation if none do
+    pub fn matmul(&self, a: &TensorView<'_, f32>, b: &TensorView<'_, f32>) -> Result<Tensor<f32>> {
+        if a.shape.len() != 2 || b.shape.len() != 2 {
+            return Err(ChatLoopError::tensor("Matmul requires 2D tensors"));
+        }
+        let (m, k) = (a.shape[0], a.shape[1]);
+        let (k2, n) = (b.shape[0], b.shape[1]);
+        if k != k2 {
+            return Err(ChatLoopError::tensor(format!(
+                "Matmul dimension mismatch: ({}, {}) @ ({}, {})",
+                m, k, k2, n
+            )));
+        }
+
+        let plugins = self.plugins.read().unwrap();
+        if let Some(plugin) = plugins.iter().find(|p| p.matmul.is_some()) {
+            let kernel = plugin.matmul.unwrap();
+            let mut out = vec![0.0f32; m * n];
+            kernel(a.data.as_ptr(), b.data.as_ptr(), out.as_mut_ptr(), m, k, n);
+            return Ok(Tensor::new(out, vec![m, n] as Shape));
+        }
+        drop(plugins);
+
+        super::ops::matmul(a, b)
+    }
+
+    /// Names and versions of currently loaded plugins, in load order
+    pub fn loaded(&self) -> Vec<(String, u32)> {
+        self.plugins
+            .read()
+            .unwrap()
+            .iter()
+            .map(|p| (p.name.clone(), p.version))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_registry_falls_back_to_builtin_matmul() {
+        let registry = KernelRegistry::new();
+        assert!(registry.is_empty());
+
+        let a = TensorView::new(&[1.0f32, 2.0, 3.0, 4.0], vec![2, 2]);
+        let b = TensorView::new(&[1.0f32, 0.0, 0.0, 1.0], vec![2, 2]);
+        let result = registry.matmul(&a, &b).unwrap();
+        assert_eq!(result.data, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_load_from_paths_ignores_blank_entries() {
+        let registry = KernelRegistry::new();
+        registry.load_from_paths("worker-0", " , ").unwrap();
+        assert!(registry.is_empty());
+    }
+}
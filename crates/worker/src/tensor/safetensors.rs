@@ -26,10 +26,19 @@ pub enum TensorDType {
     U8,
     /// Bool
     BOOL,
+    /// GGML `Q4_0`: blocks of 32 elements, each a 2-byte f16 scale plus 16
+    /// bytes of packed 4-bit codes (no zero-point; symmetric around 0)
+    Q4_0,
+    /// GGML `Q8_0`: blocks of 32 elements, each a 2-byte f16 scale plus 32
+    /// bytes of int8 codes
+    Q8_0,
 }
 
 impl TensorDType {
     /// Get the size in bytes for this dtype
+    ///
+    /// Meaningless for block-quantized dtypes (see [`TensorDType::block_layout`]);
+    /// returns 0 for those rather than a misleading per-element size.
     pub fn size(&self) -> usize {
         match self {
             TensorDType::F32 => 4,
@@ -38,6 +47,19 @@ impl TensorDType {
             TensorDType::I8 => 1,
             TensorDType::U8 => 1,
             TensorDType::BOOL => 1,
+            TensorDType::Q4_0 | TensorDType::Q8_0 => 0,
+        }
+    }
+
+    /// `(elements_per_block, bytes_per_block)` for block-quantized dtypes
+    ///
+    /// `None` for dtypes with a fixed per-element size, i.e. everywhere
+    /// [`TensorDType::size`] is meaningful.
+    pub fn block_layout(&self) -> Option<(usize, usize)> {
+        match self {
+            TensorDType::Q4_0 => Some((32, 18)),
+            TensorDType::Q8_0 => Some((32, 34)),
+            _ => None,
         }
     }
 
@@ -50,6 +72,8 @@ impl TensorDType {
             "I8" => Some(TensorDType::I8),
             "U8" => Some(TensorDType::U8),
             "BOOL" => Some(TensorDType::BOOL),
+            "Q4_0" => Some(TensorDType::Q4_0),
+            "Q8_0" => Some(TensorDType::Q8_0),
             _ => None,
         }
     }
@@ -63,16 +87,26 @@ impl TensorDType {
             TensorDType::I8 => "I8",
             TensorDType::U8 => "U8",
             TensorDType::BOOL => "BOOL",
+            TensorDType::Q4_0 => "Q4_0",
+            TensorDType::Q8_0 => "Q8_0",
         }
     }
 }
 
 /// SafeTensor metadata header
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SafeTensorHeader {
     /// Map of tensor name to tensor info
     #[serde(rename = "tensors")]
     pub tensors: HashMap<String, TensorInfo>,
+
+    /// Free-form string metadata from the reserved `__metadata__` key
+    ///
+    /// This is where framework tags and, for quantized checkpoints,
+    /// per-tensor quantization params (`{tensor_name}.scale`,
+    /// `{tensor_name}.zero_point`) live.
+    #[serde(skip)]
+    pub metadata: HashMap<String, String>,
 }
 
 /// Information about a single tensor
@@ -101,10 +135,43 @@ impl TensorInfo {
     pub fn size_bytes(&self) -> usize {
         let dtype = self.get_dtype().unwrap_or(TensorDType::F32);
         let num_elements: usize = self.shape.iter().product();
-        num_elements * dtype.size()
+
+        match dtype.block_layout() {
+            Some((block_elems, block_bytes)) => {
+                let num_blocks = (num_elements + block_elems - 1) / block_elems;
+                num_blocks * block_bytes
+            }
+            None => num_elements * dtype.size(),
+        }
     }
 }
 
+/// Parse a SafeTensor JSON header, splitting the reserved `__metadata__`
+/// string map out of the flat object before deserializing the rest as
+/// tensor entries
+///
+/// A plain `HashMap<String, TensorInfo>` can't deserialize the object
+/// directly: `__metadata__`'s value is a string map, not a `TensorInfo`.
+fn parse_header_json(header_json: &str) -> Result<SafeTensorHeader> {
+    let mut raw: serde_json::Map<String, serde_json::Value> = serde_json::from_str(header_json)
+        .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to parse header JSON: {}", e)))?;
+
+    let metadata = match raw.remove("__metadata__") {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to parse __metadata__: {}", e)))?,
+        None => HashMap::new(),
+    };
+
+    let mut tensors = HashMap::with_capacity(raw.len());
+    for (name, value) in raw {
+        let info: TensorInfo = serde_json::from_value(value)
+            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to parse tensor entry {}: {}", name, e)))?;
+        tensors.insert(name, info);
+    }
+
+    Ok(SafeTensorHeader { tensors, metadata })
+}
+
 /// Memory-mapped SafeTensor buffer
 ///
 /// This provides zero-copy access to tensor data stored in SafeTensor format.
@@ -153,8 +220,7 @@ impl SafeTensorBuffer {
         let header_json = std::str::from_utf8(&mmap[8..8 + header_len])
             .map_err(|e| ChatLoopError::MemoryMap(format!("Invalid UTF-8 in header: {}", e)))?;
 
-        let header: SafeTensorHeader = serde_json::from_str(header_json)
-            .map_err(|e| ChatLoopError::MemoryMap(format!("Failed to parse header JSON: {}", e)))?;
+        let header = parse_header_json(header_json)?;
 
         Ok(Self {
             mmap,
@@ -168,6 +234,11 @@ impl SafeTensorBuffer {
         &self.header
     }
 
+    /// Get the `__metadata__` string map (framework tags, quantization params, ...)
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.header.metadata
+    }
+
     /// Get tensor names
     pub fn tensor_names(&self) -> impl Iterator<Item = &String> {
         self.header.tensors.keys()
@@ -188,11 +259,7 @@ impl SafeTensorBuffer {
         let dtype = info.get_dtype()?;
         let data = &self.mmap[data_start..data_end];
 
-        Some(SafeTensorView {
-            data,
-            shape: info.shape.clone(),
-            dtype,
-        })
+        Some(SafeTensorView::new(data, info.shape.clone(), dtype))
     }
 
     /// Get multiple tensors at once (more efficient)
@@ -216,9 +283,90 @@ pub struct SafeTensorView<'a> {
     data: &'a [u8],
     shape: Vec<usize>,
     dtype: TensorDType,
+
+    /// Byte length of one "row" (everything but the outermost dimension)
+    /// in the *original, unsliced* tensor this view was cut from, if this
+    /// view was produced by [`SafeTensorView::sub_view`] along a
+    /// non-outermost dimension. `None` means `data` is fully packed
+    /// row-major per `shape`/`dtype`, as for any ordinary view.
+    row_stride_bytes: Option<usize>,
+
+    /// Byte offset of this view's first column within each
+    /// `row_stride_bytes`-sized row of the original tensor. Always `0`
+    /// unless `row_stride_bytes` is `Some`.
+    col_offset_bytes: usize,
 }
 
 impl<'a> SafeTensorView<'a> {
+    /// Build a view directly from its parts
+    ///
+    /// Used by sibling container-format modules (e.g. `tensor::gguf`) that
+    /// parse their own header but want to hand back the same zero-copy view
+    /// type as [`SafeTensorBuffer::get_tensor`].
+    pub(crate) fn new(data: &'a [u8], shape: Vec<usize>, dtype: TensorDType) -> Self {
+        Self {
+            data,
+            shape,
+            dtype,
+            row_stride_bytes: None,
+            col_offset_bytes: 0,
+        }
+    }
+
+    /// Slice this view along one dimension for tensor-parallel sharding
+    ///
+    /// `dim == 0` selects whole rows: since the backing data is row-major,
+    /// this is a genuine zero-copy byte sub-range of `self`. Slicing the
+    /// innermost dimension of a 2D tensor (`dim == 1`) instead selects a
+    /// column range, which isn't contiguous in row-major layout — the
+    /// returned view keeps referencing all of `self`'s data plus a stride,
+    /// and only [`SafeTensorView::to_f32_vec`] actually copies out the
+    /// `len`-sized share a worker needs.
+    ///
+    /// Returns `None` for block-quantized dtypes (a quantization block can
+    /// span multiple logical elements, so a byte-range slice isn't
+    /// meaningful), for an out-of-range `(start, len)`, for a view that is
+    /// already itself a column sub-view, or for any `dim` other than `0`
+    /// or the innermost dimension of a 2D tensor.
+    pub fn sub_view(&self, dim: usize, start: usize, len: usize) -> Option<SafeTensorView<'a>> {
+        if self.row_stride_bytes.is_some() || self.dtype.block_layout().is_some() {
+            return None;
+        }
+        if dim >= self.shape.len() || start + len > self.shape[dim] {
+            return None;
+        }
+
+        let elem_bytes = self.dtype.size();
+
+        if dim == 0 {
+            let row_bytes: usize = self.shape[1..].iter().product::<usize>() * elem_bytes;
+            let mut shape = self.shape.clone();
+            shape[0] = len;
+
+            Some(SafeTensorView {
+                data: &self.data[start * row_bytes..(start + len) * row_bytes],
+                shape,
+                dtype: self.dtype,
+                row_stride_bytes: None,
+                col_offset_bytes: 0,
+            })
+        } else if dim == self.shape.len() - 1 && self.shape.len() == 2 {
+            let row_bytes = self.shape[1] * elem_bytes;
+            let mut shape = self.shape.clone();
+            shape[1] = len;
+
+            Some(SafeTensorView {
+                data: self.data,
+                shape,
+                dtype: self.dtype,
+                row_stride_bytes: Some(row_bytes),
+                col_offset_bytes: start * elem_bytes,
+            })
+        } else {
+            None
+        }
+    }
+
     /// Get the tensor shape
     pub fn shape(&self) -> &[usize] {
         &self.shape
@@ -252,6 +400,60 @@ impl<'a> SafeTensorView<'a> {
         std::slice::from_raw_parts(self.data.as_ptr() as *const half::f16, self.data.len() / 2)
     }
 
+    /// Materialize this view's elements into a row-major `Vec<f32>`
+    ///
+    /// For a fully-packed view this is equivalent to copying
+    /// `as_f32_slice()`/`as_f16_slice()`. For a column sub-view produced by
+    /// [`SafeTensorView::sub_view`] this is the one point where the
+    /// strided column range is actually read out, so the resulting `Vec`
+    /// is sized to this view's `len()` — a worker's `1/world_size` share —
+    /// never the original tensor's full size.
+    ///
+    /// Returns `None` for dtypes other than `F32`/`F16` (block-quantized
+    /// dtypes never reach here since [`SafeTensorView::sub_view`] already
+    /// refuses to slice them).
+    pub fn to_f32_vec(&self) -> Option<Vec<f32>> {
+        let row_bytes = match self.row_stride_bytes {
+            None => {
+                return Some(match self.dtype {
+                    TensorDType::F32 => unsafe { self.as_f32_slice() }.to_vec(),
+                    TensorDType::F16 => unsafe { self.as_f16_slice() }.iter().map(|x| x.to_f32()).collect(),
+                    _ => return None,
+                });
+            }
+            Some(row_bytes) => row_bytes,
+        };
+
+        if !matches!(self.dtype, TensorDType::F32 | TensorDType::F16) {
+            return None;
+        }
+
+        let elem_bytes = self.dtype.size();
+        let cols = *self.shape.last().unwrap();
+        let col_bytes = cols * elem_bytes;
+        let rows = self.len() / cols.max(1);
+        let mut out = Vec::with_capacity(self.len());
+
+        for r in 0..rows {
+            let row_start = r * row_bytes + self.col_offset_bytes;
+            let row = &self.data[row_start..row_start + col_bytes];
+
+            match self.dtype {
+                TensorDType::F32 => out.extend(
+                    row.chunks_exact(4)
+                        .map(|c| f32::from_le_bytes(c.try_into().unwrap())),
+                ),
+                TensorDType::F16 => out.extend(
+                    row.chunks_exact(2)
+                        .map(|c| half::f16::from_bits(u16::from_le_bytes(c.try_into().unwrap())).to_f32()),
+                ),
+                _ => unreachable!(),
+            }
+        }
+
+        Some(out)
+    }
+
     /// Get the number of elements
     pub fn len(&self) -> usize {
         self.shape.iter().product()
@@ -319,4 +521,70 @@ mod tests {
         let data = unsafe { tensor.as_f32_slice() };
         assert_eq!(data, &[1.0, 2.0, 3.0, 4.0]);
     }
+
+    #[test]
+    fn test_size_bytes_block_quantized() {
+        let q4_0 = TensorInfo {
+            dtype: "Q4_0".to_string(),
+            shape: vec![64],
+            data_offsets: vec![0, 0],
+        };
+        // 64 elements / 32 per block = 2 blocks * 18 bytes/block
+        assert_eq!(q4_0.size_bytes(), 36);
+
+        let q8_0 = TensorInfo {
+            dtype: "Q8_0".to_string(),
+            shape: vec![40], // not a multiple of the 32-element block size
+            data_offsets: vec![0, 0],
+        };
+        // ceil(40 / 32) = 2 blocks * 34 bytes/block
+        assert_eq!(q8_0.size_bytes(), 68);
+    }
+
+    #[test]
+    fn test_sub_view_dim0_is_contiguous_row_slice() {
+        // 3x2 row-major matrix: rows [0,1], [2,3], [4,5]
+        let data: Vec<u8> = (0..6u8)
+            .flat_map(|v| (v as f32).to_le_bytes())
+            .collect();
+        let view = SafeTensorView::new(&data, vec![3, 2], TensorDType::F32);
+
+        let sub = view.sub_view(0, 1, 2).unwrap();
+        assert_eq!(sub.shape(), vec![2, 2]);
+        assert_eq!(sub.to_f32_vec().unwrap(), vec![2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_sub_view_dim1_is_column_slice() {
+        // 2x3 row-major matrix: rows [0,1,2], [3,4,5]
+        let data: Vec<u8> = (0..6u8)
+            .flat_map(|v| (v as f32).to_le_bytes())
+            .collect();
+        let view = SafeTensorView::new(&data, vec![2, 3], TensorDType::F32);
+
+        let sub = view.sub_view(1, 1, 2).unwrap();
+        assert_eq!(sub.shape(), vec![2, 2]);
+        assert_eq!(sub.to_f32_vec().unwrap(), vec![1.0, 2.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_metadata_block_is_split_from_tensor_entries() {
+        let header_json = r#"{
+            "__metadata__": { "format": "pt", "weight.scale": "0.5" },
+            "weight": { "dtype": "F32", "shape": [2, 2], "data_offsets": [0, 16] }
+        }"#;
+
+        let header = parse_header_json(header_json).unwrap();
+        assert_eq!(header.tensors.len(), 1);
+        assert!(header.tensors.contains_key("weight"));
+        assert_eq!(header.metadata.get("format").map(String::as_str), Some("pt"));
+        assert_eq!(header.metadata.get("weight.scale").map(String::as_str), Some("0.5"));
+    }
+
+    #[test]
+    fn test_sub_view_rejects_block_quantized() {
+        let data = vec![0u8; 36];
+        let view = SafeTensorView::new(&data, vec![64], TensorDType::Q4_0);
+        assert!(view.sub_view(0, 0, 32).is_none());
+    }
 }
@@ -0,0 +1,81 @@
+//! Runtime-adjustable throttle on worker CPU/GPU usage, named after
+//! Garage's scrub throttle of the same concept
+//!
+//! After processing a batch that took `duration`, the inference loop sleeps
+//! for `duration * tranquility` before pulling the next one, so a
+//! tranquility of 2 keeps the worker busy at most one-third of the time.
+//! Unlike the rest of `WorkerConfig`, this value is also adjustable at
+//! runtime through the admin `/control/tranquility` endpoint (see
+//! `crate::admin`), and persisted to `WorkerConfig::tranquility_state_path`
+//! so a restart resumes at the operator's last setting rather than
+//! reverting to the config file's default.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+/// On-disk representation of the last value [`TranquilityControl::set`] wrote
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TranquilityState {
+    tranquility: f64,
+}
+
+/// Shared, runtime-adjustable tranquility multiplier
+///
+/// The current value is stored as the bit pattern of an `f64` inside an
+/// `AtomicU64`, the same trick `BatchScheduler` uses for its latency EMA,
+/// since there's no stable `AtomicF64`.
+pub struct TranquilityControl {
+    bits: AtomicU64,
+    state_path: Option<PathBuf>,
+}
+
+impl TranquilityControl {
+    /// Create a control seeded from `state_path` if a persisted value
+    /// exists there, falling back to `default` (`batching.tranquility`)
+    /// otherwise
+    pub fn new(default: f64, state_path: Option<PathBuf>) -> Self {
+        let initial = state_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str::<TranquilityState>(&contents).ok())
+            .map(|state| state.tranquility)
+            .unwrap_or(default);
+
+        Self {
+            bits: AtomicU64::new(initial.to_bits()),
+            state_path,
+        }
+    }
+
+    /// Current tranquility multiplier
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+
+    /// Update the tranquility multiplier and persist it to `state_path`
+    /// (if configured), so the new value survives a restart
+    pub fn set(&self, value: f64) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+
+        let Some(path) = &self.state_path else {
+            return;
+        };
+
+        match serde_json::to_string(&TranquilityState { tranquility: value }) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(path, json) {
+                    warn!("Failed to persist tranquility state to {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize tranquility state: {}", e),
+        }
+    }
+
+    /// How long the inference loop should sleep after a batch that took
+    /// `batch_duration`, at the current tranquility multiplier
+    pub fn throttle_delay(&self, batch_duration: Duration) -> Duration {
+        batch_duration.mul_f64(self.get().max(0.0))
+    }
+}